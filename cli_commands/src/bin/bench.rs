@@ -0,0 +1,181 @@
+use std::{
+    env,
+    hint::black_box,
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use my_rusttools::{
+    GCacher,
+    render_table,
+    TableFormat,
+    factories::sieve_primes,
+    traits::{HumanFormat, HumanizeDuration},
+};
+use purple_blox::pool::ThreadPool;
+
+const USAGE: &str = "usage: bench [--iterations N] [Text: Filter]";
+
+/// A measurement of the workspace's performance-sensitive paths.
+struct Bench {
+    name: String,
+    /// Runs the measured code once, returning how long the part measured took,
+    /// so set up can be left out.
+    run: Box<dyn Fn() -> Duration>,
+}
+
+impl Bench {
+    fn new(name: impl Into<String>, run: impl Fn() -> Duration + 'static) -> Self {
+        Self {
+            name: name.into(),
+            run: Box::new(run),
+        }
+    }
+
+    /// Creates a measurement timing the whole of `f`.
+    fn timed(name: impl Into<String>, f: impl Fn() + 'static) -> Self {
+        Self::new(name, move||{
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+    }
+}
+
+/// Returns the primes up to `upper_bound`, by trial division,
+/// as the baseline the sieve is compared against.
+fn trial_division_primes(upper_bound: usize) -> impl Iterator<Item = usize> {
+    (2..=upper_bound).filter(|x|(2..).take_while(|i|i * i <= *x).all(|i|x % i != 0))
+}
+
+fn benches() -> Vec<Bench> {
+    let mut benches = Vec::new();
+
+    for x in [1_000, 100_000, 1_000_000] {
+        benches.push(Bench::timed(format!("sieve_primes({})", x), move||{
+            black_box(sieve_primes(black_box(x)).count());
+        }));
+    }
+
+    for x in [1_000, 100_000] {
+        benches.push(Bench::timed(format!("trial_division_primes({})", x), move||{
+            black_box(trial_division_primes(black_box(x)).count());
+        }));
+    }
+
+    benches.push(Bench::timed("GCacher 10000 misses", ||{
+        let mut cacher = GCacher::new(|x: &usize|x * x);
+
+        for x in 0..10_000 {
+            black_box(cacher.value_from(x));
+        }
+    }));
+
+    benches.push(Bench::new("GCacher 10000 hits", ||{
+        let mut cacher = GCacher::new(|x: &usize|x * x);
+
+        for x in 0..10_000 {
+            cacher.value_from(x);
+        }
+
+        let start = Instant::now();
+
+        for x in 0..10_000 {
+            black_box(cacher.value_from(x));
+        }
+
+        start.elapsed()
+    }));
+
+    for threads in [1, 2, 4] {
+        benches.push(Bench::new(format!("ThreadPool({}) 10000 jobs", threads), move||{
+            let pool = ThreadPool::new(threads).unwrap();
+            let done = Arc::new(AtomicUsize::new(0));
+            let start = Instant::now();
+
+            for _ in 0..10_000 {
+                let done = Arc::clone(&done);
+                pool.execute(move||{
+                    done.fetch_add(1, Ordering::Relaxed);
+                }).unwrap();
+            }
+
+            drop(pool); // Waits for the queued jobs to finish.
+            assert_eq!(10_000, done.load(Ordering::Relaxed));
+            start.elapsed()
+        }));
+    }
+
+    benches
+}
+
+fn parse(mut args: impl Iterator<Item = String>) -> Result<(usize, Option<String>), String> {
+    let mut iterations = 10;
+    let mut filter = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" => iterations = args.next()
+                .ok_or_else(||String::from("expected a value after: --iterations"))?
+                .parse()
+                .map_err(|err|format!("invalid value: --iterations: {}", err))?,
+            x if x.starts_with('-') => return Err(format!("unknown flag: {}", x)),
+            _ if filter.is_none() => filter = Some(arg),
+            x => return Err(format!("unexpected argument: {}", x)),
+        }
+    }
+
+    match iterations {
+        0 => Err(String::from("invalid value: --iterations: must be at least 1")),
+        _ => Ok((iterations, filter)),
+    }
+}
+
+fn main() {
+    let (iterations, filter) = parse(env::args().skip(1)) // Parses the command arguments following the binary path.
+        .unwrap_or_else(|err|{
+            eprintln!("{}\n\narguments cannot be parsed: {}", USAGE, err);
+            process::exit(1); // Prints usage and error, then exits the process, if the arguments can't be parsed.
+        });
+
+    let rows = benches()
+        .into_iter()
+        .filter(|x|filter.as_ref().map_or(true, |filter|x.name.contains(filter.as_str())))
+        .map(|x|{
+            let mut times = (0..iterations).map(|_|(x.run)()).collect::<Vec<_>>();
+            times.sort();
+
+            [
+                x.name,
+                times[0].humanize(HumanFormat::Short),
+                times[times.len() / 2].humanize(HumanFormat::Short),
+                times[times.len() - 1].humanize(HumanFormat::Short),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    print!("{}", render_table(&["benchmark", "min", "median", "max"], rows, TableFormat::Ascii));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trial_division_matches_sieve() {
+        assert!(trial_division_primes(1_000).eq(sieve_primes(1_000)));
+    }
+
+    #[test]
+    fn benches_run() {
+        benches()
+            .iter()
+            .filter(|x|!x.name.contains("1000000"))
+            .for_each(|x|{
+                (x.run)();
+            });
+    }
+}
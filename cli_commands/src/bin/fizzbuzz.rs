@@ -1,21 +1,178 @@
 use std::{
     env,
+    io::{self, BufWriter, Write},
     process,
 };
-use my_rusttools::factories::fizzbuzz;
+use cli_commands::config::CliConfig;
+use my_rusttools::factories::FizzBuzzBuilder;
+
+const USAGE: &str = "usage: fizzbuzz [--rules N=WORD,...] [--start N] [--end N] [--separator TEXT] [Num: Whole number]";
+
+/// The sequence fizzbuzz was asked to print,
+/// and how to separate it.
+#[derive(Debug)]
+struct Options {
+    builder: FizzBuzzBuilder,
+    count: Option<usize>,
+    separator: String,
+}
+
+impl Options {
+    /// Parses `args`, with defaults for the rules and separator
+    /// taken from the `[fizzbuzz]` section of the config file.
+    fn new(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let config = CliConfig::load()
+            .map_err(|err|err.to_string())?;
+        let section = config.section("fizzbuzz");
+        let rules = section.string("rules")
+            .map_err(|err|err.to_string())?;
+        let separator = section.string("separator")
+            .map_err(|err|err.to_string())?;
+
+        Self::parse(args, rules, separator)
+    }
+
+    fn parse(mut args: impl Iterator<Item = String>, rules: Option<String>, separator: Option<String>) -> Result<Self, String> {
+        let mut rules = rules;
+        let mut separator = separator.unwrap_or_else(||String::from("\n"));
+        let mut start = None;
+        let mut end = None;
+        let mut count = None;
+
+        while let Some(arg) = args.next() {
+            let (name, attached) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+                _ => (arg, None),
+            };
+            let mut value = ||attached.clone()
+                .or_else(||args.next())
+                .ok_or_else(||format!("expected a value after: {}", name));
+
+            match name.as_str() {
+                "--rules" => rules = Some(value()?),
+                "--start" => start = Some(parse_number(&name, &value()?)?),
+                "--end" => end = Some(parse_number(&name, &value()?)?),
+                "--separator" => separator = unescape(&value()?),
+                x if x.starts_with('-') => return Err(format!("unknown flag: {}", x)),
+                x if count.is_none() => count = Some(parse_number("count", x)?),
+                x => return Err(format!("unexpected argument: {}", x)),
+            }
+        }
+
+        if count.is_none() && end.is_none() {
+            return Err(String::from("expected a count, or --end"));
+        }
+
+        let mut builder = match rules {
+            Some(rules) => parse_rules(&rules)?,
+            None => FizzBuzzBuilder::classic(),
+        };
+
+        if let Some(start) = start {
+            builder = builder.start(start);
+        }
+
+        if let Some(end) = end {
+            builder = builder.end(end);
+        }
+
+        Ok(Self {
+            builder,
+            count,
+            separator,
+        })
+    }
+}
+
+/// Parses rules in the form `3=Fizz,5=Buzz`.
+fn parse_rules(rules: &str) -> Result<FizzBuzzBuilder, String> {
+    rules.split(',')
+        .try_fold(FizzBuzzBuilder::new(), |builder, x|{
+            let (divisor, word) = x.split_once('=')
+                .ok_or_else(||format!("invalid rule: {}", x))?;
+
+            match divisor.trim().parse() {
+                Ok(divisor) if divisor != 0 => Ok(builder.rule(divisor, word)),
+                _ => Err(format!("invalid rule: {}", x)),
+            }
+        })
+}
+
+fn parse_number(name: &str, value: &str) -> Result<usize, String> {
+    value.trim()
+        .parse()
+        .map_err(|err|format!("invalid value: {} {}: {}", name, value, err))
+}
+
+/// Replaces `\n` and `\t` in separators with the characters they stand for.
+fn unescape(separator: &str) -> String {
+    separator.replace("\\n", "\n")
+        .replace("\\t", "\t")
+}
 
 fn main() {
-    let iterations: usize = env::args()
-        .skip(1)
-        .collect::<String>() // Takes the command arguments following the binary path, and collects it as a String.
-        .trim()
-        .parse() // Trims the string, and attempts to parse it as a `usize`.
+    let options = Options::new(env::args().skip(1)) // Parses the command arguments following the binary path.
         .unwrap_or_else(|err|{
-            eprintln!("usage: fizzbuzz <Num: Whole number>\n\narguments cannot be parsed: {}", err);
-            process::exit(1); // Prints usage and error, then exits the process, if the value can't be parsed.
+            eprintln!("{}\n\narguments cannot be parsed: {}", USAGE, err);
+            process::exit(1); // Prints usage and error, then exits the process, if the arguments can't be parsed.
         });
 
-    fizzbuzz()
-        .take(iterations)
-        .for_each(|x|println!("{}", x));
-}
\ No newline at end of file
+    let sequence = options.builder
+        .build()
+        .take(options.count.unwrap_or(usize::MAX));
+    let mut stdout = BufWriter::new(io::stdout().lock());
+
+    let written = sequence.enumerate()
+        .try_for_each(|(i, x)|match i {
+            0 => write!(stdout, "{}", x),
+            _ => write!(stdout, "{}{}", options.separator, x),
+        })
+        .and_then(|_|writeln!(stdout))
+        .and_then(|_|stdout.flush());
+
+    if let Err(err) = written {
+        eprintln!("output writing error: {}", err);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(args: &[&str]) -> Result<Vec<String>, String> {
+        let options = Options::parse(args.iter().map(|x|x.to_string()), None, None)?;
+
+        Ok(options.builder.build().take(options.count.unwrap_or(usize::MAX)).collect())
+    }
+
+    #[test]
+    fn classic_count() {
+        assert_eq!(vec!["1", "2", "Fizz", "4", "Buzz"], sequence(&["5"]).unwrap());
+    }
+
+    #[test]
+    fn custom_rules_and_range() {
+        assert_eq!(
+            vec!["Fizz", "Buzz", "11", "Fizz", "13", "Qux"],
+            sequence(&["--rules", "3=Fizz,5=Buzz,7=Qux", "--start=9", "--end", "14"]).unwrap(),
+        );
+        assert_eq!(vec!["4", "5"], sequence(&["--start", "4", "--end", "10", "2", "--rules=11=Eleven"]).unwrap());
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert!(sequence(&[]).is_err());
+        assert!(sequence(&["--rules", "0=Never", "5"]).is_err());
+        assert!(sequence(&["--rules", "Fizz", "5"]).is_err());
+        assert!(sequence(&["--end"]).is_err());
+        assert!(sequence(&["--step", "2", "5"]).is_err());
+        assert!(sequence(&["5", "6"]).is_err());
+    }
+
+    #[test]
+    fn separators() {
+        let options = Options::parse(["3", "--separator", "\\t"].into_iter().map(String::from), None, Some(String::from(", "))).unwrap();
+        assert_eq!("\t", options.separator);
+    }
+}
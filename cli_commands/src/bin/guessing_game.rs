@@ -0,0 +1,280 @@
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    fmt,
+    fs,
+    io,
+    ops::{ControlFlow, RangeInclusive},
+    path::{Path, PathBuf},
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use cli_commands::config::{self, CliConfig};
+use my_rusttools::{
+    ParseStdinExtended,
+    style::{self, Colour},
+};
+
+/// The range a secret is chosen from,
+/// and how many guesses there are to find it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Difficulty {
+    name: &'static str,
+    range: (u32, u32),
+    attempts: Option<u32>,
+}
+
+impl Difficulty {
+    fn range(&self) -> RangeInclusive<u32> {
+        self.range.0..=self.range.1
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} to {}", self.name, self.range.0, self.range.1)?;
+
+        match self.attempts {
+            Some(attempts) => write!(f, ", in {} guesses", attempts),
+            None => write!(f, ", in as many guesses as it takes"),
+        }
+    }
+}
+
+const DIFFICULTIES: [Difficulty; 4] = [
+    Difficulty {
+        name: "Easy",
+        range: (1, 10),
+        attempts: None,
+    },
+    Difficulty {
+        name: "Normal",
+        range: (1, 100),
+        attempts: None,
+    },
+    Difficulty {
+        name: "Hard",
+        range: (1, 100),
+        attempts: Some(7),
+    },
+    Difficulty {
+        name: "Expert",
+        range: (1, 1000),
+        attempts: Some(10),
+    },
+];
+
+/// The games played at a difficulty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Score {
+    played: u32,
+    won: u32,
+    /// The fewest guesses a game was won in.
+    best: Option<u32>,
+    /// The guesses made in every game won, for their average.
+    total_guesses: u32,
+}
+
+impl Score {
+    fn record(&mut self, guesses: Option<u32>) {
+        self.played += 1;
+
+        if let Some(guesses) = guesses {
+            self.won += 1;
+            self.total_guesses += guesses;
+            self.best = Some(self.best.map_or(guesses, |x|x.min(guesses)));
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "won {} of {}", self.won, self.played)?;
+
+        match self.best {
+            Some(best) => write!(f, ", best {} guesses, average {:.1}", best, self.total_guesses as f64 / self.won as f64),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The scores for each difficulty, by name,
+/// kept in a JSON file between games.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Scoreboard(BTreeMap<String, Score>);
+
+impl Scoreboard {
+    /// Loads the scoreboard at `path`,
+    /// or returns an empty one if there isn't one.
+    fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|err|io::Error::new(io::ErrorKind::InvalidData, err)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|err|io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        fs::write(path, json)
+    }
+
+    fn score(&mut self, difficulty: &Difficulty) -> &mut Score {
+        self.0.entry(difficulty.name.to_string()).or_default()
+    }
+}
+
+/// Returns where the scoreboard is kept:
+/// the `scoreboard` setting of the `[guessing_game]` section,
+/// or `scores.json` beside the config file.
+fn scoreboard_path() -> Option<PathBuf> {
+    let configured = CliConfig::load()
+        .and_then(|x|x.section("guessing_game").string("scoreboard"))
+        .unwrap_or_else(|err|{
+            eprintln!("{}", err);
+            None
+        });
+
+    configured.map(PathBuf::from)
+        .or_else(||Some(config::default_path()?.with_file_name("scores.json")))
+}
+
+/// How a game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// The secret was found, in this many guesses.
+    Won(u32),
+    /// The guesses ran out.
+    Lost,
+    /// Input ended before the game did.
+    Abandoned,
+}
+
+/// Plays a game, returning how it ended.
+fn play(uinp: &ParseStdinExtended, difficulty: &Difficulty) -> Outcome {
+    let secret = rand::thread_rng().gen_range(difficulty.range());
+    let mut guesses = 0;
+    println!("Guess the number!");
+
+    loop {
+        if difficulty.attempts.is_some_and(|x|guesses >= x) {
+            println!("{}", style::colour(format!("Out of guesses, it was {}!", secret), Colour::Red));
+            return Outcome::Lost;
+        }
+
+        let guess = loop {
+            let guess = uinp.try_read_line_until_parsed(
+                ||{
+                    println!("Please enter a number from {} to {},", difficulty.range.0, difficulty.range.1);
+                    ControlFlow::Continue(())
+                },
+                |err|eprintln!("invalid input: {err}")
+            );
+
+            match guess {
+                Some(guess) if difficulty.range().contains(&guess) => break guess,
+                Some(_) => (),
+                None => return Outcome::Abandoned,
+            }
+        };
+
+        guesses += 1;
+        println!("Your guess: {}", guess);
+
+        match guess.cmp(&secret) {
+            Ordering::Less => println!("Too small!"),
+            Ordering::Greater => println!("Too large!"),
+            Ordering::Equal => {
+                println!("{}", style::colour(format!("You win, in {} guesses!", guesses), Colour::Green));
+                return Outcome::Won(guesses);
+            }
+        }
+    }
+}
+
+fn main() {
+    let uinp = ParseStdinExtended::new();
+    let path = scoreboard_path();
+    let mut scoreboard = path.as_deref()
+        .map(Scoreboard::load)
+        .transpose()
+        .unwrap_or_else(|err|{
+            eprintln!("scoreboard could not be read: {}", err);
+            None
+        })
+        .unwrap_or_default();
+
+    // The end of input ends the session, as declining to play again would,
+    // a game it cuts short going unrecorded.
+    loop {
+        let selected = uinp.try_select_from(&DIFFICULTIES, ||{
+            println!("Please choose a difficulty, by its number,");
+            ControlFlow::Continue(())
+        });
+        let Some(difficulty) = selected.map(|x|&DIFFICULTIES[x]) else {
+            break;
+        };
+
+        let guesses = match play(&uinp, difficulty) {
+            Outcome::Won(guesses) => Some(guesses),
+            Outcome::Lost => None,
+            Outcome::Abandoned => break,
+        };
+        let score = scoreboard.score(difficulty);
+        score.record(guesses);
+        println!("{}: {}", difficulty.name, score);
+
+        let again = uinp.try_read_line_until_bool(||{
+            println!("Play again? y(es)/n(o),");
+            ControlFlow::Continue(())
+        });
+
+        if again != Some(true) {
+            break;
+        }
+
+        print!("{}", style::clear_screen());
+    }
+
+    if let Some(Err(err)) = path.as_deref().map(|x|scoreboard.save(x)) {
+        eprintln!("scoreboard could not be saved: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn records_scores() {
+        let mut scoreboard = Scoreboard::default();
+        let score = scoreboard.score(&DIFFICULTIES[1]);
+        score.record(Some(6));
+        score.record(None);
+        score.record(Some(4));
+
+        assert_eq!("won 2 of 3, best 4 guesses, average 5.0", score.to_string());
+        assert_eq!("won 0 of 0", Score::default().to_string());
+    }
+
+    #[test]
+    fn scoreboard_round_trips() {
+        let path = env::temp_dir().join(format!("guessing_game-{}.json", std::process::id()));
+        let mut scoreboard = Scoreboard::default();
+        scoreboard.score(&DIFFICULTIES[0]).record(Some(3));
+
+        assert_eq!(Scoreboard::default(), Scoreboard::load(&path).unwrap());
+        scoreboard.save(&path).unwrap();
+        assert_eq!(scoreboard, Scoreboard::load(&path).unwrap());
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,301 @@
+//! Splitting minigrep's arguments into flags, options and their values,
+//! and the errors parsing them can give.
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+/// A summary of minigrep's arguments.
+pub const USAGE: &str = "usage: minigrep [-iFrncvq] [-m N] [-A N] [-B N] [-C N] [--color=WHEN] [--format FORMAT] [--threads N] [--replace TEMPLATE [--in-place]] <Text: RegEx> [Text: File Path]...";
+
+/// What minigrep's arguments do, printed by `--help`.
+pub const HELP: &str = "\
+Searches files, or standard input, for lines matching a Regular Expression.
+
+With no file paths, or `-`, standard input is searched.
+
+  -i                 match case-insensitively, as does setting MINIGREP_CASE_INSENSITIVE
+  -F                 match the query as a literal string
+  -r                 search directories recursively
+  -n                 prefix lines with their line numbers
+  -c                 print only the number of matching lines
+  -v                 match lines which don't contain the query
+  -q                 print nothing, exiting at the first match
+  -m N               stop searching each file after N matches
+  -A N               print N lines of context after matches
+  -B N               print N lines of context before matches
+  -C N               print N lines of context around matches
+  --color[=WHEN]     highlight matches: auto, always or never
+  --format FORMAT    write results as text, json or csv
+  --threads N        search files on N threads
+  --replace TEMPLATE print matching lines with matches replaced, using $1 for groups
+  --in-place         rewrite the files with matches replaced, instead of printing them
+  --help             print this help
+  --version          print the version
+
+Flags may be combined, as in `-nA3`, and end at a `--` argument.";
+
+/// An argument, split from those passed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Arg {
+    /// A flag, as in `-i`, or one of a group, as in `-iF`.
+    Short(char),
+    /// An option, as in `--format`, without its value.
+    Long(String),
+    /// Any other argument, such as the query or a path,
+    /// or any argument after `--`.
+    Positional(String),
+}
+
+/// Splits arguments into [`Arg`]s, in order,
+/// leaving the options to ask for their values.
+pub struct Parser<I> {
+    args: I,
+    /// The rest of a group of flags, as in `-iF`.
+    shorts: String,
+    /// The value attached to an option, as in `--format=json`.
+    attached: Option<String>,
+    /// The last flag or option returned, for errors.
+    last: String,
+    flags_ended: bool,
+}
+
+impl<I: Iterator<Item = String>> Parser<I> {
+    pub fn new(args: I) -> Self {
+        Self {
+            args,
+            shorts: String::new(),
+            attached: None,
+            last: String::new(),
+            flags_ended: false,
+        }
+    }
+
+    /// Returns the next argument, or `None` if there are none left.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the last option was given a value with `=`
+    /// which wasn't asked for.
+    pub fn next(&mut self) -> Result<Option<Arg>, ParseError> {
+        if let Some(value) = self.attached.take() {
+            return Err(ParseError::new(ParseErrorKind::UnexpectedValue, format!("{}={}", self.last, value)));
+        }
+
+        if !self.shorts.is_empty() {
+            let flag = self.shorts.remove(0);
+            self.last = format!("-{}", flag);
+            return Ok(Some(Arg::Short(flag)));
+        }
+
+        let arg = match self.args.next() {
+            Some(arg) => arg,
+            None => return Ok(None),
+        };
+
+        if self.flags_ended || arg == "-" || !arg.starts_with('-') {
+            return Ok(Some(Arg::Positional(arg)));
+        }
+
+        if arg == "--" {
+            self.flags_ended = true;
+            return self.next();
+        }
+
+        match arg.strip_prefix("--") {
+            Some(long) => {
+                let (name, value) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(value.to_string())),
+                    None => (long, None),
+                };
+
+                self.attached = value;
+                self.last = format!("--{}", name);
+                Ok(Some(Arg::Long(name.to_string())))
+            },
+            None => {
+                self.shorts = arg[1..].to_string();
+                self.next()
+            },
+        }
+    }
+
+    /// Returns the value of the last flag or option:
+    /// the rest of its argument, as in `-A3` or `--format=json`,
+    /// or the next argument, as in `-A 3` or `--format json`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there are no arguments left.
+    pub fn value(&mut self) -> Result<String, ParseError> {
+        if let Some(value) = self.attached.take() {
+            return Ok(value);
+        }
+
+        match self.shorts.is_empty() {
+            true => self.args
+                .next()
+                .ok_or_else(||ParseError::new(ParseErrorKind::MissingValue, &self.last)),
+            false => Ok(std::mem::take(&mut self.shorts)),
+        }
+    }
+
+    /// Returns the value attached to the last option with `=`, if there is one,
+    /// for options whose values are optional.
+    pub fn optional_value(&mut self) -> Option<String> {
+        self.attached.take()
+    }
+
+    /// Parses the value of the last flag or option, as with [`Parser::value`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there are no arguments left,
+    /// or the value can't be parsed.
+    pub fn parse_value<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let value = self.value()?;
+
+        value.parse()
+            .map_err(|_|self.invalid(&value))
+    }
+
+    /// Returns an error for `value` being invalid for the last flag or option.
+    pub fn invalid(&self, value: &str) -> ParseError {
+        ParseError::new(ParseErrorKind::InvalidValue, format!("{} {}", self.last, value))
+    }
+
+    /// Returns an error for the last flag or option being unknown.
+    pub fn unknown(&self) -> ParseError {
+        ParseError::new(ParseErrorKind::UnknownFlag, &self.last)
+    }
+}
+
+/// The error type for parsing minigrep's arguments.
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    detail: String,
+}
+
+/// A list specifying the categories of [`ParseError`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// `--help` was passed, so the help should be printed instead.
+    Help,
+    /// `--version` was passed, so the version should be printed instead.
+    Version,
+    /// No query was passed.
+    MissingQuery,
+    /// A flag or option minigrep doesn't have was passed.
+    UnknownFlag,
+    /// A flag or option was passed without its value.
+    MissingValue,
+    /// A flag or option was passed a value it can't use.
+    InvalidValue,
+    /// An option which doesn't take a value was passed one.
+    UnexpectedValue,
+    /// The query wasn't a valid Regular Expression.
+    InvalidRegex,
+    /// An option was passed without another it needs.
+    MissingOption,
+    /// The config file couldn't be loaded, or had an invalid setting.
+    InvalidConfig,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, detail: impl fmt::Display) -> Self {
+        Self {
+            kind,
+            detail: detail.to_string(),
+        }
+    }
+
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self.kind {
+            ParseErrorKind::Help => return "help requested".fmt(f),
+            ParseErrorKind::Version => return "version requested".fmt(f),
+            ParseErrorKind::MissingQuery => return "expected a query".fmt(f),
+            ParseErrorKind::UnknownFlag => "unknown flag",
+            ParseErrorKind::MissingValue => "expected a value after",
+            ParseErrorKind::InvalidValue => "invalid value",
+            ParseErrorKind::UnexpectedValue => "unexpected value",
+            ParseErrorKind::InvalidRegex => "invalid query",
+            ParseErrorKind::MissingOption => "missing option",
+            ParseErrorKind::InvalidConfig => return self.detail.fmt(f),
+        };
+
+        write!(f, "{}: {}", description, self.detail)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parser(args: &[&str]) -> Parser<impl Iterator<Item = String>> {
+        Parser::new(args.iter().map(|x|x.to_string()).collect::<Vec<_>>().into_iter())
+    }
+
+    #[test]
+    fn splits_arguments() {
+        let mut parser = parser(&["-iF", "query", "--format", "json", "my file.txt", "-", "--", "-n"]);
+        let mut args = Vec::new();
+
+        while let Some(arg) = parser.next().unwrap() {
+            if arg == Arg::Long(String::from("format")) {
+                assert_eq!("json", parser.value().unwrap());
+            }
+            args.push(arg);
+        }
+
+        let expected = [
+            Arg::Short('i'),
+            Arg::Short('F'),
+            Arg::Positional(String::from("query")),
+            Arg::Long(String::from("format")),
+            Arg::Positional(String::from("my file.txt")),
+            Arg::Positional(String::from("-")),
+            Arg::Positional(String::from("-n")),
+        ];
+        assert_eq!(expected[..], args[..]);
+    }
+
+    #[test]
+    fn values() {
+        let mut parser = parser(&["-nA3", "-B", "2", "--threads=4", "--color", "--format"]);
+
+        assert_eq!(Some(Arg::Short('n')), parser.next().unwrap());
+        assert_eq!(Some(Arg::Short('A')), parser.next().unwrap());
+        assert_eq!(3, parser.parse_value::<usize>().unwrap());
+        assert_eq!(Some(Arg::Short('B')), parser.next().unwrap());
+        assert_eq!("2", parser.value().unwrap());
+        assert_eq!(Some(Arg::Long(String::from("threads"))), parser.next().unwrap());
+        assert_eq!("4", parser.value().unwrap());
+        assert_eq!(Some(Arg::Long(String::from("color"))), parser.next().unwrap());
+        assert_eq!(None, parser.optional_value());
+        assert_eq!(Some(Arg::Long(String::from("format"))), parser.next().unwrap());
+        assert_eq!(ParseErrorKind::MissingValue, *parser.value().unwrap_err().kind());
+    }
+
+    #[test]
+    fn errors() {
+        let mut unexpected = parser(&["--help=yes"]);
+        unexpected.next().unwrap();
+        assert_eq!(ParseErrorKind::UnexpectedValue, *unexpected.next().unwrap_err().kind());
+
+        let mut invalid = parser(&["-A", "x"]);
+        invalid.next().unwrap();
+        let err = invalid.parse_value::<usize>().unwrap_err();
+        assert_eq!(ParseErrorKind::InvalidValue, *err.kind());
+        assert_eq!("invalid value: -A x", err.to_string());
+    }
+}
@@ -0,0 +1,1225 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs,
+    io::{self, BufRead, BufReader, BufWriter, IsTerminal, Write},
+    num::NonZeroUsize,
+    ops::Range,
+    path::Path,
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+use cli_commands::config::CliConfig;
+
+use crate::{
+    args::{Arg, ParseError, ParseErrorKind, Parser},
+    output::{Csv, Format, Json, OutputFormatter, Recorder, Silent, Text},
+};
+
+/// Config struct for searching for lines in files,
+/// or standard input, containing the specified query.
+/// 
+/// # Examples
+/// ```
+/// let config = grep::Config::new(env::args().skip(1)) // Attempts to construct a new minigrep config struct, based on the command arguments minus the first file path argument.
+///     .unwrap_or_else(|err| {
+///         eprintln!("{}\n\narguments cannot be parsed: {}", args::USAGE, err);
+///         process::exit(2); // Prints usage and error, then exits the process, if a `Config` struct can't be constructed.
+///     });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Config {
+    query: regex::Regex,
+    paths: Vec<String>,
+    recursive: bool,
+    line_numbers: bool,
+    count: bool,
+    invert: bool,
+    before: usize,
+    after: usize,
+    colour: Colour,
+    format: Format,
+    threads: Option<NonZeroUsize>,
+    quiet: bool,
+    max_count: Option<usize>,
+    replace: Option<String>,
+    in_place: bool,
+}
+
+/// When matches are highlighted, chosen with `--color`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    /// Highlights matches when writing to a terminal.
+    Auto,
+    Always,
+    Never,
+}
+
+impl std::str::FromStr for Colour {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Colour::Auto),
+            "always" => Ok(Colour::Always),
+            "never" => Ok(Colour::Never),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A line read by a search,
+/// with its 1-based line number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub line_no: usize,
+    pub text: String,
+}
+
+/// A line yielded by a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    /// A line selected by the query.
+    Match(Match),
+    /// A line near a selected one, printed with `-A`, `-B` or `-C`.
+    Context(Match),
+    /// A gap between groups of context, printed as `--`.
+    Break,
+}
+
+/// What happened in a run of minigrep,
+/// which its exit code reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Whether any line was selected.
+    pub matched: bool,
+    /// Whether any file couldn't be searched.
+    pub failed: bool,
+}
+
+impl Summary {
+    /// Returns the exit code grep would:
+    /// `2` if any file couldn't be searched,
+    /// `0` if any line was selected,
+    /// or `1` if none were.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Summary { failed: true, .. } => 2,
+            Summary { matched: true, .. } => 0,
+            _ => 1,
+        }
+    }
+}
+
+impl Config {
+    /// Attempts to create a new `Config` struct,
+    /// with a query based on the arguments passed.
+    /// 
+    /// Defaults for `case_insensitive` and `color` are read from
+    /// the `[minigrep]` section of the shared config file, or the environment,
+    /// as described in [`cli_commands::config`].
+    /// 
+    /// Any number of file paths may follow the query,
+    /// with none, or `-`, searching standard input.
+    /// Flags may come before or after the query and paths,
+    /// and be combined, as in `-iF`, until a `--` argument:
+    /// 
+    /// * `-i` matches case-insensitively,
+    ///   as does setting `MINIGREP_CASE_INSENSITIVE`.
+    /// * `-F` matches the query as a literal string,
+    ///   rather than a Regular Expression.
+    /// * `-r` searches directories recursively,
+    ///   rather than skipping them.
+    /// * `-n` prefixes lines with their line numbers.
+    /// * `-c` prints only the number of matching lines.
+    /// * `-v` matches lines which don't contain the query.
+    /// * `-q` prints nothing, stopping at the first match.
+    /// * `-m N` stops searching each file after `N` matches.
+    /// * `--replace TEMPLATE` prints matching lines with their matches replaced,
+    ///   where `$1` or `${name}` in the template refer to capture groups.
+    /// * `--in-place` rewrites the files with their matches replaced,
+    ///   rather than printing anything, and needs `--replace`.
+    /// 
+    /// # Errors
+    /// 
+    /// Will return `Err` if the command had no arguments,
+    /// an unknown flag, a flag without a valid value,
+    /// or did not provide a valid Regular Expression,
+    /// or if the config file couldn't be loaded, or had an invalid setting.
+    /// `--help` and `--version` are returned as errors too,
+    /// of the kinds [`ParseErrorKind::Help`] and [`ParseErrorKind::Version`],
+    /// as nothing should be searched.
+    /// 
+    /// # Examples
+    /// ```
+    /// let config = grep::Config::new(env::args().skip(1)) // Attempts to construct a new minigrep config struct, based on the command arguments minus the first file path argument.
+    ///     .unwrap_or_else(|err| {
+    ///         eprintln!("{}\n\narguments cannot be parsed: {}", args::USAGE, err);
+    ///         process::exit(2); // Prints usage and error, then exits the process, if a `Config` struct can't be constructed.
+    ///     });
+    /// ```
+    pub fn new(args: impl Iterator<Item = String>) -> Result<Self, ParseError> {
+        let invalid = |err|ParseError::new(ParseErrorKind::InvalidConfig, err);
+        let settings = CliConfig::load()
+            .map_err(invalid)?;
+        let section = settings.section("minigrep");
+
+        let case_insensitive = section.bool("case_insensitive")
+            .map_err(invalid)?
+            .unwrap_or(false);
+        let colour = match section.string("color").map_err(invalid)? {
+            Some(x) => x.parse()
+                .map_err(|_|ParseError::new(ParseErrorKind::InvalidConfig, format!("invalid setting: color = {}", x)))?,
+            None => Colour::Auto,
+        };
+
+        Self::parse(args, case_insensitive, colour)
+    }
+
+    /// Parses the arguments, with `case_insensitive` and `colour` as the defaults,
+    /// before any `-i` or `--color` flag.
+    fn parse(args: impl Iterator<Item = String>, mut case_insensitive: bool, mut colour: Colour) -> Result<Self, ParseError> {
+        let mut fixed_strings = false;
+        let mut recursive = false;
+        let mut line_numbers = false;
+        let mut count = false;
+        let mut invert = false;
+        let mut before = None;
+        let mut after = None;
+        let mut context = 0;
+        let mut format = Format::Text;
+        let mut threads = None;
+        let mut quiet = false;
+        let mut max_count = None;
+        let mut replace = None;
+        let mut in_place = false;
+        let mut positional = Vec::new();
+        let mut parser = Parser::new(args);
+
+        while let Some(arg) = parser.next()? {
+            match arg {
+                Arg::Short('i') => case_insensitive = true,
+                Arg::Short('F') => fixed_strings = true,
+                Arg::Short('r') => recursive = true,
+                Arg::Short('n') => line_numbers = true,
+                Arg::Short('c') => count = true,
+                Arg::Short('v') => invert = true,
+                Arg::Short('q') => quiet = true,
+                Arg::Short('m') => max_count = Some(parser.parse_value()?),
+                Arg::Short('A') => after = Some(parser.parse_value()?),
+                Arg::Short('B') => before = Some(parser.parse_value()?),
+                Arg::Short('C') => context = parser.parse_value()?,
+                Arg::Short(_) => return Err(parser.unknown()),
+                Arg::Long(name) => match name.as_str() {
+                    "color" => colour = match parser.optional_value() {
+                        Some(x) => x.parse()
+                            .map_err(|_|parser.invalid(&x))?,
+                        None => Colour::Auto,
+                    },
+                    "format" => format = match parser.value()?.as_str() {
+                        "text" => Format::Text,
+                        "json" => Format::Json,
+                        "csv" => Format::Csv,
+                        x => return Err(parser.invalid(x)),
+                    },
+                    "threads" => threads = Some(parser.parse_value()?),
+                    "replace" => replace = Some(parser.value()?),
+                    "in-place" => in_place = true,
+                    "help" => return Err(ParseError::new(ParseErrorKind::Help, "")),
+                    "version" => return Err(ParseError::new(ParseErrorKind::Version, "")),
+                    _ => return Err(parser.unknown()),
+                },
+                Arg::Positional(x) => positional.push(x),
+            }
+        }
+
+        if in_place && replace.is_none() {
+            return Err(ParseError::new(ParseErrorKind::MissingOption, "--in-place needs --replace"));
+        }
+
+        let mut positional = positional.into_iter();
+        let query = positional.next()
+            .ok_or_else(||ParseError::new(ParseErrorKind::MissingQuery, ""))?;
+
+        let pattern = match fixed_strings {
+            true => regex::escape(&query),
+            false => query,
+        };
+
+        let query = regex::RegexBuilder::new(&pattern)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|err|ParseError::new(ParseErrorKind::InvalidRegex, err))?;
+
+        Ok(Config {
+            query,
+            paths: positional.collect(),
+            recursive,
+            line_numbers,
+            count,
+            invert,
+            before: before.unwrap_or(context),
+            after: after.unwrap_or(context),
+            colour,
+            format,
+            threads,
+            quiet,
+            max_count,
+            replace,
+            in_place,
+        })
+    }
+
+    /// Searches for lines matching the specified query
+    /// in the passed reader, yielding them as they're read,
+    /// or those which don't match, if inverted with `-v`.
+    /// 
+    /// Lines of context around them are yielded too, if requested,
+    /// with breaks between groups which aren't adjacent.
+    /// 
+    /// The search stops once it has yielded as many matches as `-m` allows,
+    /// or one, with `-q`, after yielding any trailing context.
+    /// 
+    /// # Examples
+    /// ```
+    /// let file = BufReader::new(fs::File::open(path)?);
+    ///
+    /// for line in config.search(file) {
+    ///     if let Line::Match(item) = line? {
+    ///         println!("{}: {}", item.line_no, item.text);
+    ///     }
+    /// }
+    /// ```
+    fn search<'a, R: BufRead + 'a>(&'a self, reader: R) -> impl Iterator<Item = io::Result<Line>> + 'a {
+        Scanner {
+            config: self,
+            lines: reader.lines().zip(1..),
+            before: VecDeque::with_capacity(self.before),
+            after_left: 0,
+            last: None,
+            pending: VecDeque::new(),
+            matches: 0,
+        }
+    }
+
+    /// Returns how many lines may be selected in each file, if it's limited.
+    fn max_count(&self) -> Option<usize> {
+        match self.quiet {
+            true => Some(self.max_count.map_or(1, |x|x.min(1))),
+            false => self.max_count,
+        }
+    }
+
+    /// Gets a referance to the query a `Config` was created with.
+    /// 
+    /// # Examples
+    /// ```
+    /// let args = ["\\AExample\\z", ""];
+    /// let config = Config::new(args.iter().map(|x|x.to_string()))
+    ///     .unwrap();
+    /// 
+    /// assert!(config.query().is_match("Example"));
+    /// ```
+    pub fn query(&self) -> &regex::Regex {
+        &self.query
+    }
+
+    /// Returns the paths a `Config` was created with,
+    /// where `-` refers to standard input,
+    /// which is searched when there are none.
+    /// 
+    /// # Examples
+    /// ```
+    /// let args = ["\\A\\z", "first.txt", "second.txt"];
+    /// let config = Config::new(args.iter().map(|x|x.to_string()))
+    ///     .unwrap();
+    /// 
+    /// assert_eq!(["first.txt", "second.txt"], config.paths());
+    /// ```
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    /// Returns a formatter for the configured output format.
+    fn formatter(&self) -> Box<dyn OutputFormatter> {
+        if self.quiet {
+            return Box::new(Silent);
+        }
+
+        match self.format {
+            Format::Text => Box::new(Text::new(self.line_numbers, self.colour == Colour::Always)),
+            Format::Json => Box::new(Json),
+            Format::Csv => Box::new(Csv::new()),
+        }
+    }
+
+    /// Returns the byte ranges of `text` matching the query,
+    /// leaving out empty matches.
+    fn ranges(&self, text: &str) -> Vec<Range<usize>> {
+        self.query()
+            .find_iter(text)
+            .map(|x|x.range())
+            .filter(|x|!x.is_empty())
+            .collect()
+    }
+
+    /// Returns the files to search, in order,
+    /// with directories expanded if searching recursively,
+    /// and whether they all could be.
+    /// 
+    /// Directories which aren't searched, or can't be read,
+    /// are reported to standard error.
+    fn files(&self) -> (Vec<String>, bool) {
+        let mut files = Vec::new();
+        let stdin = [String::from("-")];
+        let paths = match self.paths().is_empty() {
+            true => &stdin[..],
+            false => self.paths(),
+        };
+
+        let mut complete = true;
+
+        for path in paths {
+            match Path::new(path).is_dir() {
+                true if self.recursive => complete &= walk(Path::new(path), &mut files),
+                true => {
+                    eprintln!("minigrep: {}: is a directory", path);
+                    complete = false;
+                },
+                false => files.push(path.clone()),
+            }
+        }
+
+        (files, complete)
+    }
+}
+
+/// Scans lines for matches, keeping the last few unselected lines,
+/// so they can be yielded as context before the next match.
+struct Scanner<'a, I> {
+    config: &'a Config,
+    lines: I,
+    before: VecDeque<Match>,
+    after_left: usize,
+    last: Option<usize>,
+    pending: VecDeque<Line>,
+    matches: usize,
+}
+
+impl<I> Scanner<'_, I> {
+    /// Returns whether as many lines have been selected as are allowed.
+    fn limited(&self) -> bool {
+        matches!(self.config.max_count(), Some(x) if self.matches >= x)
+    }
+
+    /// Queues whatever should be yielded for `line`.
+    /// 
+    /// Once the limit of selected lines is reached,
+    /// lines are only yielded as trailing context.
+    fn scan(&mut self, line: Match) {
+        let config = self.config;
+
+        if !self.limited() && config.query().is_match(&line.text) != config.invert {
+            let first = self.before.front().map_or(line.line_no, |x|x.line_no);
+            let context = config.before > 0 || config.after > 0;
+
+            if context && matches!(self.last, Some(x) if x + 1 < first) {
+                self.pending.push_back(Line::Break);
+            }
+
+            self.pending.extend(self.before.drain(..).map(Line::Context));
+            self.last = Some(line.line_no);
+            self.after_left = config.after;
+            self.matches += 1;
+            self.pending.push_back(Line::Match(line));
+        } else if self.after_left > 0 {
+            self.after_left -= 1;
+            self.last = Some(line.line_no);
+            self.pending.push_back(Line::Context(line));
+        } else if config.before > 0 {
+            if self.before.len() == config.before {
+                self.before.pop_front();
+            }
+            self.before.push_back(line);
+        }
+    }
+}
+
+impl<I> Iterator for Scanner<'_, I>
+    where
+        I: Iterator<Item = (io::Result<String>, usize)>,
+{
+    type Item = io::Result<Line>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            // Nothing more can be yielded, so the rest of the input needn't be read.
+            if self.limited() && self.after_left == 0 {
+                return None;
+            }
+
+            match self.lines.next()? {
+                (Ok(text), line_no) => self.scan(Match {
+                    line_no,
+                    text,
+                }),
+                (Err(err), _) => return Some(Err(err)),
+            }
+        }
+
+        self.pending
+            .pop_front()
+            .map(Ok)
+    }
+}
+
+/// Adds the files beneath `dir` to `files`, sorted by path,
+/// returning whether every directory could be read.
+/// 
+/// Symbolic links to directories aren't followed,
+/// so links back up the tree can't loop forever.
+fn walk(dir: &Path, files: &mut Vec<String>) -> bool {
+    let mut entries = match fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(Result::ok)
+            .collect::<Vec<_>>(),
+        Err(err) => {
+            eprintln!("minigrep: {}: {}", dir.display(), err);
+            return false;
+        }
+    };
+    entries.sort_by_key(|x|x.path());
+
+    entries.into_iter()
+        .fold(true, |acc, x|match x.file_type() {
+            Ok(kind) if kind.is_dir() => walk(&x.path(), files) && acc,
+            Ok(kind) if kind.is_file() => {
+                files.push(x.path().display().to_string());
+                acc
+            },
+            _ => acc,
+        })
+}
+
+/// Searches the configured files, or standard input,
+/// printing matching lines to standard output,
+/// prefixed by their file's path when more than one file is searched,
+/// or the output is structured.
+/// 
+/// Matches are highlighted when standard output is a terminal,
+/// unless `--color` says otherwise.
+/// 
+/// Files are searched in parallel, on as many threads as `--threads` says,
+/// or the machine has cores, but their results are still written in order.
+/// 
+/// Files which can't be read are reported to standard error, and skipped.
+/// With `-q`, nothing is printed, and files are searched one at a time,
+/// stopping at the first match, which also overrides any earlier errors.
+/// With `--in-place`, the files are rewritten, rather than printed.
+/// 
+/// # Errors
+/// 
+/// Will return `Err` if standard output couldn't be written to.
+pub fn run(mut config: Config) -> io::Result<Summary> {
+    let stdout = io::stdout();
+
+    if config.colour == Colour::Auto {
+        config.colour = match stdout.is_terminal() {
+            true => Colour::Always,
+            false => Colour::Never,
+        };
+    }
+
+    let mut writer = stdout.lock();
+    let mut formatter = config.formatter();
+    let (files, complete) = config.files();
+    let mut summary = Summary {
+        matched: false,
+        failed: !complete,
+    };
+
+    if let Some(replacement) = config.replace.as_deref().filter(|_|config.in_place) {
+        for file in &files {
+            match replace_in_place(&config, file, replacement) {
+                Ok(replaced) => summary.matched |= replaced,
+                Err(err) => {
+                    eprintln!("minigrep: {}: {}", file, err);
+                    summary.failed = true;
+                },
+            }
+        }
+
+        return Ok(summary);
+    }
+
+    let prefixed = files.len() > 1 || config.format != Format::Text;
+    let threads = match config.quiet {
+        true => 1,
+        false => config.threads
+            .or_else(||thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get)
+            .min(files.len()),
+    };
+
+    match threads > 1 {
+        true => run_parallel(&config, &files, threads, &mut writer, &mut *formatter, prefixed, &mut summary)?,
+        false => for file in &files {
+            match search_path(&config, file, &mut writer, &mut *formatter, prefixed) {
+                Ok(true) if config.quiet => return Ok(Summary {
+                    matched: true,
+                    failed: false,
+                }),
+                Ok(matched) => summary.matched |= matched,
+                Err(err) => {
+                    eprintln!("minigrep: {}: {}", file, err);
+                    summary.failed = true;
+                },
+            }
+        },
+    }
+
+    writer.flush()?;
+    Ok(summary)
+}
+
+/// Searches `files` on `threads` threads,
+/// writing each file's results once those of the files before it have been,
+/// and noting them in `summary`.
+fn run_parallel<W: Write>(config: &Config, files: &[String], threads: usize, mut writer: W, formatter: &mut dyn OutputFormatter, prefixed: bool, summary: &mut Summary) -> io::Result<()> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope|{
+        for _ in 0..threads {
+            let sender = sender.clone();
+            let next = &next;
+
+            scope.spawn(move||loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(file) = files.get(index) else {
+                    break;
+                };
+
+                let mut recorder = Recorder::new();
+                let result = search_path(config, file, io::sink(), &mut recorder, prefixed)
+                    .map(|x|(recorder, x));
+
+                // The receiver is only gone if writing failed, so there's no point carrying on.
+                if sender.send((index, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut finished = BTreeMap::new();
+        let mut written = 0;
+
+        for (index, result) in receiver {
+            finished.insert(index, result);
+
+            while let Some(result) = finished.remove(&written) {
+                let file = &files[written];
+
+                match result {
+                    Ok((recorder, matched)) => {
+                        recorder.replay(&mut writer, formatter, prefixed.then(||display_name(file)))?;
+                        summary.matched |= matched;
+                    },
+                    Err(err) => {
+                        eprintln!("minigrep: {}: {}", file, err);
+                        summary.failed = true;
+                    },
+                }
+                written += 1;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// Searches the file at `path`, or standard input, if it's `-`,
+/// prefixing the results with its name, if `prefixed`,
+/// and returning whether any line was selected.
+fn search_path<W: Write>(config: &Config, path: &str, writer: W, formatter: &mut dyn OutputFormatter, prefixed: bool) -> io::Result<bool> {
+    let name = prefixed.then(||display_name(path));
+
+    match path {
+        "-" => run_with(config, io::stdin().lock(), writer, formatter, name),
+        path => fs::File::open(path)
+            .and_then(|x|search_file(config, x, writer, formatter, name)),
+    }
+}
+
+/// Rewrites the file at `path`, with the matches in its lines replaced with `replacement`,
+/// returning whether any were.
+/// 
+/// The new contents are written to a temporary file beside it,
+/// which is renamed over the original,
+/// so the file is never left half written.
+/// Binary files are skipped, like when searching.
+fn replace_in_place(config: &Config, path: &str, replacement: &str) -> io::Result<bool> {
+    if path == "-" {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "standard input can't be rewritten in place"));
+    }
+
+    let path = Path::new(path);
+    let mut reader = BufReader::new(fs::File::open(path)?);
+
+    if is_binary(reader.fill_buf()?) {
+        return Ok(false);
+    }
+
+    let name = path.file_name()
+        .map_or_else(String::new, |x|x.to_string_lossy().into_owned());
+    let temp = path.with_file_name(format!(".{}.minigrep-{}", name, process::id()));
+
+    let mut rewrite = ||{
+        let mut writer = BufWriter::new(fs::File::create(&temp)?);
+        let mut replaced = false;
+        let mut line = String::new();
+
+        while reader.read_line(&mut line)? > 0 {
+            // Line endings are kept as they were, and aren't matched against.
+            let text = line.trim_end_matches('\n')
+                .trim_end_matches('\r');
+            let ending = &line[text.len()..];
+
+            match config.query().is_match(text) {
+                true => {
+                    replaced = true;
+                    write!(writer, "{}{}", config.query().replace_all(text, replacement), ending)?;
+                },
+                false => writer.write_all(line.as_bytes())?,
+            }
+            line.clear();
+        }
+
+        writer.into_inner()
+            .map_err(|err|err.into_error())?
+            .set_permissions(fs::metadata(path)?.permissions())?;
+        fs::rename(&temp, path)?;
+        Ok(replaced)
+    };
+
+    rewrite().map_err(|err|{
+        let _ = fs::remove_file(&temp);
+        err
+    })
+}
+
+/// Returns the name results from `path` are prefixed with.
+fn display_name(path: &str) -> &str {
+    match path {
+        "-" => "(standard input)",
+        path => path,
+    }
+}
+
+/// Searches an open file, a line at a time,
+/// so the memory used doesn't grow with the file's size.
+#[cfg(not(feature = "mmap"))]
+fn search_file<W: Write>(config: &Config, file: fs::File, writer: W, formatter: &mut dyn OutputFormatter, name: Option<&str>) -> io::Result<bool> {
+    run_with(config, BufReader::new(file), writer, formatter, name)
+}
+
+/// Searches an open file, mapping it into memory,
+/// so it's read without copying it into a buffer first.
+/// 
+/// Files which can't be mapped, such as empty files and pipes,
+/// are read a line at a time instead.
+#[cfg(feature = "mmap")]
+fn search_file<W: Write>(config: &Config, file: fs::File, writer: W, formatter: &mut dyn OutputFormatter, name: Option<&str>) -> io::Result<bool> {
+    // SAFETY: The map is only read from, while it's searched.
+    // As with any mapped file, it being truncated by another process meanwhile could fault,
+    // which is the trade-off the `mmap` feature opts into.
+    match unsafe { memmap2::Mmap::map(&file) } {
+        Ok(map) => run_with(config, &map[..], writer, formatter, name),
+        Err(_) => run_with(config, BufReader::new(file), writer, formatter, name),
+    }
+}
+
+/// How many bytes from the start of input are checked for a NUL byte,
+/// being the capacity of a default [`BufReader`],
+/// so mapped and buffered files are checked alike.
+const BINARY_PREFIX_LEN: usize = 8 * 1024;
+
+/// Returns whether the input starting with `buf` is taken to be binary,
+/// having a NUL byte within its first [`BINARY_PREFIX_LEN`] bytes.
+fn is_binary(buf: &[u8]) -> bool {
+    buf[..buf.len().min(BINARY_PREFIX_LEN)].contains(&0)
+}
+
+/// Writes the lines of `reader` matching the query to `writer`,
+/// or their count, with `formatter`,
+/// prefixed by `name`, if given,
+/// returning whether any line was selected.
+/// 
+/// Input with a NUL byte near its start is taken to be binary,
+/// and skipped.
+fn run_with<R: BufRead, W: Write>(config: &Config, mut reader: R, mut writer: W, formatter: &mut dyn OutputFormatter, name: Option<&str>) -> io::Result<bool> {
+    if is_binary(reader.fill_buf()?) {
+        return Ok(false);
+    }
+
+    if config.count {
+        let count = config.search(reader)
+            .try_fold(0, |acc, x|x.map(|x|match x {
+                Line::Match(_) => acc + 1,
+                _ => acc,
+            }))?;
+
+        formatter.count(&mut writer, name, count)?;
+        return Ok(count > 0);
+    }
+
+    let mut selected = false;
+
+    for line in config.search(reader) {
+        let mut line = line?;
+        selected |= matches!(line, Line::Match(_));
+
+        // Replaced matches aren't highlighted, as they've moved.
+        let ranges = match (&mut line, &config.replace) {
+            (Line::Match(_), _) if config.invert => Vec::new(),
+            (Line::Match(item), Some(replacement)) => {
+                item.text = config.query()
+                    .replace_all(&item.text, replacement.as_str())
+                    .into_owned();
+                Vec::new()
+            },
+            (Line::Match(item), None) => config.ranges(&item.text),
+            _ => Vec::new(),
+        };
+
+        formatter.line(&mut writer, name, &line, &ranges)?;
+    }
+
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    /// Parses `args` without the config file, or environment,
+    /// so tests aren't affected by them.
+    fn config(args: &[&str]) -> Result<Config, ParseError> {
+        Config::parse(args.iter().map(|x|x.to_string()), false, Colour::Auto)
+    }
+
+    fn output(config: &Config, input: &str) -> String {
+        let mut output = Vec::new();
+        run_with(config, input.as_bytes(), &mut output, &mut *config.formatter(), None).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn parsing_test() {
+        regex::Regex::new("").unwrap();
+    }
+
+    #[test]
+    fn stdin_when_no_path() {
+        assert_eq!(["-"], config(&["query"]).unwrap().files().0[..]);
+        assert_eq!(["-"], config(&["query", "-"]).unwrap().files().0[..]);
+        assert_eq!(["log.txt"], config(&["query", "log.txt"]).unwrap().files().0[..]);
+        assert!(config(&[]).is_err());
+    }
+
+    #[test]
+    fn searches_readers() {
+        let config = config(&["ERROR"]).unwrap();
+
+        assert_eq!("ERROR one\nERROR three\n", output(&config, "ERROR one\nINFO two\nERROR three"));
+    }
+
+    fn parse(args: &[&str], case_insensitive: bool) -> Result<Config, ParseError> {
+        Config::parse(args.iter().map(|x|x.to_string()), case_insensitive, Colour::Auto)
+    }
+
+    #[test]
+    fn case_insensitive_flag() {
+        let input = "Error\nERROR\nerror\nwarning";
+
+        assert_eq!("ERROR\n", output(&parse(&["ERROR"], false).unwrap(), input));
+        assert_eq!("Error\nERROR\nerror\n", output(&parse(&["-i", "ERROR"], false).unwrap(), input));
+        assert_eq!("Error\nERROR\nerror\n", output(&parse(&["ERROR", "-i"], false).unwrap(), input));
+        // The environment variable applies without the flag.
+        assert_eq!("Error\nERROR\nerror\n", output(&parse(&["ERROR"], true).unwrap(), input));
+    }
+
+    #[test]
+    fn fixed_strings_flag() {
+        let input = "1+1=2\n11=2\n(a|b)\na";
+
+        assert_eq!("11=2\n", output(&parse(&["1+1=2"], false).unwrap(), input));
+        assert_eq!("1+1=2\n", output(&parse(&["-F", "1+1=2"], false).unwrap(), input));
+        assert_eq!("(a|b)\n", output(&parse(&["-F", "(a|b)"], false).unwrap(), input));
+        assert!(parse(&["(a|b"], false).is_err());
+        assert!(parse(&["-F", "(a|b"], false).is_ok());
+    }
+
+    #[test]
+    fn combined_flags() {
+        let config = parse(&["-iF", "A.B", "notes.txt"], false).unwrap();
+
+        assert_eq!(["notes.txt"], config.paths());
+        assert_eq!("a.b\n", output(&config, "a.b\naxb"));
+
+        // Flags end at `--`, so queries can start with a dash.
+        assert_eq!("-i\n", output(&parse(&["--", "-i"], false).unwrap(), "-i\ni"));
+        assert!(parse(&["-x", "query"], false).is_err());
+    }
+
+    /// Creates a directory tree to search, unique to the test.
+    fn tree(name: &str) -> String {
+        let root = env::temp_dir().join(format!("minigrep_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("b.txt"), "match b").unwrap();
+        fs::write(root.join("sub/a.txt"), "match a\nother").unwrap();
+        fs::write(root.join("image.bin"), b"match\0binary").unwrap();
+
+        root.display().to_string()
+    }
+
+    #[test]
+    fn recursive_search() {
+        let root = tree("recursive");
+        let (files, complete) = config(&["-r", "match", &root]).unwrap().files();
+        let names = files.iter()
+            .map(|x|x.strip_prefix(&root).unwrap().replace('\\', "/"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(["/b.txt", "/image.bin", "/sub/a.txt"], names[..]);
+        // Directories are skipped without `-r`.
+        assert!(complete);
+        assert_eq!((Vec::new(), false), config(&["match", &root]).unwrap().files());
+    }
+
+    #[test]
+    fn prefixes_and_binary_files() {
+        let config = config(&["match"]).unwrap();
+        let mut output = Vec::new();
+
+        run_with(&config, &b"match one\nnone"[..], &mut output, &mut *config.formatter(), Some("a.txt")).unwrap();
+        run_with(&config, &b"match\0binary"[..], &mut output, &mut *config.formatter(), Some("b.bin")).unwrap();
+
+        // Only the start is checked, whether input is read a buffer at a time or all at once.
+        let late_nul = [&b"match late\n"[..], &[b' '; 8192], b"\0"].concat();
+        run_with(&config, &late_nul[..], &mut output, &mut *config.formatter(), Some("c.log")).unwrap();
+        run_with(&config, BufReader::new(&late_nul[..]), &mut output, &mut *config.formatter(), Some("d.log")).unwrap();
+
+        assert_eq!("a.txt:match one\nc.log:match late\nd.log:match late\n", String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn line_numbers_counts_and_inversion() {
+        let input = "match one\nother\nmatch two\nlast";
+
+        assert_eq!("1:match one\n3:match two\n", output(&config(&["-n", "match"]).unwrap(), input));
+        assert_eq!("2\n", output(&config(&["-c", "match"]).unwrap(), input));
+        assert_eq!("other\nlast\n", output(&config(&["-v", "match"]).unwrap(), input));
+        // The modes compose, with `-c` taking precedence over `-n`.
+        assert_eq!("2:other\n4:last\n", output(&config(&["-nv", "match"]).unwrap(), input));
+        assert_eq!("2\n", output(&config(&["-cvn", "match"]).unwrap(), input));
+    }
+
+    #[test]
+    fn matches_are_numbered() {
+        let config = config(&["b"]).unwrap();
+        let matches = config.search(&b"a\nb\nab"[..])
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+
+        let expected = [
+            Line::Match(Match { line_no: 2, text: String::from("b") }),
+            Line::Match(Match { line_no: 3, text: String::from("ab") }),
+        ];
+        assert_eq!(expected[..], matches[..]);
+    }
+
+    #[test]
+    fn context_lines() {
+        let input = "a\nb\nmatch 1\nc\nd\ne\nf\nmatch 2\nmatch 3\ng";
+
+        assert_eq!("match 1\nc\n--\nmatch 2\nmatch 3\ng\n", output(&config(&["-A", "1", "match"]).unwrap(), input));
+        assert_eq!("b\nmatch 1\n--\nf\nmatch 2\nmatch 3\n", output(&config(&["-B1", "match"]).unwrap(), input));
+        // Groups which touch, or overlap, aren't separated.
+        assert_eq!("a\nb\nmatch 1\nc\nd\ne\nf\nmatch 2\nmatch 3\ng\n", output(&config(&["-C", "2", "match"]).unwrap(), input));
+        assert_eq!(
+            "2-b\n3:match 1\n4-c\n--\n7-f\n8:match 2\n9:match 3\n10-g\n",
+            output(&config(&["-nC1", "match"]).unwrap(), input),
+        );
+        // `-A` and `-B` take precedence over `-C`, and don't affect counts.
+        assert_eq!("a\nb\nmatch 1\n--\nd\ne\nf\nmatch 2\nmatch 3\n", output(&config(&["-C", "3", "-A0", "match"]).unwrap(), input));
+        assert_eq!("3\n", output(&config(&["-cC", "5", "match"]).unwrap(), input));
+    }
+
+    #[test]
+    fn context_flags_need_numbers() {
+        assert!(config(&["match", "-A"]).is_err());
+        assert!(config(&["-B", "x", "match"]).is_err());
+        assert!(config(&["-Cn", "match"]).is_err());
+
+        let mut output = Vec::new();
+        let config = config(&["-A1", "match"]).unwrap();
+        run_with(&config, &b"match\nnext"[..], &mut output, &mut *config.formatter(), Some("a.txt")).unwrap();
+        assert_eq!("a.txt:match\na.txt-next\n", String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn match_ranges() {
+        assert_eq!([1..3, 5..6], config(&["o+"]).unwrap().ranges("foo box")[..]);
+        assert!(config(&["o+"]).unwrap().ranges("bar").is_empty());
+        // Empty matches aren't highlighted.
+        assert!(config(&["x*"]).unwrap().ranges("bar").is_empty());
+    }
+
+    #[test]
+    fn color_option() {
+        let input = "one match\nother";
+
+        assert_eq!("one \x1b[1;31mmatch\x1b[0m\n", output(&config(&["--color=always", "match"]).unwrap(), input));
+        assert_eq!("one match\n", output(&config(&["--color=never", "match"]).unwrap(), input));
+        // `auto` is resolved when running, so nothing is highlighted here.
+        assert_eq!("one match\n", output(&config(&["--color", "match"]).unwrap(), input));
+        // Inverted lines have nothing to highlight.
+        assert_eq!("other\n", output(&config(&["--color=always", "-v", "match"]).unwrap(), input));
+
+        assert!(config(&["--color=sometimes", "match"]).is_err());
+        assert!(config(&["--colour", "match"]).is_err());
+    }
+
+    #[test]
+    fn format_option() {
+        let input = "one match\nother";
+
+        assert_eq!(
+            "{\"path\":null,\"line_number\":1,\"text\":\"one match\",\"matches\":[{\"start\":4,\"end\":9}]}\n",
+            output(&config(&["--format", "json", "-C1", "match"]).unwrap(), input),
+        );
+        assert_eq!("path,count\n,1\n", output(&config(&["--format=csv", "-c", "match"]).unwrap(), input));
+        assert_eq!("one match\n", output(&config(&["--format=text", "match"]).unwrap(), input));
+
+        assert!(config(&["--format=xml", "match"]).is_err());
+        assert!(config(&["match", "--format"]).is_err());
+    }
+
+    #[test]
+    fn searches_files() {
+        let root = tree("files");
+        let config = config(&["match"]).unwrap();
+        let mut output = Vec::new();
+
+        for name in ["sub/a.txt", "b.txt"] {
+            let file = fs::File::open(Path::new(&root).join(name)).unwrap();
+            search_file(&config, file, &mut output, &mut *config.formatter(), Some(name)).unwrap();
+        }
+
+        // Empty files can't be mapped, so are read instead.
+        fs::write(Path::new(&root).join("empty.txt"), "").unwrap();
+        let file = fs::File::open(Path::new(&root).join("empty.txt")).unwrap();
+        search_file(&config, file, &mut output, &mut *config.formatter(), Some("empty.txt")).unwrap();
+
+        assert_eq!("sub/a.txt:match a\nb.txt:match b\n", String::from_utf8(output).unwrap());
+    }
+
+    /// Measures how quickly a large file is searched, run with:
+    /// `cargo test --release --bin minigrep -- --ignored --nocapture throughput`,
+    /// adding `--features mmap` to compare mapping files.
+    #[test]
+    #[ignore = "throughput benchmark"]
+    fn throughput() {
+        let path = env::temp_dir().join(format!("minigrep_throughput_{}.log", std::process::id()));
+        let line = "2024-01-01T00:00:00Z INFO request handled in 12ms, status 200\n";
+        let lines = 2_000_000;
+
+        let mut file = io::BufWriter::new(fs::File::create(&path).unwrap());
+        for x in 0..lines {
+            match x % 1000 {
+                0 => file.write_all(b"2024-01-01T00:00:00Z ERROR request failed, status 500\n").unwrap(),
+                _ => file.write_all(line.as_bytes()).unwrap(),
+            }
+        }
+        file.flush().unwrap();
+        drop(file);
+
+        let size = fs::metadata(&path).unwrap().len();
+
+        for args in [&["ERROR", "-c"][..], &["-i", "status 5\\d\\d"], &["-v", "INFO"]] {
+            let config = config(args).unwrap();
+            let start = std::time::Instant::now();
+            let file = fs::File::open(&path).unwrap();
+
+            search_file(&config, file, io::sink(), &mut *config.formatter(), None).unwrap();
+
+            let elapsed = start.elapsed();
+            println!(
+                "{:?}: {} MiB in {:.2?}, {:.1} MiB/s",
+                args,
+                size >> 20,
+                elapsed,
+                size as f64 / (1 << 20) as f64 / elapsed.as_secs_f64(),
+            );
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn parallel_search_keeps_order() {
+        let root = tree("parallel");
+        for x in 0..20 {
+            fs::write(Path::new(&root).join(format!("sub/{:02}.txt", x)), format!("match {}\nother", x)).unwrap();
+        }
+
+        for args in [&["-r", "match", &root][..], &["-rn", "--format=csv", "match", &root], &["-rc", "match", &root]] {
+            let config = config(args).unwrap();
+            let (files, _) = config.files();
+            let mut sequential = Vec::new();
+            let mut parallel = Vec::new();
+
+            for file in &files {
+                search_path(&config, file, &mut sequential, &mut *config.formatter(), true).unwrap();
+            }
+            run_parallel(&config, &files, 4, &mut parallel, &mut *config.formatter(), true, &mut Summary::default()).unwrap();
+
+            // CSV headers are only written once, so the sequential output has them repeated.
+            let sequential = String::from_utf8(sequential).unwrap();
+            let expected = match args.contains(&"--format=csv") {
+                true => String::from("path,line_number,text,matches\n") + &sequential.replace("path,line_number,text,matches\n", ""),
+                false => sequential,
+            };
+            assert_eq!(expected, String::from_utf8(parallel).unwrap());
+        }
+
+        assert!(config(&["--threads", "0", "match"]).is_err());
+        assert_eq!(NonZeroUsize::new(2), config(&["--threads=2", "match"]).unwrap().threads);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let kind = |args: &[&str]|*config(args).unwrap_err().kind();
+
+        assert_eq!(ParseErrorKind::MissingQuery, kind(&["-n"]));
+        assert_eq!(ParseErrorKind::UnknownFlag, kind(&["-x", "query"]));
+        assert_eq!(ParseErrorKind::UnknownFlag, kind(&["--colour", "query"]));
+        assert_eq!(ParseErrorKind::MissingValue, kind(&["query", "-A"]));
+        assert_eq!(ParseErrorKind::InvalidValue, kind(&["--threads", "0", "query"]));
+        assert_eq!(ParseErrorKind::InvalidRegex, kind(&["(query"]));
+        // Help and version are reported even alongside other errors after them.
+        assert_eq!(ParseErrorKind::Help, kind(&["--help", "-x"]));
+        assert_eq!(ParseErrorKind::Version, kind(&["--version"]));
+    }
+
+    #[test]
+    fn paths_keep_their_spaces() {
+        let config = config(&["query", "my notes.txt", "-n", "other file.txt"]).unwrap();
+
+        assert_eq!(["my notes.txt", "other file.txt"], config.paths());
+    }
+
+    #[test]
+    fn exit_codes() {
+        let run = |args: &[&str], input: &str|{
+            let config = config(args).unwrap();
+            run_with(&config, input.as_bytes(), io::sink(), &mut *config.formatter(), None).unwrap()
+        };
+
+        assert!(run(&["match"], "a match"));
+        assert!(!run(&["match"], "nothing"));
+        assert!(!run(&["-c", "match"], "nothing"));
+        assert!(run(&["-v", "match"], "match\nother"));
+        // Context lines alone aren't selected.
+        assert!(!run(&["-C1", "match"], "other"));
+
+        assert_eq!(0, Summary { matched: true, failed: false }.exit_code());
+        assert_eq!(1, Summary::default().exit_code());
+        assert_eq!(2, Summary { matched: true, failed: true }.exit_code());
+
+        let root = tree("exit_codes");
+        let mut summary = Summary::default();
+        let files = [format!("{}/b.txt", root), format!("{}/missing.txt", root)];
+        run_parallel(&config(&["match"]).unwrap(), &files, 2, io::sink(), &mut Text::new(false, false), true, &mut summary).unwrap();
+        assert_eq!(Summary { matched: true, failed: true }, summary);
+    }
+
+    #[test]
+    fn max_count_stops_early() {
+        let input = "match 1\nother\nmatch 2\nmatch 3\nlast";
+
+        assert_eq!("match 1\nmatch 2\n", output(&config(&["-m2", "match"]).unwrap(), input));
+        assert_eq!("2\n", output(&config(&["-c", "-m", "2", "match"]).unwrap(), input));
+        assert_eq!("", output(&config(&["-m0", "match"]).unwrap(), input));
+        // Trailing context is still printed, even if it would match.
+        assert_eq!("match 1\nother\nmatch 2\n", output(&config(&["-m1", "-A2", "match"]).unwrap(), input));
+
+        // The rest of the input isn't read, so it can't fail.
+        let config = config(&["-m1", "match"]).unwrap();
+        let reader = io::BufReader::new(io::Read::chain(&b"match\n"[..], FailingReader));
+        assert!(run_with(&config, reader, io::sink(), &mut *config.formatter(), None).unwrap());
+    }
+
+    /// A reader which always fails.
+    struct FailingReader;
+
+    impl io::Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "read past the first match"))
+        }
+    }
+
+    #[test]
+    fn quiet_prints_nothing() {
+        let quiet = config(&["-q", "-n", "match"]).unwrap();
+
+        assert_eq!("", output(&quiet, "match\nmatch"));
+        assert_eq!(Some(1), quiet.max_count());
+        assert!(config(&["-qv", "match"]).unwrap().search(&b"match"[..]).next().is_none());
+    }
+
+    #[test]
+    fn replaces_matches() {
+        let input = "name: Ferris\nother\nname: Corro";
+
+        assert_eq!("Ferris the crab\nCorro the crab\n", output(&config(&["--replace", "$1 the crab", "name: (\\w+)"]).unwrap(), input));
+        assert_eq!("1:hi: Ferris\n2-other\n", output(&config(&["-nA1", "-m1", "--replace=hi", "name"]).unwrap(), input));
+        assert_eq!(ParseErrorKind::MissingOption, *config(&["--in-place", "name"]).unwrap_err().kind());
+    }
+
+    #[test]
+    fn replaces_in_place() {
+        let root = tree("in_place");
+        let path = format!("{}/notes.txt", root);
+        fs::write(&path, "colour: red\r\nsize: big\ncolour: blue").unwrap();
+
+        let config = config(&["--in-place", "--replace", "color: $1", "colour: (\\w+)", &path]).unwrap();
+
+        assert!(replace_in_place(&config, &path, "color: $1").unwrap());
+        assert_eq!("color: red\r\nsize: big\ncolor: blue", fs::read_to_string(&path).unwrap());
+        assert!(!replace_in_place(&config, &path, "color: $1").unwrap());
+
+        // Nothing is left behind, and binary files and standard input aren't rewritten.
+        assert_eq!(4, fs::read_dir(&root).unwrap().count());
+        assert!(!replace_in_place(&config, &format!("{}/image.bin", root), "x").unwrap());
+        assert!(replace_in_place(&config, "-", "x").is_err());
+    }
+
+    #[test]
+    fn config_file_defaults() {
+        let input = "Match\nother";
+
+        assert_eq!("\x1b[1;31mMatch\x1b[0m\n", output(&Config::parse(["match".to_string()].into_iter(), true, Colour::Always).unwrap(), input));
+        // Arguments take precedence over the defaults.
+        assert_eq!("Match\n", output(&Config::parse(["--color=never".to_string(), "match".to_string()].into_iter(), true, Colour::Always).unwrap(), input));
+    }
+}
@@ -1,18 +1,34 @@
-mod lib;
+mod args;
+mod grep;
+mod output;
 
 use std::{
     env,
     process
 };
 
+use args::ParseErrorKind;
+
 fn main() {
-    let config = lib::Config::new(env::args().skip(1)) // Attempts to construct a new minigrep config struct, based on the command arguments minus the first file path argument.
+    let config = grep::Config::new(env::args().skip(1)) // Attempts to construct a new minigrep config struct, based on the command arguments minus the first file path argument.
         .unwrap_or_else(|err| {
-            eprintln!("usage: minigrep <Text: RegEx> <Text: File Path>\n\narguments cannot be parsed: {}", err);
-            process::exit(1); // Prints usage and error, then exits the process, if a `Config` struct can't be constructed.
+            match err.kind() {
+                ParseErrorKind::Help => println!("{}\n\n{}", args::USAGE, args::HELP),
+                ParseErrorKind::Version => println!("minigrep {}", env!("CARGO_PKG_VERSION")),
+                _ => {
+                    eprintln!("{}\n\narguments cannot be parsed: {}", args::USAGE, err);
+                    process::exit(2); // Prints usage and error, then exits the process, if a `Config` struct can't be constructed.
+                },
+            }
+
+            process::exit(0);
         });
 
-    if let Err(err) = lib::run(config) {
-        eprintln!("file reading error: {}", err); // Runs the main process of the command, and prints and error if the specified file can't be found.
+    match grep::run(config) { // Runs the main process of the command, exiting with 0 if any line matched, 1 if none did, or 2 if there was an error.
+        Ok(summary) => process::exit(summary.exit_code()),
+        Err(err) => {
+            eprintln!("output writing error: {}", err);
+            process::exit(2);
+        },
     }
 }
@@ -0,0 +1,321 @@
+//! Formats minigrep can write its results in,
+//! chosen with `--format`.
+use std::{
+    io::{self, Write},
+    ops::Range,
+};
+
+use crate::grep::Line;
+
+/// The ANSI escape highlighting matches, in bold red.
+const MATCH_COLOUR: &str = "\x1b[1;31m";
+/// The ANSI escape resetting the colour after a match.
+const RESET_COLOUR: &str = "\x1b[0m";
+
+/// The formats results can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Lines, as grep would print them.
+    Text,
+    /// A JSON object per line.
+    Json,
+    /// CSV, with a header.
+    Csv,
+}
+
+/// Writes the results of searches, in some format.
+///
+/// `path` is the file the results are from, if it should be written.
+pub trait OutputFormatter {
+    /// Writes a line yielded by a search,
+    /// with the byte ranges the query matched in it.
+    fn line(&mut self, writer: &mut dyn Write, path: Option<&str>, line: &Line, ranges: &[Range<usize>]) -> io::Result<()>;
+
+    /// Writes the number of lines a search matched.
+    fn count(&mut self, writer: &mut dyn Write, path: Option<&str>, count: usize) -> io::Result<()>;
+}
+
+/// Writes lines as grep would, with context lines and breaks,
+/// optionally numbered and highlighted.
+pub struct Text {
+    line_numbers: bool,
+    colour: bool,
+}
+
+impl Text {
+    pub fn new(line_numbers: bool, colour: bool) -> Self {
+        Self {
+            line_numbers,
+            colour,
+        }
+    }
+}
+
+impl OutputFormatter for Text {
+    fn line(&mut self, writer: &mut dyn Write, path: Option<&str>, line: &Line, ranges: &[Range<usize>]) -> io::Result<()> {
+        let (item, separator) = match line {
+            Line::Match(item) => (item, ':'),
+            Line::Context(item) => (item, '-'),
+            Line::Break => return writeln!(writer, "--"),
+        };
+
+        if let Some(path) = path {
+            write!(writer, "{}{}", path, separator)?;
+        }
+
+        if self.line_numbers {
+            write!(writer, "{}{}", item.line_no, separator)?;
+        }
+
+        match self.colour {
+            true => writeln!(writer, "{}", highlight(&item.text, ranges)),
+            false => writeln!(writer, "{}", item.text),
+        }
+    }
+
+    fn count(&mut self, writer: &mut dyn Write, path: Option<&str>, count: usize) -> io::Result<()> {
+        match path {
+            Some(path) => writeln!(writer, "{}:{}", path, count),
+            None => writeln!(writer, "{}", count),
+        }
+    }
+}
+
+/// Writes a JSON object per matching line, or count,
+/// leaving out lines of context.
+pub struct Json;
+
+impl OutputFormatter for Json {
+    fn line(&mut self, writer: &mut dyn Write, path: Option<&str>, line: &Line, ranges: &[Range<usize>]) -> io::Result<()> {
+        let Line::Match(item) = line else {
+            return Ok(());
+        };
+
+        let matches = ranges.iter()
+            .map(|x|format!("{{\"start\":{},\"end\":{}}}", x.start, x.end))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        writeln!(
+            writer,
+            "{{\"path\":{},\"line_number\":{},\"text\":{},\"matches\":[{}]}}",
+            path.map_or_else(||String::from("null"), json_string),
+            item.line_no,
+            json_string(&item.text),
+            matches,
+        )
+    }
+
+    fn count(&mut self, writer: &mut dyn Write, path: Option<&str>, count: usize) -> io::Result<()> {
+        writeln!(writer, "{{\"path\":{},\"count\":{}}}", path.map_or_else(||String::from("null"), json_string), count)
+    }
+}
+
+/// Writes a CSV record per matching line, or count,
+/// leaving out lines of context.
+///
+/// The header is written before the first record,
+/// and match ranges are written as `start-end`, separated by spaces.
+#[derive(Default)]
+pub struct Csv {
+    started: bool,
+}
+
+impl Csv {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn header(&mut self, writer: &mut dyn Write, header: &str) -> io::Result<()> {
+        match self.started {
+            true => Ok(()),
+            false => {
+                self.started = true;
+                writeln!(writer, "{}", header)
+            },
+        }
+    }
+}
+
+impl OutputFormatter for Csv {
+    fn line(&mut self, writer: &mut dyn Write, path: Option<&str>, line: &Line, ranges: &[Range<usize>]) -> io::Result<()> {
+        let Line::Match(item) = line else {
+            return Ok(());
+        };
+
+        self.header(writer, "path,line_number,text,matches")?;
+
+        let matches = ranges.iter()
+            .map(|x|format!("{}-{}", x.start, x.end))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        writeln!(writer, "{},{},{},{}", csv_field(path.unwrap_or_default()), item.line_no, csv_field(&item.text), matches)
+    }
+
+    fn count(&mut self, writer: &mut dyn Write, path: Option<&str>, count: usize) -> io::Result<()> {
+        self.header(writer, "path,count")?;
+        writeln!(writer, "{},{}", csv_field(path.unwrap_or_default()), count)
+    }
+}
+
+/// Writes nothing, for when only whether anything matched matters.
+pub struct Silent;
+
+impl OutputFormatter for Silent {
+    fn line(&mut self, _: &mut dyn Write, _: Option<&str>, _: &Line, _: &[Range<usize>]) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn count(&mut self, _: &mut dyn Write, _: Option<&str>, _: usize) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Records the results written to it,
+/// so they can be written with another formatter later,
+/// such as when files are searched in parallel, but written in order.
+#[derive(Default)]
+pub struct Recorder {
+    records: Vec<Record>,
+}
+
+enum Record {
+    Line(Line, Vec<Range<usize>>),
+    Count(usize),
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the recorded results with `formatter`, for `path`.
+    pub fn replay(self, writer: &mut dyn Write, formatter: &mut dyn OutputFormatter, path: Option<&str>) -> io::Result<()> {
+        self.records
+            .into_iter()
+            .try_for_each(|x|match x {
+                Record::Line(line, ranges) => formatter.line(writer, path, &line, &ranges),
+                Record::Count(count) => formatter.count(writer, path, count),
+            })
+    }
+}
+
+impl OutputFormatter for Recorder {
+    fn line(&mut self, _: &mut dyn Write, _: Option<&str>, line: &Line, ranges: &[Range<usize>]) -> io::Result<()> {
+        self.records.push(Record::Line(line.clone(), ranges.to_vec()));
+        Ok(())
+    }
+
+    fn count(&mut self, _: &mut dyn Write, _: Option<&str>, count: usize) -> io::Result<()> {
+        self.records.push(Record::Count(count));
+        Ok(())
+    }
+}
+
+/// Wraps the `ranges` of `text` in ANSI escapes, highlighting them.
+pub fn highlight(text: &str, ranges: &[Range<usize>]) -> String {
+    let mut highlighted = String::with_capacity(text.len());
+    let mut end = 0;
+
+    for x in ranges {
+        highlighted.push_str(&text[end..x.start]);
+        highlighted.push_str(MATCH_COLOUR);
+        highlighted.push_str(&text[x.clone()]);
+        highlighted.push_str(RESET_COLOUR);
+        end = x.end;
+    }
+
+    highlighted.push_str(&text[end..]);
+    highlighted
+}
+
+/// Quotes `text` as a JSON string.
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+
+    for x in text.chars() {
+        match x {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            x if x.is_control() => quoted.push_str(&format!("\\u{:04x}", x as u32)),
+            x => quoted.push(x),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Quotes `text` as a CSV field, if it needs to be.
+fn csv_field(text: &str) -> String {
+    match text.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", text.replace('"', "\"\"")),
+        false => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grep::Match;
+
+    fn matched(line_no: usize, text: &str) -> Line {
+        Line::Match(Match {
+            line_no,
+            text: text.to_string(),
+        })
+    }
+
+    fn write(formatter: &mut dyn OutputFormatter, lines: &[(Line, &[Range<usize>])]) -> String {
+        let mut output = Vec::new();
+
+        for (line, ranges) in lines {
+            formatter.line(&mut output, Some("a.txt"), line, ranges).unwrap();
+        }
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn highlights_ranges() {
+        assert_eq!("f\x1b[1;31moo\x1b[0m b\x1b[1;31mo\x1b[0mx", highlight("foo box", &[1..3, 5..6]));
+        assert_eq!("bar", highlight("bar", &[]));
+    }
+
+    #[test]
+    fn json_lines() {
+        let lines: [(Line, &[Range<usize>]); 3] = [
+            (matched(2, "say \"hi\"\tnow"), &[0..3, 9..12]),
+            (Line::Break, &[]),
+            (matched(5, "café"), &[0..3, 3..5]),
+        ];
+
+        assert_eq!(
+            "{\"path\":\"a.txt\",\"line_number\":2,\"text\":\"say \\\"hi\\\"\\tnow\",\"matches\":[{\"start\":0,\"end\":3},{\"start\":9,\"end\":12}]}\n\
+            {\"path\":\"a.txt\",\"line_number\":5,\"text\":\"café\",\"matches\":[{\"start\":0,\"end\":3},{\"start\":3,\"end\":5}]}\n",
+            write(&mut Json, &lines),
+        );
+
+        let mut output = Vec::new();
+        Json.count(&mut output, None, 3).unwrap();
+        assert_eq!("{\"path\":null,\"count\":3}\n", String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn csv_records() {
+        let lines: [(Line, &[Range<usize>]); 2] = [
+            (matched(1, "plain"), &[0..2, 3..5]),
+            (matched(4, "a, \"b\""), &[0..1, 4..5]),
+        ];
+
+        assert_eq!(
+            "path,line_number,text,matches\na.txt,1,plain,0-2 3-5\na.txt,4,\"a, \"\"b\"\"\",0-1 4-5\n",
+            write(&mut Csv::new(), &lines),
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    process,
+};
+use my_rusttools::{
+    pigify,
+    traits::{TextTransform, Unpigify},
+};
+
+const USAGE: &str = "usage: pigify [--reverse] [-o FILE] [Text: File Path]...";
+
+/// The files pigify was asked to translate, and where to.
+#[derive(Debug, PartialEq, Eq)]
+struct Options {
+    /// The files to translate, with `-` being standard input.
+    paths: Vec<String>,
+    /// The file to write to, instead of standard output.
+    output: Option<String>,
+    /// Whether to translate from Pig Latin, instead of into it.
+    reverse: bool,
+}
+
+fn parse(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut paths = Vec::new();
+    let mut output = None;
+    let mut reverse = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => output = Some(args.next()
+                .ok_or_else(||format!("expected a value after: {}", arg))?),
+            x if x.starts_with("--output=") => output = Some(x["--output=".len()..].to_string()),
+            "-r" | "--reverse" => reverse = true,
+            "--" => paths.extend(args.by_ref()),
+            x if x.starts_with('-') && x != "-" => return Err(format!("unknown flag: {}", x)),
+            _ => paths.push(arg),
+        }
+    }
+
+    if paths.is_empty() {
+        paths.push(String::from("-"));
+    }
+
+    Ok(Options {
+        paths,
+        output,
+        reverse,
+    })
+}
+
+/// Translates each line of `reader` with `translate`,
+/// writing them to `writer`.
+fn translate_lines(reader: impl BufRead, writer: &mut impl Write, translate: &dyn TextTransform) -> io::Result<()> {
+    reader.lines()
+        .try_for_each(|x|writeln!(writer, "{}", translate.transform(&x?)))
+}
+
+fn run(options: &Options) -> io::Result<()> {
+    let output: Box<dyn Write> = match &options.output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut writer = BufWriter::new(output);
+    let translate: &dyn TextTransform = match options.reverse {
+        true => &Unpigify,
+        false => &pigify,
+    };
+
+    for path in &options.paths {
+        match path.as_str() {
+            "-" => translate_lines(io::stdin().lock(), &mut writer, translate),
+            _ => File::open(path)
+                .and_then(|x|translate_lines(BufReader::new(x), &mut writer, translate))
+                .map_err(|err|io::Error::new(err.kind(), format!("{}: {}", path, err))),
+        }?;
+    }
+
+    writer.flush()
+}
+
+fn main() {
+    let options = parse(env::args().skip(1)) // Parses the command arguments following the binary path.
+        .unwrap_or_else(|err|{
+            eprintln!("{}\n\narguments cannot be parsed: {}", USAGE, err);
+            process::exit(1); // Prints usage and error, then exits the process, if the arguments can't be parsed.
+        });
+
+    if let Err(err) = run(&options) {
+        eprintln!("pigify error: {}", err);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter().map(|x|x.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn parses_arguments() {
+        assert_eq!(Ok(Options { paths: vec![String::from("-")], output: None, reverse: false }), parse(args(&[])));
+        assert_eq!(
+            Ok(Options { paths: vec![String::from("a.txt"), String::from("-"), String::from("-o")], output: Some(String::from("out.txt")), reverse: true }),
+            parse(args(&["a.txt", "--output=out.txt", "--reverse", "-", "--", "-o"])),
+        );
+        assert!(parse(args(&["-o"])).is_err());
+        assert!(parse(args(&["--upside-down"])).is_err());
+    }
+
+    #[test]
+    fn translates_lines() {
+        let mut output = Vec::new();
+        translate_lines("Example text\n\nthe end\n".as_bytes(), &mut output, &pigify).unwrap();
+        assert_eq!("Example-hay ext-tay\n\nhe-tay end-hay\n", String::from_utf8(output).unwrap());
+
+        let mut reversed = Vec::new();
+        translate_lines("Example-hay ext-tay\n".as_bytes(), &mut reversed, &Unpigify).unwrap();
+        assert_eq!("Example text\n", String::from_utf8(reversed).unwrap());
+    }
+}
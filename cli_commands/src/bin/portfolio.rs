@@ -0,0 +1,142 @@
+use std::{
+    env,
+    fmt,
+    io,
+    ops::ControlFlow,
+    path::PathBuf,
+    process::{self, Command},
+};
+use my_rusttools::ParseStdinExtended;
+
+/// A demo the launcher can run,
+/// being one of the workspace's binaries.
+struct Demo {
+    name: &'static str,
+    binary: &'static str,
+    description: &'static str,
+}
+
+impl fmt::Display for Demo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {}", self.name, self.description)
+    }
+}
+
+const DEMOS: [Demo; 5] = [
+    Demo {
+        name: "Guessing Game",
+        binary: "guessing_game",
+        description: "guess the secret number",
+    },
+    Demo {
+        name: "FizzBuzz",
+        binary: "fizzbuzz",
+        description: "prints fizzbuzz, with your own rules",
+    },
+    Demo {
+        name: "Primes",
+        binary: "primes",
+        description: "lists primes, or factorises a number",
+    },
+    Demo {
+        name: "Pigify",
+        binary: "pigify",
+        description: "translates text into Pig Latin",
+    },
+    Demo {
+        name: "Minigrep",
+        binary: "minigrep",
+        description: "searches files for a Regular Expression",
+    },
+];
+
+/// Splits a line of arguments on whitespace,
+/// keeping whitespace within single or double quotes.
+fn split_args(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = None::<String>;
+    let mut quote = None;
+
+    for x in line.chars() {
+        match (quote, x) {
+            (Some(q), x) if x == q => quote = None,
+            (Some(_), x) => current.get_or_insert_with(String::new).push(x),
+            (None, '"' | '\'') => {
+                quote = Some(x);
+                current.get_or_insert_with(String::new);
+            },
+            (None, x) if x.is_whitespace() => args.extend(current.take()),
+            (None, x) => current.get_or_insert_with(String::new).push(x),
+        }
+    }
+
+    args.extend(current);
+    args
+}
+
+/// Returns the path of one of the workspace's binaries,
+/// which are built beside this one.
+fn binary_path(binary: &str) -> io::Result<PathBuf> {
+    let mut path = env::current_exe()?;
+    path.set_file_name(format!("{}{}", binary, env::consts::EXE_SUFFIX));
+
+    Ok(path)
+}
+
+fn run(demo: &Demo, args: &[String]) -> io::Result<process::ExitStatus> {
+    Command::new(binary_path(demo.binary)?)
+        .args(args)
+        .status()
+}
+
+fn main() {
+    let uinp = ParseStdinExtended::new();
+    let mut options = DEMOS.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    options.push(String::from("Quit"));
+
+    println!("Welcome to the portfolio!");
+
+    loop {
+        // The end of input quits, as choosing to would.
+        let selected = uinp.try_select_from(&options, ||{
+            println!("\nPlease choose a demo, by its number,");
+            ControlFlow::Continue(())
+        });
+
+        let Some(demo) = selected.and_then(|x|DEMOS.get(x)) else {
+            break;
+        };
+
+        println!("Please enter the arguments for {}, or leave blank for its usage,", demo.binary);
+
+        let args = uinp.read_line_new_string()
+            .unwrap_or_else(|err|{
+                eprintln!("input error: {}", err);
+                process::exit(1);
+            });
+
+        if args.is_empty() {
+            break;
+        }
+
+        match run(demo, &split_args(&args)) {
+            Ok(status) if !status.success() => println!("{} exited with {}", demo.binary, status),
+            Ok(_) => (),
+            Err(err) => eprintln!("{} could not be run: {}, has it been built?", demo.binary, err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_quoted_args() {
+        assert_eq!(vec!["-n", "hello world", "a.txt"], split_args("  -n \"hello world\" a.txt\n"));
+        assert_eq!(vec!["it's", ""], split_args("\"it's\" ''"));
+        assert!(split_args(" \n").is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+use std::{
+    env,
+    io::{self, BufWriter, Write},
+    process,
+    str::FromStr,
+};
+use cli_commands::config::CliConfig;
+use my_rusttools::factories::sieve_primes;
+
+const USAGE: &str = "usage: primes (--below N | --count K | --factor N) [--format lines|csv]";
+
+/// The largest bound sieved, keeping the sieve to around 100 MB.
+const MAX_SIEVE: usize = 100_000_000;
+
+/// What primes was asked to print.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// The primes less than a number.
+    Below(usize),
+    /// A number of primes, from `2`.
+    Count(usize),
+    /// The prime factors of a number, with repeats.
+    Factor(usize),
+}
+
+/// How the primes are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// One prime per line.
+    Lines,
+    /// Every prime on one line, separated by commas.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lines" => Ok(Self::Lines),
+            "csv" => Ok(Self::Csv),
+            x => Err(format!("invalid value: --format {}", x)),
+        }
+    }
+}
+
+/// Parses `args`, with the default format
+/// taken from the `[primes]` section of the config file.
+fn options(args: impl Iterator<Item = String>) -> Result<(Mode, Format), String> {
+    let config = CliConfig::load()
+        .map_err(|err|err.to_string())?;
+    let format = config.section("primes")
+        .string("format")
+        .map_err(|err|err.to_string())?
+        .map(|x|x.parse())
+        .transpose()?;
+
+    parse(args, format.unwrap_or(Format::Lines))
+}
+
+fn parse(mut args: impl Iterator<Item = String>, format: Format) -> Result<(Mode, Format), String> {
+    let mut format = format;
+    let mut mode = None;
+
+    while let Some(arg) = args.next() {
+        let (name, attached) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg, None),
+        };
+        let value = attached.or_else(||args.next())
+            .ok_or_else(||format!("expected a value after: {}", name))?;
+        let number = ||value.trim()
+            .parse::<usize>()
+            .map_err(|err|format!("invalid value: {} {}: {}", name, value, err));
+
+        let next = match name.as_str() {
+            "--below" => Mode::Below(number()?),
+            "--count" => Mode::Count(number()?),
+            "--factor" => Mode::Factor(number()?),
+            "--format" => {
+                format = value.parse()?;
+                continue;
+            },
+            x => return Err(format!("unknown flag: {}", x)),
+        };
+
+        if mode.replace(next).is_some() {
+            return Err(String::from("expected only one of --below, --count or --factor"));
+        }
+    }
+
+    mode.map(|x|(x, format))
+        .ok_or_else(||String::from("expected --below, --count or --factor"))
+}
+
+/// Returns the primes less than `bound`,
+/// or an error if sieving them would exceed [`MAX_SIEVE`].
+fn primes_below(bound: usize) -> Result<Vec<usize>, String> {
+    let upper_bound = bound.saturating_sub(1);
+
+    if upper_bound > MAX_SIEVE {
+        return Err(format!("--below can be at most {}", MAX_SIEVE + 1));
+    }
+
+    Ok(sieve_primes(upper_bound).collect())
+}
+
+/// Returns the first `count` primes,
+/// or an error if sieving them would exceed [`MAX_SIEVE`].
+fn first_primes(count: usize) -> Result<Vec<usize>, String> {
+    // The `n`th prime is less than `n(ln n + ln ln n)`, for `n` of at least `6`.
+    let n = count.max(6) as f64;
+    let upper_bound = n * (n.ln() + n.ln().ln());
+
+    if upper_bound > MAX_SIEVE as f64 {
+        return Err(format!("--count {} needs too large a sieve", count));
+    }
+
+    Ok(sieve_primes(upper_bound as usize).take(count).collect())
+}
+
+/// Returns the prime factors of `number`, smallest first,
+/// repeated as many times as they divide it.
+///
+/// Factors are found by trial division, so no sieve is needed,
+/// however large `number` is.
+fn factorise(number: usize) -> Vec<usize> {
+    let mut remainder = number;
+    let mut factors = Vec::new();
+
+    // Two, followed by every odd number, tried while its square is no larger than the remainder.
+    // Dividing out each factor as it's found means only primes ever divide the remainder.
+    for x in (2..3).chain((3..).step_by(2)) {
+        if x > remainder / x {
+            break;
+        }
+
+        while remainder % x == 0 {
+            factors.push(x);
+            remainder /= x;
+        }
+    }
+
+    if remainder > 1 {
+        factors.push(remainder); // Whatever's left after dividing out the factors up to its root is prime.
+    }
+
+    factors
+}
+
+fn primes(mode: Mode) -> Result<Vec<usize>, String> {
+    match mode {
+        Mode::Below(x) => primes_below(x),
+        Mode::Count(x) => first_primes(x),
+        Mode::Factor(x) => Ok(factorise(x)),
+    }
+}
+
+fn write_primes(writer: &mut impl Write, primes: &[usize], format: Format) -> io::Result<()> {
+    match format {
+        Format::Lines => primes.iter()
+            .try_for_each(|x|writeln!(writer, "{}", x)),
+        Format::Csv => writeln!(writer, "{}", primes.iter().map(usize::to_string).collect::<Vec<_>>().join(",")),
+    }
+}
+
+fn main() {
+    let (mode, format) = options(env::args().skip(1)) // Parses the command arguments following the binary path.
+        .unwrap_or_else(|err|{
+            eprintln!("{}\n\narguments cannot be parsed: {}", USAGE, err);
+            process::exit(1); // Prints usage and error, then exits the process, if the arguments can't be parsed.
+        });
+
+    let primes = primes(mode)
+        .unwrap_or_else(|err|{
+            eprintln!("{}", err);
+            process::exit(1);
+        });
+    let mut stdout = BufWriter::new(io::stdout().lock());
+
+    if let Err(err) = write_primes(&mut stdout, &primes, format).and_then(|_|stdout.flush()) {
+        eprintln!("output writing error: {}", err);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter().map(|x|x.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(Ok(vec![2, 3, 5, 7]), primes(Mode::Below(11)));
+        assert_eq!(Ok(vec![2, 3, 5, 7, 11]), primes(Mode::Count(5)));
+        assert_eq!(Some(&7919), primes(Mode::Count(1000)).unwrap().last());
+        assert_eq!(Ok(vec![2, 2, 3, 7]), primes(Mode::Factor(84)));
+        assert_eq!(Ok(vec![97]), primes(Mode::Factor(97)));
+        assert_eq!(Ok(vec![2, 499_979]), primes(Mode::Factor(999_958)));
+        assert_eq!(Ok(vec![]), primes(Mode::Factor(1)));
+    }
+
+    #[test]
+    fn large_numbers() {
+        assert!(primes(Mode::Below(usize::MAX)).is_err());
+        assert!(primes(Mode::Count(100_000_000_000)).is_err());
+        assert!(primes(Mode::Count(usize::MAX)).is_err());
+        assert_eq!(Ok(vec![3, 5, 17, 257, 641, 65537, 6_700_417]), primes(Mode::Factor(u64::MAX as usize)));
+        assert_eq!(Ok(vec![999_983, 999_983]), primes(Mode::Factor(999_983 * 999_983)));
+    }
+
+    #[test]
+    fn parses_arguments() {
+        assert_eq!(Ok((Mode::Below(10), Format::Lines)), parse(args(&["--below", "10"]), Format::Lines));
+        assert_eq!(Ok((Mode::Factor(12), Format::Csv)), parse(args(&["--factor=12", "--format", "csv"]), Format::Lines));
+        assert!(parse(args(&[]), Format::Lines).is_err());
+        assert!(parse(args(&["--below", "10", "--count", "3"]), Format::Lines).is_err());
+        assert!(parse(args(&["--format", "json", "--count", "3"]), Format::Lines).is_err());
+        assert!(parse(args(&["--count"]), Format::Lines).is_err());
+    }
+
+    #[test]
+    fn formats() {
+        let mut output = Vec::new();
+        write_primes(&mut output, &[2, 3, 5], Format::Csv).unwrap();
+        write_primes(&mut output, &[7, 11], Format::Lines).unwrap();
+
+        assert_eq!("2,3,5\n7\n11\n", String::from_utf8(output).unwrap());
+    }
+}
@@ -0,0 +1,277 @@
+//! Defaults for the command line tools,
+//! shared between them in one config file.
+//!
+//! The file is TOML, with a section for each tool,
+//! and a `[defaults]` section for settings every tool shares:
+//!
+//! ```toml
+//! [defaults]
+//! color = "never"
+//!
+//! [minigrep]
+//! case_insensitive = true
+//!
+//! [fizzbuzz]
+//! rules = "3=Fizz,5=Buzz,7=Qux"
+//! ```
+//!
+//! A setting is looked for, in order of precedence:
+//!
+//! 1. in the environment, as the tool and key's names in uppercase,
+//!    such as `MINIGREP_CASE_INSENSITIVE`,
+//! 2. in the tool's section,
+//! 3. in the `[defaults]` section.
+//!
+//! Arguments passed to a tool take precedence over all of these.
+use std::{
+    collections::HashMap,
+    env,
+    fmt,
+    fs,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use my_rusttools::traits::parse_human_bool;
+use toml::{Table, Value};
+
+/// The environment variable which, when set, gives the path of the config file to load,
+/// instead of the default.
+pub const CONFIG_VAR: &str = "DUNNCLI_CONFIG";
+
+/// The settings loaded from the config file.
+///
+/// # Examples
+///
+/// ```
+/// use cli_commands::config::CliConfig;
+///
+/// let config = CliConfig::from_toml("[defaults]\ncolor = \"never\"\n\n[minigrep]\ncolor = \"always\"").unwrap();
+///
+/// assert_eq!(Some(String::from("always")), config.section("minigrep").string("color").unwrap());
+/// assert_eq!(Some(String::from("never")), config.section("fizzbuzz").string("color").unwrap());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliConfig {
+    table: Table,
+}
+
+/// The settings for one tool,
+/// with those from the environment and `[defaults]` section.
+#[derive(Debug, Clone)]
+pub struct Section<'a> {
+    name: &'a str,
+    own: Option<&'a Table>,
+    defaults: Option<&'a Table>,
+    /// The tool's variables, with `None` for a value which isn't valid UTF-8.
+    vars: HashMap<String, Option<String>>,
+}
+
+impl CliConfig {
+    /// Loads the config file at [`default_path`],
+    /// or returns an empty config if there isn't one.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the file exists, but can't be read or parsed,
+    /// or if [`CONFIG_VAR`] gives a file which doesn't exist.
+    pub fn load() -> Result<Self, ConfigError> {
+        match env::var_os(CONFIG_VAR) {
+            Some(path) => Self::from_path(path),
+            None => match default_path() {
+                Some(path) if path.exists() => Self::from_path(path),
+                _ => Ok(Self::default()),
+            },
+        }
+    }
+
+    /// Loads the config file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the file can't be read, or isn't valid TOML.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|err|ConfigError::new(ConfigErrorKind::Io(err.kind()), path.display()))?;
+
+        Self::from_toml(&contents)
+    }
+
+    /// Parses a config from TOML.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if `toml` isn't valid TOML,
+    /// or has a top-level value which isn't a section.
+    pub fn from_toml(toml: &str) -> Result<Self, ConfigError> {
+        let table: Table = toml.parse()
+            .map_err(|err: toml::de::Error|ConfigError::new(ConfigErrorKind::Syntax, err.message()))?;
+
+        match table.iter().find(|(_, x)|!x.is_table()) {
+            Some((key, _)) => Err(ConfigError::new(ConfigErrorKind::Syntax, format!("{} is not a section", key))),
+            None => Ok(Self {
+                table
+            }),
+        }
+    }
+
+    /// Returns the settings for the tool `name`,
+    /// with overrides from the environment.
+    ///
+    /// Variables named in anything but UTF-8 are skipped,
+    /// while one of the tool's with a value which isn't UTF-8
+    /// is reported as invalid when its setting is read.
+    pub fn section<'a>(&'a self, name: &'a str) -> Section<'a> {
+        let vars = env::vars_os()
+            .filter_map(|(key, value)|Some((key.into_string().ok()?, value.into_string().ok())));
+
+        self.section_with(name, vars)
+    }
+
+    /// Returns the settings for the tool `name`,
+    /// with overrides from `vars`, rather than the environment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cli_commands::config::CliConfig;
+    ///
+    /// let config = CliConfig::from_toml("[minigrep]\ncase_insensitive = false").unwrap();
+    /// let section = config.section_from("minigrep", [("MINIGREP_CASE_INSENSITIVE", "1")]);
+    ///
+    /// assert_eq!(Some(true), section.bool("case_insensitive").unwrap());
+    /// ```
+    pub fn section_from<'a, I, K, V>(&'a self, name: &'a str, vars: I) -> Section<'a>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>, {
+            let vars = vars.into_iter()
+                .map(|(key, value)|(key.as_ref().to_string(), Some(value.as_ref().to_string())));
+
+            self.section_with(name, vars)
+        }
+
+    fn section_with<'a>(&'a self, name: &'a str, vars: impl Iterator<Item = (String, Option<String>)>) -> Section<'a> {
+        let prefix = format!("{}_", name.to_uppercase());
+
+        Section {
+            name,
+            own: self.table.get(name).and_then(Value::as_table),
+            defaults: self.table.get("defaults").and_then(Value::as_table),
+            vars: vars.filter(|(key, _)|key.starts_with(&prefix))
+                .collect(),
+        }
+    }
+}
+
+impl Section<'_> {
+    /// Returns the setting `key` as a string.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the setting isn't a string.
+    pub fn string(&self, key: &str) -> Result<Option<String>, ConfigError> {
+        self.get(key, |x|Some(x.to_string()), |x|x.as_str().map(str::to_string))
+    }
+
+    /// Returns the setting `key` as a boolean.
+    ///
+    /// Environment variables which are set enable the setting,
+    /// unless they're a term for false, such as `0`, `no` or `off`.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the setting isn't a boolean.
+    pub fn bool(&self, key: &str) -> Result<Option<bool>, ConfigError> {
+        self.get(key, |x|Some(parse_human_bool(x).unwrap_or(true)), Value::as_bool)
+    }
+
+    /// Returns the setting `key` as a number.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the setting isn't a number `T` can hold.
+    pub fn number<T: FromStr + TryFrom<i64>>(&self, key: &str) -> Result<Option<T>, ConfigError> {
+        self.get(key, |x|x.parse().ok(), |x|x.as_integer().and_then(|x|T::try_from(x).ok()))
+    }
+
+    fn get<T>(&self, key: &str, from_var: impl Fn(&str) -> Option<T>, from_value: impl Fn(&Value) -> Option<T>) -> Result<Option<T>, ConfigError> {
+        let var = format!("{}_{}", self.name.to_uppercase(), key.to_uppercase());
+
+        if let Some(value) = self.vars.get(&var) {
+            return value.as_deref()
+                .and_then(from_var)
+                .map(Some)
+                .ok_or_else(||ConfigError::new(ConfigErrorKind::InvalidValue, var));
+        }
+
+        [(self.own, self.name), (self.defaults, "defaults")]
+            .into_iter()
+            .find_map(|(table, name)|Some((table?.get(key)?, name)))
+            .map(|(value, name)|from_value(value)
+                .ok_or_else(||ConfigError::new(ConfigErrorKind::InvalidValue, format!("[{}] {}", name, key))))
+            .transpose()
+    }
+}
+
+/// Returns where the config file is looked for by [`CliConfig::load`]:
+/// `dunncli/config.toml`, in `XDG_CONFIG_HOME`, or `~/.config`.
+///
+/// [`CONFIG_VAR`] takes precedence over this path.
+pub fn default_path() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|x|!x.is_empty())
+        .map(PathBuf::from)
+        .or_else(||env::var_os("HOME").or_else(||env::var_os("USERPROFILE")).map(|x|Path::new(&x).join(".config")))?;
+
+    Some(config_home.join("dunncli").join("config.toml"))
+}
+
+/// The error type for loading the config file, and reading its settings.
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    kind: ConfigErrorKind,
+    detail: String,
+}
+
+/// A list specifying the categories of [`ConfigError`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigErrorKind {
+    /// The config file couldn't be read.
+    Io(io::ErrorKind),
+    /// The config file wasn't valid TOML, or had a value outside a section.
+    Syntax,
+    /// A setting had the wrong type.
+    InvalidValue,
+}
+
+impl ConfigError {
+    fn new(kind: ConfigErrorKind, detail: impl fmt::Display) -> Self {
+        Self {
+            kind,
+            detail: detail.to_string(),
+        }
+    }
+
+    pub fn kind(&self) -> &ConfigErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self.kind {
+            ConfigErrorKind::Io(kind) => return write!(f, "config file could not be read: {}: {}", kind, self.detail),
+            ConfigErrorKind::Syntax => "invalid config file",
+            ConfigErrorKind::InvalidValue => "invalid setting",
+        };
+
+        write!(f, "{}: {}", description, self.detail)
+    }
+}
+
+impl std::error::Error for ConfigError {}
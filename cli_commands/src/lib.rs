@@ -0,0 +1,2 @@
+//! Code shared by the command line tools.
+pub mod config;
@@ -0,0 +1,85 @@
+use std::{
+    env,
+    fs,
+};
+use cli_commands::config::{CliConfig, ConfigErrorKind};
+
+const TOML: &str = "
+[defaults]
+color = \"never\"
+threads = 2
+
+[minigrep]
+color = \"always\"
+case_insensitive = true
+";
+
+#[test]
+fn sections_fall_back_to_defaults() {
+    let config = CliConfig::from_toml(TOML).unwrap();
+    let minigrep = config.section_from("minigrep", [("HOME", "/root")]);
+    let fizzbuzz = config.section_from("fizzbuzz", [("HOME", "/root")]);
+
+    assert_eq!(Some(String::from("always")), minigrep.string("color").unwrap());
+    assert_eq!(Some(true), minigrep.bool("case_insensitive").unwrap());
+    assert_eq!(Some(2), minigrep.number::<usize>("threads").unwrap());
+
+    assert_eq!(Some(String::from("never")), fizzbuzz.string("color").unwrap());
+    assert_eq!(None, fizzbuzz.bool("case_insensitive").unwrap());
+}
+
+#[test]
+fn environment_takes_precedence() {
+    let config = CliConfig::from_toml(TOML).unwrap();
+    let vars = [("MINIGREP_COLOR", "auto"), ("MINIGREP_CASE_INSENSITIVE", "false"), ("FIZZBUZZ_THREADS", "8")];
+    let minigrep = config.section_from("minigrep", vars);
+
+    assert_eq!(Some(String::from("auto")), minigrep.string("color").unwrap());
+    assert_eq!(Some(false), minigrep.bool("case_insensitive").unwrap());
+    // Variables for other tools are ignored.
+    assert_eq!(Some(2), minigrep.number::<usize>("threads").unwrap());
+
+    // Set variables enable settings, as flags do.
+    let empty = CliConfig::default();
+    assert_eq!(Some(true), empty.section_from("minigrep", [("MINIGREP_CASE_INSENSITIVE", "")]).bool("case_insensitive").unwrap());
+    assert_eq!(Some(false), empty.section_from("minigrep", [("MINIGREP_CASE_INSENSITIVE", "off")]).bool("case_insensitive").unwrap());
+}
+
+#[test]
+fn invalid_settings() {
+    let config = CliConfig::from_toml(TOML).unwrap();
+    let minigrep = config.section_from("minigrep", [("MINIGREP_THREADS", "many")]);
+
+    assert_eq!(ConfigErrorKind::InvalidValue, *minigrep.bool("color").unwrap_err().kind());
+    assert_eq!(ConfigErrorKind::InvalidValue, *minigrep.number::<usize>("threads").unwrap_err().kind());
+    // Numbers too big for the type asked for are invalid too.
+    let small = CliConfig::from_toml("[minigrep]\nthreads = 300").unwrap();
+    assert_eq!(ConfigErrorKind::InvalidValue, *small.section_from("minigrep", [("HOME", "/root")]).number::<u8>("threads").unwrap_err().kind());
+
+    assert_eq!(ConfigErrorKind::Syntax, *CliConfig::from_toml("[minigrep").unwrap_err().kind());
+    assert_eq!(ConfigErrorKind::Syntax, *CliConfig::from_toml("color = \"never\"").unwrap_err().kind());
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_environment() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    env::set_var("CONFIG_TESTS_NOT_UTF8", OsStr::from_bytes(b"caf\xe9"));
+    env::set_var("CONFIG_TESTS_COLOR", OsStr::from_bytes(b"caf\xe9"));
+
+    // Another program's variable is skipped, while the tool's own is invalid.
+    let config = CliConfig::from_toml(TOML).unwrap();
+    let section = config.section("config_tests");
+    assert_eq!(Some(2), section.number::<usize>("threads").unwrap());
+    assert_eq!(ConfigErrorKind::InvalidValue, *section.string("color").unwrap_err().kind());
+}
+
+#[test]
+fn loads_files() {
+    let path = env::temp_dir().join(format!("dunncli_config_{}.toml", std::process::id()));
+    fs::write(&path, TOML).unwrap();
+
+    assert_eq!(CliConfig::from_toml(TOML).unwrap(), CliConfig::from_path(&path).unwrap());
+    assert!(matches!(CliConfig::from_path(path.with_extension("missing")).unwrap_err().kind(), ConfigErrorKind::Io(_)));
+}
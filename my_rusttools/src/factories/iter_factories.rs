@@ -1,6 +1,8 @@
 use std::{
-    ops::RangeBounds,
-    iter,
+    borrow::Cow,
+    ops::{RangeBounds, RangeInclusive},
+    iter::{self, FusedIterator},
+    vec,
 };
 
 /// Creates an iterator which returns all the primes,
@@ -14,8 +16,8 @@ use std::{
 /// 
 /// assert!(primes.eq(vec![2, 3, 5, 7]));
 /// ```
-pub fn sieve_primes(upper_bound: usize) -> impl Iterator<Item = usize> {
-    match upper_bound {
+pub fn sieve_primes(upper_bound: usize) -> SievePrimes {
+    let primes = match upper_bound {
         0 | 1 => Vec::new(), // Escapes the 0 and 1 cases early.
         x => (3usize..).step_by(2) // Only steps over odd values, even value inherrantly not being prime.
             .take_while(|i|i * i <= x)
@@ -35,6 +37,194 @@ pub fn sieve_primes(upper_bound: usize) -> impl Iterator<Item = usize> {
             (x, y) if y => Some(x), // Includes indexes which are still marked as primes.
             _ => None,
         })
+        .collect::<Vec<_>>();
+
+    SievePrimes {
+        primes: primes.into_iter(),
+    }
+}
+
+/// An iterator over the primes up to a bound,
+/// created by [`sieve_primes`].
+/// 
+/// As the primes are sieved upfront, their count is known,
+/// and they can be iterated from either end.
+/// 
+/// # Examples
+/// ```
+/// # use my_rusttools::factories::sieve_primes;
+/// #
+/// let primes = sieve_primes(10);
+/// 
+/// assert_eq!(4, primes.len());
+/// assert!(primes.rev().eq(vec![7, 5, 3, 2]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SievePrimes {
+    primes: vec::IntoIter<usize>,
+}
+
+impl Iterator for SievePrimes {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.primes.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.primes.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for SievePrimes {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.primes.next_back()
+    }
+}
+
+impl ExactSizeIterator for SievePrimes {}
+
+impl FusedIterator for SievePrimes {}
+
+/// Creates an iterator which returns the sequence of a recurrence,
+/// starting with the `initial` terms, then each term computed by `f`,
+/// from the `N` terms before it.
+/// 
+/// Terms are only computed as they're returned,
+/// so taking terms up to the limit of `T` doesn't overflow.
+/// 
+/// # Examples
+/// ```
+/// # use my_rusttools::factories::recurrence;
+/// #
+/// let fibonacci = recurrence([0u64, 1], |[a, b]|a + b);
+/// let lucas = recurrence([2u64, 1], |[a, b]|a + b);
+/// let tribonacci = recurrence([0u64, 0, 1], |[a, b, c]|a + b + c);
+/// 
+/// assert!(fibonacci.take(8).eq([0, 1, 1, 2, 3, 5, 8, 13]));
+/// assert!(lucas.take(6).eq([2, 1, 3, 4, 7, 11]));
+/// assert!(tribonacci.take(8).eq([0, 0, 1, 1, 2, 4, 7, 13]));
+/// ```
+pub fn recurrence<T, F, const N: usize>(initial: [T; N], f: F) -> impl Iterator<Item = T>
+where
+    T: Clone,
+    F: Fn(&[T; N]) -> T, {
+        let mut terms = initial;
+        let mut returned = 0;
+
+        iter::from_fn(move||{
+            if returned < N {
+                returned += 1;
+                return Some(terms[returned - 1].clone());
+            }
+
+            let next = f(&terms);
+            terms.rotate_left(N.min(1)); // Without terms, there's nothing to shift along.
+
+            if let Some(last) = terms.last_mut() {
+                *last = next.clone();
+            }
+
+            Some(next)
+        })
+    }
+
+/// Creates an iterator which returns the rows of the truth table,
+/// for a boolean function of `inputs` inputs.
+/// 
+/// Rows count up in binary, with the first input being the most significant,
+/// from all `false` to all `true`.
+/// 
+/// # Panics
+/// 
+/// Will panic if there are too many inputs for the rows to be counted in a [`usize`].
+/// 
+/// # Examples
+/// ```
+/// # use my_rusttools::factories::truth_table;
+/// #
+/// let xor = truth_table(2)
+///     .map(|x|x[0] ^ x[1])
+///     .collect::<Vec<_>>();
+/// 
+/// assert_eq!(4, truth_table(2).len());
+/// assert_eq!(vec![false, true, true, false], xor);
+/// ```
+pub fn truth_table(inputs: u32) -> impl DoubleEndedIterator<Item = Vec<bool>> + ExactSizeIterator + FusedIterator {
+    assert!(inputs < usize::BITS, "truth tables can't have more than {} inputs", usize::BITS - 1);
+
+    (0..1usize << inputs)
+        .map(move|row|(0..inputs).rev()
+            .map(|bit|(row >> bit) & 1 == 1)
+            .collect()
+        )
+}
+
+/// Creates an iterator which returns the rows of the truth table,
+/// for a boolean function of `inputs` inputs, as [`truth_table`] does,
+/// with `true` as `1.0`, and `false` as `0.0`,
+/// such as for training neurons.
+/// 
+/// # Panics
+/// 
+/// Will panic if there are too many inputs for the rows to be counted in a [`usize`].
+/// 
+/// # Examples
+/// ```
+/// # use my_rusttools::factories::truth_table_f64;
+/// #
+/// assert!(truth_table_f64(1).eq([vec![0.0], vec![1.0]]));
+/// ```
+pub fn truth_table_f64(inputs: u32) -> impl DoubleEndedIterator<Item = Vec<f64>> + ExactSizeIterator + FusedIterator {
+    truth_table(inputs)
+        .map(|row|row.into_iter()
+            .map(f64::from)
+            .collect()
+        )
+}
+
+/// Creates an iterator which returns the `bits` bit Gray codes,
+/// in which each code differs from the last by a single bit.
+/// 
+/// # Panics
+/// 
+/// Will panic if there are too many bits for the codes to be counted in a [`usize`].
+/// 
+/// # Examples
+/// ```
+/// # use my_rusttools::factories::gray_codes;
+/// #
+/// assert!(gray_codes(3).eq([0b000, 0b001, 0b011, 0b010, 0b110, 0b111, 0b101, 0b100]));
+/// ```
+pub fn gray_codes(bits: u32) -> impl DoubleEndedIterator<Item = usize> + ExactSizeIterator + FusedIterator {
+    assert!(bits < usize::BITS, "gray codes can't have more than {} bits", usize::BITS - 1);
+
+    (0..1usize << bits).map(|x|x ^ (x >> 1))
+}
+
+/// Creates an iterator which returns the `bits` bit binary strings,
+/// counting up from all `0`s to all `1`s.
+/// 
+/// # Panics
+/// 
+/// Will panic if there are too many bits for the strings to be counted in a [`usize`].
+/// 
+/// # Examples
+/// ```
+/// # use my_rusttools::factories::binary_strings;
+/// #
+/// assert!(binary_strings(2).eq(["00", "01", "10", "11"]));
+/// ```
+pub fn binary_strings(bits: u32) -> impl DoubleEndedIterator<Item = String> + ExactSizeIterator + FusedIterator {
+    assert!(bits < usize::BITS, "binary strings can't have more than {} bits", usize::BITS - 1);
+
+    (0..1usize << bits).map(move|x|match bits {
+        0 => String::new(), // Formatting would otherwise pad `0` to a width of one.
+        _ => format!("{:0width$b}", x, width = bits as usize),
+    })
 }
 
 /// Creates an iterator which returns values
@@ -92,19 +282,145 @@ where
 pub fn fizzbuzz() -> impl Iterator<Item = String> {
     // Sets up cycling iterators, with `Fizz` and `Buzz` values at the appropriate intervals,
     // zipping them into a single iterator.
-    let fizzbuzz = repeat_interval("Fizz", 3).zip(repeat_interval("Buzz", 5));
+    let fizzbuzz = repeat_interval_or(None, Some("Fizz"), 3).zip(repeat_interval_or(None, Some("Buzz"), 5));
 
     // Zips the cycling sequence into a `RangeFrom`,
     // due to needing to begin indexing at `1`.
     (1usize..).zip(fizzbuzz)
         .map(|(i, x)|
             match x {
-                ("", "") => i.to_string(), // Matches for values where the index isn't devisible by `3` or `5`.
-                (x, y) => x.to_owned() + y
+                (None, None) => i.to_string(), // Matches for values where the index isn't devisible by `3` or `5`.
+                (x, y) => x.unwrap_or_default().to_owned() + y.unwrap_or_default()
             }
         )
 }
 
+/// Creates an iterator which returns
+/// the fizzbuzz sequence, for the numbers of `range`.
+/// 
+/// Unlike [`fizzbuzz`], the sequence is finite,
+/// ending at [`usize::MAX`] at the latest, rather than overflowing.
+/// Words are borrowed, so only numbers are allocated.
+/// 
+/// # Examples
+/// ```
+/// # use my_rusttools::factories::fizzbuzz_range;
+/// #
+/// assert!(fizzbuzz_range(9..=12).eq(["Fizz", "Buzz", "11", "Fizz"]));
+/// assert_eq!(Some("FizzBuzz".into()), fizzbuzz_range(usize::MAX - 15..=usize::MAX).last());
+/// ```
+pub fn fizzbuzz_range(range: RangeInclusive<usize>) -> impl DoubleEndedIterator<Item = Cow<'static, str>> + FusedIterator {
+    range.map(|i|match (i % 3, i % 5) {
+        (0, 0) => Cow::Borrowed("FizzBuzz"),
+        (0, _) => Cow::Borrowed("Fizz"),
+        (_, 0) => Cow::Borrowed("Buzz"),
+        _ => Cow::Owned(i.to_string()),
+    })
+}
+
+/// A builder for fizzbuzz-like sequences,
+/// with custom rules, and a custom range.
+/// 
+/// Each number in the range is replaced by the words of the rules
+/// whose divisors divide it, in the order the rules were added,
+/// or left as it is if none do.
+/// 
+/// # Examples
+/// ```
+/// # use my_rusttools::factories::FizzBuzzBuilder;
+/// #
+/// let fizzbuzzqux = FizzBuzzBuilder::new()
+///     .rule(3, "Fizz")
+///     .rule(5, "Buzz")
+///     .rule(7, "Qux")
+///     .start(20)
+///     .end(21)
+///     .build();
+/// 
+/// assert!(fizzbuzzqux.eq(["Buzz", "FizzQux"]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FizzBuzzBuilder {
+    rules: Vec<(usize, String)>,
+    start: usize,
+    end: usize,
+}
+
+impl FizzBuzzBuilder {
+    /// Creates a builder with no rules,
+    /// counting up from `1`, without end.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a builder with the classic rules,
+    /// `Fizz` for multiples of `3`, and `Buzz` for multiples of `5`.
+    pub fn classic() -> Self {
+        Self::new()
+            .rule(3, "Fizz")
+            .rule(5, "Buzz")
+    }
+
+    /// Adds a rule, replacing multiples of `divisor` with `word`.
+    /// 
+    /// # Panics
+    /// 
+    /// Will panic if `divisor` is `0`.
+    #[must_use]
+    pub fn rule(mut self, divisor: usize, word: impl Into<String>) -> Self {
+        assert!(divisor != 0, "fizzbuzz divisors must be non-zero");
+
+        self.rules.push((divisor, word.into()));
+        self
+    }
+
+    /// Sets the first number of the sequence.
+    #[must_use]
+    pub fn start(mut self, start: usize) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Sets the last number of the sequence, inclusively.
+    #[must_use]
+    pub fn end(mut self, end: usize) -> Self {
+        self.end = end;
+        self
+    }
+
+    /// Creates an iterator which returns the sequence.
+    /// 
+    /// Unlike [`fizzbuzz`], the sequence ends at [`usize::MAX`],
+    /// if not sooner, rather than overflowing,
+    /// so can also be iterated from its end.
+    pub fn build(self) -> impl DoubleEndedIterator<Item = String> + FusedIterator {
+        let rules = self.rules;
+
+        (self.start..=self.end)
+            .map(move|i|{
+                let words = rules.iter()
+                    .filter(|(divisor, _)|i % divisor == 0)
+                    .map(|(_, word)|word.as_str())
+                    .collect::<String>();
+
+                match words.is_empty() {
+                    true => i.to_string(),
+                    false => words,
+                }
+            })
+    }
+}
+
+impl Default for FizzBuzzBuilder {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            start: 1,
+            end: usize::MAX,
+        }
+    }
+}
+
 /// Creates an iterator that repeats a default value,
 /// inserting the `repeat` value, every `interval` iterations.
 /// 
@@ -134,7 +450,35 @@ pub fn fizzbuzz() -> impl Iterator<Item = String> {
 /// ```
 #[inline]
 pub fn repeat_interval<T: Clone + Default>(repeat: T, interval: usize) -> impl Iterator<Item = T> {
-    iter::repeat(Default::default())
+    repeat_interval_or(Default::default(), repeat, interval)
+}
+
+/// Creates an iterator that repeats the `filler` value,
+/// inserting the `repeat` value, every `interval` iterations.
+/// 
+/// # Panics
+/// 
+/// The function does not guard against underflows,
+/// so passing a value of `0` either produces the wrong interval, or panics.
+/// If debug assertions are enabled, a panic is guaranteed.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use my_rusttools::factories::repeat_interval_or;
+/// #
+/// let mut fizzy = repeat_interval_or(None, Some("Fizz"), 3);
+/// 
+/// // First two values are the filler.
+/// assert_eq!(fizzy.next(), Some(None));
+/// assert_eq!(fizzy.next(), Some(None));
+/// 
+/// // The third value is now `Some("Fizz")`.
+/// assert_eq!(fizzy.next(), Some(Some("Fizz")));
+/// ```
+#[inline]
+pub fn repeat_interval_or<T: Clone>(filler: T, repeat: T, interval: usize) -> impl Iterator<Item = T> {
+    iter::repeat(filler)
         .take(interval - 1)
         .chain(iter::once(repeat))
         .cycle()
@@ -169,8 +513,8 @@ pub fn repeat_interval<T: Clone + Default>(repeat: T, interval: usize) -> impl I
 /// assert_eq!(fizzy.next(), Some("".to_owned()));
 /// ```
 #[inline]
-pub fn repeat_interval_with<T: Default, F: Clone>(repeat: F, interval: usize) -> impl Iterator<Item = T> where
-F: FnMut() -> T, {
+pub fn repeat_interval_with<T: Default, F>(repeat: F, interval: usize) -> impl Iterator<Item = T> where
+F: FnMut() -> T + Clone, {
     iter::repeat_with(Default::default)
         .take(interval - 1)
         .chain(iter::once_with(repeat))
@@ -190,8 +534,8 @@ F: FnMut() -> T, {
 /// 
 /// assert!(foobar_1.eq(foobar_2));
 /// ```
-pub fn repeat_values<T: Clone>(repeat: &[(T, usize)]) -> impl Iterator<Item = T> {
-    repeat.to_owned()
+pub fn repeat_values<T: Clone>(repeat: &[(T, usize)]) -> impl DoubleEndedIterator<Item = T> + FusedIterator {
+    Vec::from(repeat)
         .into_iter()
-        .flat_map(|(x, y)|iter::repeat(x).take(y))
+        .flat_map(|(x, y)|(0..y).map(move |_|x.clone()))
 }
\ No newline at end of file
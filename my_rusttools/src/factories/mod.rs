@@ -1,4 +1,8 @@
 //! Functions for producing special purpose data structures.
 mod iter_factories;
+#[cfg(feature = "rand")]
+mod noise;
 
-pub use iter_factories::*;
\ No newline at end of file
+pub use iter_factories::*;
+#[cfg(feature = "rand")]
+pub use noise::*;
@@ -0,0 +1,65 @@
+use std::f64::consts::TAU;
+
+use rand::Rng;
+
+/// Creates an iterator which perturbs each value of `iter`,
+/// by uniform noise, of up to `amplitude` either way.
+/// 
+/// # Panics
+/// 
+/// Will panic if `amplitude` is negative, or not finite.
+/// 
+/// # Examples
+/// ```
+/// # use my_rusttools::factories::{jittered, truth_table_f64};
+/// #
+/// let mut noisy = truth_table_f64(2)
+///     .flatten()
+///     .zip(jittered(truth_table_f64(2).flatten(), 0.1, rand::thread_rng()));
+/// 
+/// assert!(noisy.all(|(x, y)|(x - y).abs() <= 0.1));
+/// ```
+pub fn jittered<I, R>(iter: I, amplitude: f64, mut rng: R) -> impl Iterator<Item = f64>
+where
+    I: IntoIterator,
+    I::Item: Into<f64>,
+    R: Rng, {
+        assert!(amplitude >= 0.0 && amplitude.is_finite(), "jitter amplitude must be finite and non-negative");
+
+        iter.into_iter()
+            .map(move|x|x.into() + rng.gen_range(-amplitude..=amplitude))
+    }
+
+/// Creates an iterator which perturbs each value of `iter`,
+/// by gaussian noise, with a standard deviation of `std_dev`.
+/// 
+/// # Panics
+/// 
+/// Will panic if `std_dev` is negative, or not finite.
+/// 
+/// # Examples
+/// ```
+/// # use my_rusttools::factories::gaussian_jittered;
+/// #
+/// let noisy = gaussian_jittered([1.0; 1000], 0.1, rand::thread_rng())
+///     .collect::<Vec<_>>();
+/// let mean = noisy.iter().sum::<f64>() / noisy.len() as f64;
+/// 
+/// assert!((mean - 1.0).abs() < 0.05);
+/// ```
+pub fn gaussian_jittered<I, R>(iter: I, std_dev: f64, mut rng: R) -> impl Iterator<Item = f64>
+where
+    I: IntoIterator,
+    I::Item: Into<f64>,
+    R: Rng, {
+        assert!(std_dev >= 0.0 && std_dev.is_finite(), "jitter standard deviation must be finite and non-negative");
+
+        iter.into_iter()
+            .map(move|x|{
+                // Box-Muller transform, with `1.0 - u` keeping the logarithm's argument above `0`.
+                let (u, v) = (rng.gen::<f64>(), rng.gen::<f64>());
+                let standard = (-2.0 * (1.0 - u).ln()).sqrt() * (TAU * v).cos();
+
+                x.into() + std_dev * standard
+            })
+    }
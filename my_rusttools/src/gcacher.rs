@@ -1,18 +1,23 @@
 //! This module contains the [`GCacher`] type, a wrapper for [`HashMap`],
 //! designed for storing the result of expensive closures,
-//! retrieving an accurate return of the closure, without recalling it.
+//! retrieving an accurate return of the closure, without recalling it,
+//! and the [`TimedGCacher`] type, which also records how long each value took to instance.
 use std::{
     borrow::Borrow,
+    cmp::Reverse,
     collections::{
         HashMap,
         hash_map::{
             RandomState,
             Drain,
+            Entry,
         }, TryReserveError,
     },
     hash::Hash,
     ops::Deref,
+    sync::Arc,
     convert::From,
+    time::{Duration, Instant},
 };
 
 use getset::Getters;
@@ -193,6 +198,30 @@ where
             Self::create(instancer, HashMap::with_capacity(capacity))
         }
 
+        /// Creates a [`TimedGCacher`] with an empty `HashMap`,
+        /// which records how long the instancer takes for each key.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use my_rusttools::GCacher;
+        /// let mut cacher = GCacher::with_timing(|x: &usize|x * x);
+        /// cacher.value_from(2);
+        /// assert!(cacher.timing(&2).is_some());
+        /// ```
+        #[inline]
+        #[must_use]
+        pub fn with_timing(instancer: F) -> TimedGCacher<K, F, V>
+        where
+            K: Clone, {
+                TimedGCacher {
+                    cacher: Self::new(instancer),
+                    timings: HashMap::new(),
+                    hits: 0,
+                    misses: 0,
+                }
+            }
+
         /// Returns a reference to the value corresponding to the key,
         /// instancing a new one, if a key value pairing does not already exist.
         /// 
@@ -211,9 +240,95 @@ where
         /// ```
         pub fn value_from(&mut self, val: K) -> &V {
             self.cache.entry(val)
-                .or_insert_with_key(&self.instancer)          
+                .or_insert_with_key(&self.instancer)
         }
 
+        /// Returns a reference to the value corresponding to the key,
+        /// instancing a new one with `alt`, if a key value pairing does not already exist.
+        ///
+        /// Intended for when the value is already at hand,
+        /// `alt` *must* return the same value as the instancer would.
+        ///
+        /// # Panics
+        ///
+        /// In debug builds, `alt` is checked by calling the instancer as well, whenever a value is instanced,
+        /// panicking if `alt` returns a different value from it.
+        /// So the instancer's cost, and any side effects it has, are only avoided in release builds.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use my_rusttools::GCacher;
+        /// #
+        /// let mut cacher = GCacher::new(|x: &usize|x * x);
+        /// let precomputed = 16;
+        ///
+        /// assert_eq!(&16, cacher.value_from_with(4, |_|precomputed));
+        /// assert_eq!(Some(&16), cacher.peek(&4));
+        /// ```
+        pub fn value_from_with<G>(&mut self, val: K, alt: G) -> &V
+        where
+            V: PartialEq,
+            G: FnOnce(&K) -> V, {
+                let instancer = &self.instancer;
+
+                self.cache.entry(val)
+                    .or_insert_with_key(|k|{
+                        let value = alt(k);
+                        debug_assert!(value == instancer(k), "`alt` returned a different value from the instancer");
+                        value
+                    })
+            }
+
+        /// Returns a reference to the value corresponding to the key,
+        /// if one is cached, without instancing a new one.
+        ///
+        /// The key may be an borrowed form of the maps key type,
+        /// but [`Hash`] and [`Eq`] on the borrowed form *must* match those for the key type.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use my_rusttools::GCacher;
+        /// #
+        /// let mut cacher = GCacher::new(|x: &usize|x * x);
+        /// cacher.value_from(2);
+        ///
+        /// assert_eq!(Some(&4), cacher.peek(&2));
+        /// assert_eq!(None, cacher.peek(&3));
+        /// ```
+        #[inline]
+        pub fn peek<Q>(&self, k: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: ?Sized + Eq + Hash, {
+                self.cache.get(k)
+            }
+
+        /// Returns `true` if a value is cached for the key.
+        ///
+        /// The key may be an borrowed form of the maps key type,
+        /// but [`Hash`] and [`Eq`] on the borrowed form *must* match those for the key type.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use my_rusttools::GCacher;
+        /// #
+        /// let mut cacher = GCacher::new(|x: &usize|x * x);
+        /// cacher.value_from(2);
+        ///
+        /// assert!(cacher.contains_key_cached(&2));
+        /// assert!(!cacher.contains_key_cached(&3));
+        /// ```
+        #[inline]
+        pub fn contains_key_cached<Q>(&self, k: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: ?Sized + Eq + Hash, {
+                self.cache.contains_key(k)
+            }
+
         /// Clears the cache, removing all key-value pairs.
         /// Keeps the allocated memory for reuse.
         /// 
@@ -359,10 +474,10 @@ where
         /// assert_eq!(cacher.remove(&2), None);
         /// ```
         #[inline]
-        pub fn remove<Q: ?Sized>(&mut self, k: &Q) -> Option<V>
+        pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
         where
             K: Borrow<Q>,
-            Q: Eq + Hash, {
+            Q: ?Sized + Eq + Hash, {
                 self.cache.remove(k)
             }
 
@@ -383,10 +498,10 @@ where
         /// assert_eq!(cacher.remove_entry(&2), None);
         /// ```
         #[inline]
-        pub fn remove_entry<Q: ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
+        pub fn remove_entry<Q>(&mut self, k: &Q) -> Option<(K, V)>
         where
             K: Borrow<Q>,
-            Q: Eq + Hash, {
+            Q: ?Sized + Eq + Hash, {
                 self.cache.remove_entry(k)
             }
 
@@ -472,6 +587,84 @@ where
         pub fn into_inner(self) -> (F, HashMap<K, V>) {
             (self.instancer, self.cache)
         }
+
+        /// Returns a copy of the cache, frozen as it currently is,
+        /// which can be shared between threads,
+        /// while the cacher continues to be filled.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use my_rusttools::GCacher;
+        /// # use std::thread;
+        /// #
+        /// let mut cacher = GCacher::new(|x: &usize|x * x);
+        /// cacher.value_from(2);
+        ///
+        /// let snapshot = cacher.snapshot();
+        /// let reader = thread::spawn(move||snapshot.get(&2).copied());
+        /// cacher.value_from(3);
+        ///
+        /// assert_eq!(Some(4), reader.join().unwrap());
+        /// ```
+        #[must_use]
+        pub fn snapshot(&self) -> Arc<HashMap<K, V>>
+        where
+            K: Clone,
+            V: Clone, {
+                Arc::new(self.cache.clone())
+            }
+
+        /// Consumes the cacher,
+        /// returning its cache as a `HashMap`, with each value mapped by `f`.
+        ///
+        /// Consuming the cacher keeps the mapped values from being mistaken
+        /// for the instancer's return values.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use my_rusttools::GCacher;
+        /// #
+        /// let mut cacher = GCacher::new(|x: &usize|x * x);
+        /// cacher.value_from(2);
+        ///
+        /// let cache = cacher.map_values(|x|x.to_string());
+        /// assert_eq!(Some(&String::from("4")), cache.get(&2));
+        /// ```
+        pub fn map_values<W, G>(self, mut f: G) -> HashMap<K, W>
+        where
+            G: FnMut(V) -> W, {
+                self.cache.into_iter()
+                    .map(|(k, v)|(k, f(v)))
+                    .collect()
+            }
+
+        /// Consumes the cacher,
+        /// returning its cache as a `HashMap`, with each key mapped by `f`.
+        ///
+        /// Where `f` maps multiple keys to the same new key,
+        /// only one of their values is kept, unspecified as to which.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use my_rusttools::GCacher;
+        /// #
+        /// let mut cacher = GCacher::new(|x: &usize|x * x);
+        /// cacher.value_from(2);
+        ///
+        /// let cache = cacher.transform_keys(|x|x.to_string());
+        /// assert_eq!(Some(&4), cache.get("2"));
+        /// ```
+        pub fn transform_keys<J, G>(self, mut f: G) -> HashMap<J, V>
+        where
+            J: Hash + Eq,
+            G: FnMut(K) -> J, {
+                self.cache.into_iter()
+                    .map(|(k, v)|(f(k), v))
+                    .collect()
+            }
     }
 
 impl<K, F, V, S> GCacher<K, F, V, S> 
@@ -582,4 +775,194 @@ where
         fn from(unwrap: GCacher<K, F, V>) -> (F, HashMap<K, V>) {
             unwrap.into_inner()
         }
-    }
\ No newline at end of file
+    }
+
+/// A [`GCacher`], which records how long its instancer took
+/// to instance the value of each key, to find which keys dominate the cost of cache misses,
+/// along with how many lookups hit and missed the cache.
+///
+/// Created with [`GCacher::with_timing`].
+/// Immutable access to the underlying `GCacher` is provided through implementation of the
+/// [`Deref`]`<Target=GCacher<K, F, V>>` trait.
+///
+/// ```
+/// # use my_rusttools::GCacher;
+/// #
+/// let mut cacher = GCacher::with_timing(|x: &u64|(1..=*x).product::<u64>());
+/// cacher.value_from(5);
+/// cacher.value_from(20);
+///
+/// assert_eq!(Some(&120), cacher.peek(&5));
+/// assert_eq!(2, cacher.slowest_keys(3).len());
+/// assert_eq!((0, 2), (cacher.hits(), cacher.misses()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimedGCacher<K, F, V>
+where
+    K: Hash + Eq + Clone,
+    F: Fn(&K) -> V, {
+        cacher: GCacher<K, F, V>,
+        timings: HashMap<K, Duration>,
+        hits: u64,
+        misses: u64,
+    }
+
+impl<K, F, V> TimedGCacher<K, F, V>
+where
+    K: Hash + Eq + Clone,
+    F: Fn(&K) -> V, {
+        /// Returns a reference to the value corresponding to the key,
+        /// instancing a new one, and recording how long it took,
+        /// if a key value pairing does not already exist.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use my_rusttools::GCacher;
+        /// #
+        /// let mut cacher = GCacher::with_timing(|x: &usize|x * x);
+        ///
+        /// assert_eq!(&4, cacher.value_from(2));
+        /// ```
+        pub fn value_from(&mut self, val: K) -> &V {
+            match self.cacher.cache.entry(val) {
+                Entry::Occupied(x) => {
+                    self.hits += 1;
+                    x.into_mut()
+                },
+                Entry::Vacant(x) => {
+                    self.misses += 1;
+
+                    let start = Instant::now();
+                    let value = (self.cacher.instancer)(x.key());
+                    self.timings.insert(x.key().clone(), start.elapsed());
+
+                    x.insert(value)
+                },
+            }
+        }
+
+        /// Returns how many calls to [`value_from`](Self::value_from)
+        /// found their value already cached.
+        #[inline]
+        pub fn hits(&self) -> u64 {
+            self.hits
+        }
+
+        /// Returns how many calls to [`value_from`](Self::value_from)
+        /// had to instance their value, each of which was timed.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use my_rusttools::GCacher;
+        /// #
+        /// let mut cacher = GCacher::with_timing(|x: &usize|x * x);
+        /// cacher.value_from(2);
+        /// cacher.value_from(2);
+        /// cacher.value_from(3);
+        ///
+        /// assert_eq!(1, cacher.hits());
+        /// assert_eq!(2, cacher.misses());
+        /// ```
+        #[inline]
+        pub fn misses(&self) -> u64 {
+            self.misses
+        }
+
+        /// Returns how long the instancer took to instance the value of the key,
+        /// if one is cached.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use my_rusttools::GCacher;
+        /// #
+        /// let mut cacher = GCacher::with_timing(|x: &usize|x * x);
+        /// cacher.value_from(2);
+        ///
+        /// assert!(cacher.timing(&2).is_some());
+        /// assert!(cacher.timing(&3).is_none());
+        /// ```
+        #[inline]
+        pub fn timing<Q>(&self, k: &Q) -> Option<Duration>
+        where
+            K: Borrow<Q>,
+            Q: ?Sized + Eq + Hash, {
+                self.timings.get(k).copied()
+            }
+
+        /// Returns the total time the instancer has taken,
+        /// across the keys cached.
+        #[inline]
+        pub fn total_timing(&self) -> Duration {
+            self.timings.values().sum()
+        }
+
+        /// Returns up to `n` keys, with how long their values took to instance,
+        /// slowest first.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use my_rusttools::GCacher;
+        /// #
+        /// let mut cacher = GCacher::with_timing(|x: &usize|x * x);
+        /// cacher.value_from(2);
+        /// cacher.value_from(4);
+        ///
+        /// let slowest = cacher.slowest_keys(1);
+        /// assert_eq!(1, slowest.len());
+        /// assert!(slowest[0].1 >= cacher.timing(&2).unwrap().min(cacher.timing(&4).unwrap()));
+        /// ```
+        pub fn slowest_keys(&self, n: usize) -> Vec<(&K, Duration)> {
+            let mut timings = self.timings.iter()
+                .map(|(k, v)|(k, *v))
+                .collect::<Vec<_>>();
+            timings.sort_by_key(|x|Reverse(x.1));
+            timings.truncate(n);
+
+            timings
+        }
+
+        /// Clears the cache and its timings, removing all key-value pairs,
+        /// and resets the counts of hits and misses.
+        /// Keeps the allocated memory for reuse.
+        #[inline]
+        pub fn clear(&mut self) {
+            self.cacher.clear();
+            self.timings.clear();
+            self.hits = 0;
+            self.misses = 0;
+        }
+
+        /// Removes a key from the cache, and its timing,
+        /// returning the associated value when there is one cached.
+        #[inline]
+        pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: ?Sized + Eq + Hash, {
+                self.timings.remove(k);
+                self.cacher.remove(k)
+            }
+
+        /// Consumes the timed cacher,
+        /// returning its underlying `GCacher` and timings.
+        #[inline]
+        pub fn into_inner(self) -> (GCacher<K, F, V>, HashMap<K, Duration>) {
+            (self.cacher, self.timings)
+        }
+    }
+
+impl<K, F, V> Deref for TimedGCacher<K, F, V>
+where
+    K: Eq + Hash + Clone,
+    F: Fn(&K) -> V {
+        type Target = GCacher<K, F, V>;
+
+        #[inline]
+        fn deref(&self) -> &Self::Target {
+            &self.cacher
+        }
+    }
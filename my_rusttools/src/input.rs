@@ -1,9 +1,20 @@
 //! Custom input handling tools.
 use std::{
-    io::{self, Read},
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
+    fs,
+    io::{self, BufRead, IsTerminal, Read},
     ops::{Bound::*, RangeBounds, Deref, DerefMut, ControlFlow},
     os::unix::prelude::AsRawFd,
-    str::FromStr, process
+    str::FromStr, process::{self, Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    style,
+    traits::{parse_human_bool, VariantNames},
 };
 
 /// A newtype wrapper of [`std::io::Stdin`],
@@ -11,7 +22,7 @@ use std::{
 /// 
 /// # Examples
 /// 
-/// ```
+/// ```no_run
 /// use my_rusttools::StdinExtended;
 ///  
 /// let uinp = StdinExtended::new();
@@ -27,7 +38,7 @@ impl StdinExtended {
     /// # Examples
     /// 
     /// Using implicit synchronization:
-    /// ```
+    /// ```no_run
     /// use std::io;
     /// use my_rusttools::StdinExtended;
     /// 
@@ -39,7 +50,7 @@ impl StdinExtended {
     /// ```
     /// 
     /// Using explicit syncronization:
-    /// ```
+    /// ```no_run
     /// use std::io::{self, BufRead};
     /// use my_rusttools::StdinExtended;
     /// 
@@ -61,7 +72,7 @@ impl StdinExtended {
     /// 
     /// # Examples
     /// 
-    /// ```
+    /// ```no_run
     /// use std::io;
     /// use my_rusttools::StdinExtended;
     /// 
@@ -83,7 +94,7 @@ impl StdinExtended {
     /// 
     /// # Examples
     /// 
-    /// ```
+    /// ```no_run
     /// use std::io;
     /// use my_rusttools::StdinExtended;
     /// use std::ops::ControlFlow;
@@ -102,7 +113,715 @@ impl StdinExtended {
     ///     Ok(())
     /// }
     /// ```
-    pub fn read_lines<U: RangeBounds<usize>, F, EF>(&self, bounds: U, mut notif: F, mut err_notif: EF) -> io::Result<String> where
+    pub fn read_lines<U: RangeBounds<usize>, F, EF>(&self, bounds: U, notif: F, err_notif: EF) -> io::Result<String> where
+    F: FnMut(&str),
+    EF: FnMut(&io::Error, &str) -> ControlFlow<()> {
+        LineSourceExt::read_lines(&mut &*self, bounds, notif, err_notif)
+    }
+
+    /// Repeatedly locks the handle this type warps,
+    /// reading a number of lines within the range specified,
+    /// to a new buffer, reading no more than `max_bytes` of input in total.
+    /// 
+    /// Otherwise behaves as [`read_lines`](Self::read_lines),
+    /// but a runaway pipe can't grow the buffer unboundedly.
+    /// 
+    /// # Errors
+    /// 
+    /// If the input exceeds `max_bytes`, an error of kind [`io::ErrorKind::InvalidData`] is returned,
+    /// wrapping a [`LinesLimitExceeded`], which holds the input read up to the limit.
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use std::io;
+    /// use my_rusttools::{LinesLimitExceeded, StdinExtended};
+    /// use std::ops::ControlFlow;
+    /// 
+    /// let uinp = StdinExtended::new();
+    /// let input = uinp.read_lines_limited(1..=3, 1024,
+    ///     |_|println!("Please enter between 1 and 3 lines."),
+    ///     |_, _|ControlFlow::Break(())
+    /// );
+    /// 
+    /// match input {
+    ///     Ok(input) => println!("{}", input),
+    ///     Err(err) => match err.get_ref().and_then(|x|x.downcast_ref::<LinesLimitExceeded>()) {
+    ///         Some(exceeded) => println!("truncated: {}", exceeded.input),
+    ///         None => eprintln!("input error: {}", err),
+    ///     },
+    /// }
+    /// ```
+    pub fn read_lines_limited<U: RangeBounds<usize>, F, EF>(&self, bounds: U, max_bytes: usize, notif: F, err_notif: EF) -> io::Result<String> where
+    F: FnMut(&str),
+    EF: FnMut(&io::Error, &str) -> ControlFlow<()> {
+        LineSourceExt::read_lines_limited(&mut &*self, bounds, max_bytes, notif, err_notif)
+    }
+
+    /// Locks the handle this type wraps and reads a line of input,
+    /// without echoing it, when standard input is a terminal,
+    /// returning it without its line ending.
+    /// 
+    /// Echoing is turned off with `stty`, so is left on where it isn't available.
+    /// The terminal's settings are restored however reading ends, even by panicking.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error of kind [`io::ErrorKind::UnexpectedEof`] if input has ended,
+    /// or [`io::ErrorKind::Interrupted`] if Ctrl-C is pressed,
+    /// which ends the line instead of signalling the process, while input is hidden.
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use my_rusttools::StdinExtended;
+    /// 
+    /// println!("Please enter your password,");
+    /// let password = StdinExtended::new().read_secret().unwrap();
+    /// ```
+    pub fn read_secret(&self) -> io::Result<String> {
+        LineSourceExt::read_secret(&mut &*self)
+    }
+
+    /// Repeatedly reads a secret twice, as [`read_secret`](Self::read_secret) does,
+    /// until both entries match, returning it.
+    /// 
+    /// The entries are compared in constant time, with [`constant_time_eq`].
+    /// After each mismatch, `mismatch_notif` is passed a delay,
+    /// which is waited before asking again, doubling each time, up to 8 seconds,
+    /// as [`Sensitive`] sources do.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns the first error of either entry, as [`read_secret`](Self::read_secret) does.
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use my_rusttools::StdinExtended;
+    /// 
+    /// let password = StdinExtended::new().read_secret_confirmed(
+    ///     ||println!("Please enter a new password,"),
+    ///     ||println!("Please enter it again,"),
+    ///     |delay|eprintln!("the passwords didn't match, try again in {:?}", delay),
+    /// ).unwrap();
+    /// ```
+    pub fn read_secret_confirmed<F, G, H>(&self, notif: F, confirm_notif: G, mismatch_notif: H) -> io::Result<String> where
+    F: FnMut(),
+    G: FnMut(),
+    H: FnMut(Duration), {
+        LineSourceExt::read_secret_confirmed(&mut &*self, notif, confirm_notif, mismatch_notif)
+    }
+
+    /// Locks the handle this type wraps and reads a line of input,
+    /// parsing it as a row of values, separated by `delim`,
+    /// as [`parse_csv_row`] does.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the line can't be read, input has ended,
+    /// or a value can't be parsed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use my_rusttools::StdinExtended;
+    /// 
+    /// println!("Please enter some comma separated numbers,");
+    /// let row: Vec<f64> = StdinExtended::new().read_csv_row(',').unwrap();
+    /// ```
+    pub fn read_csv_row<T>(&self, delim: char) -> Result<Vec<T>, CsvRowError> where
+    T: FromStr,
+    T::Err: Display, {
+        LineSourceExt::read_csv_row(&mut &*self, delim)
+    }
+
+    /// Locks the handle this type wraps and reads a line of input,
+    /// parsing it as a record of values, separated by `delim`,
+    /// keyed by the names of a previously read `header` row,
+    /// as [`parse_csv_record`] does.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the line can't be read, input has ended,
+    /// a value can't be parsed, or the record doesn't have a value for each name of the header.
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use my_rusttools::StdinExtended;
+    /// 
+    /// let uinp = StdinExtended::new();
+    /// println!("Please enter the names of the columns,");
+    /// let header: Vec<String> = uinp.read_csv_row(',').unwrap();
+    /// 
+    /// println!("Please enter a row of numbers,");
+    /// let record = uinp.read_csv_record::<f64>(&header, ',').unwrap();
+    /// ```
+    pub fn read_csv_record<T>(&self, header: &[String], delim: char) -> Result<HashMap<String, T>, CsvRowError> where
+    T: FromStr,
+    T::Err: Display, {
+        LineSourceExt::read_csv_record(&mut &*self, header, delim)
+    }
+}
+
+/// The error wrapped by the [`io::Error`] returned by [`StdinExtended::read_lines_limited`],
+/// when more input is entered than its limit allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinesLimitExceeded {
+    /// The limit exceeded, in bytes.
+    pub limit: usize,
+    /// The input read, truncated to the limit.
+    pub input: String,
+}
+
+impl Display for LinesLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "input exceeded the limit of {} bytes", self.limit)
+    }
+}
+
+impl Error for LinesLimitExceeded {}
+
+impl Deref for StdinExtended {
+    type Target = io::Stdin;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for StdinExtended {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Read for StdinExtended {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl AsRawFd for StdinExtended {
+    fn as_raw_fd(&self) -> std::os::unix::prelude::RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl Default for StdinExtended {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The character ending a line of hidden input when Ctrl-C is pressed.
+const INTERRUPT: char = '\u{3}';
+
+/// Hides input typed into the terminal, restoring the terminal's settings when dropped.
+/// 
+/// Ctrl-C is turned into a line ending while input is hidden,
+/// as the process being interrupted would skip restoring echoing.
+struct HiddenInput {
+    settings: String,
+}
+
+impl HiddenInput {
+    /// Saves the terminal's settings and hides input, with `stty`,
+    /// returning `None` if it isn't available.
+    fn hide() -> Option<Self> {
+        let settings = Command::new("stty")
+            .arg("-g")
+            .stdin(Stdio::inherit())
+            .output()
+            .ok()
+            .filter(|x|x.status.success())
+            .and_then(|x|String::from_utf8(x.stdout).ok())?;
+        let hidden = Self {
+            settings: settings.trim().to_string(),
+        };
+
+        // Dropped if it fails, restoring whichever settings were changed.
+        stty(&["-echo", "-isig", "eol", "^C"]).then_some(hidden)
+    }
+}
+
+impl Drop for HiddenInput {
+    fn drop(&mut self) {
+        stty(&[&self.settings]);
+        println!(); // The line ending wasn't echoed either.
+    }
+}
+
+/// Runs `stty` with `args`, on the terminal of standard input,
+/// returning whether it succeeded.
+fn stty(args: &[&str]) -> bool {
+    Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .status()
+        .is_ok_and(|x|x.success())
+}
+
+/// The delay after the first failed attempt at a sensitive prompt.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(250);
+/// The longest delay between failed attempts at a sensitive prompt.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(8);
+
+/// Returns the delay after `failures` failed attempts,
+/// starting from `initial`, doubling with each further failure, up to `max`.
+fn backoff(initial: Duration, max: Duration, failures: u32) -> Duration {
+    initial.saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+        .min(max)
+}
+
+/// Compares two byte slices in constant time for slices of the same length,
+/// so the time taken doesn't reveal how much of a secret was guessed.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use my_rusttools::constant_time_eq;
+/// 
+/// assert!(constant_time_eq(b"hunter2", b"hunter2"));
+/// assert!(!constant_time_eq(b"hunter2", b"hunter3"));
+/// ```
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter()
+        .zip(b)
+        .fold(0, |acc, (x, y)|acc | (x ^ y)) == 0
+}
+
+/// Parses a line as a row of values, separated by `delim`,
+/// with surrounding whitespace trimmed from each.
+/// 
+/// Values aren't unquoted, so can't contain `delim`.
+/// 
+/// # Errors
+/// 
+/// Returns an error of kind [`CsvRowErrorKind::Parse`], if a value can't be parsed.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use my_rusttools::{parse_csv_row, CsvRowErrorKind};
+/// 
+/// assert_eq!(Ok(vec![1, 2, 3]), parse_csv_row("1, 2,3\n", ','));
+/// assert!(matches!(
+///     parse_csv_row::<u8>("1;two", ';').unwrap_err().kind(),
+///     CsvRowErrorKind::Parse { column: 1, .. }
+/// ));
+/// ```
+pub fn parse_csv_row<T>(line: &str, delim: char) -> Result<Vec<T>, CsvRowError> where
+T: FromStr,
+T::Err: Display, {
+    line.trim_end_matches(['\r', '\n'])
+        .split(delim)
+        .enumerate()
+        .map(|(column, x)|x.trim()
+            .parse()
+            .map_err(|err: T::Err|CsvRowError {
+                kind: CsvRowErrorKind::Parse {
+                    column,
+                    detail: err.to_string(),
+                },
+            })
+        )
+        .collect()
+}
+
+/// Parses a line as a record of values, separated by `delim`,
+/// keyed by the names of `header`.
+/// 
+/// # Errors
+/// 
+/// Returns an error of kind [`CsvRowErrorKind::Parse`], if a value can't be parsed,
+/// or [`CsvRowErrorKind::FieldCount`], if there isn't a value for each name of the header.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use my_rusttools::parse_csv_record;
+/// 
+/// let header = [String::from("x"), String::from("y")];
+/// let record = parse_csv_record::<i32>(&header, "3,-4", ',').unwrap();
+/// 
+/// assert_eq!(Some(&-4), record.get("y"));
+/// ```
+pub fn parse_csv_record<T>(header: &[String], line: &str, delim: char) -> Result<HashMap<String, T>, CsvRowError> where
+T: FromStr,
+T::Err: Display, {
+    let row = parse_csv_row(line, delim)?;
+
+    match row.len() == header.len() {
+        true => Ok(header.iter().cloned().zip(row).collect()),
+        false => Err(CsvRowError {
+            kind: CsvRowErrorKind::FieldCount {
+                expected: header.len(),
+                found: row.len(),
+            },
+        }),
+    }
+}
+
+/// The error type for reading and parsing CSV rows,
+/// such as with [`StdinExtended::read_csv_row`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRowError {
+    kind: CsvRowErrorKind,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvRowErrorKind {
+    /// The line couldn't be read.
+    Io(io::ErrorKind),
+    /// The input ended before a line was read.
+    EndOfInput,
+    /// The value of the column, counted from `0`, couldn't be parsed.
+    Parse {
+        column: usize,
+        detail: String,
+    },
+    /// The record had a different number of values than its header has names.
+    FieldCount {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl CsvRowError {
+    pub fn kind(&self) -> &CsvRowErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for CsvRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            CsvRowErrorKind::Io(kind) => write!(f, "the row could not be read: {}", kind),
+            CsvRowErrorKind::EndOfInput => write!(f, "the input ended before a row was read"),
+            CsvRowErrorKind::Parse { column, detail } => write!(f, "column {} could not be parsed: {}", column + 1, detail),
+            CsvRowErrorKind::FieldCount { expected, found } => write!(f, "expected {} values, found {}", expected, found),
+        }
+    }
+}
+
+impl Error for CsvRowError {}
+
+/// An object safe source of lines of input,
+/// so sources can be swapped at runtime, behind trait objects.
+/// 
+/// Implemented by [`StdinExtended`] and [`ParseStdinExtended`],
+/// as well as any [`BufRead`] type, such as a buffered file, or a byte slice of scripted input.
+/// The reading and parsing methods of [`LineSourceExt`] are available to all of them.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use my_rusttools::LineSource;
+/// 
+/// let mut source: Box<dyn LineSource> = Box::new("first\nsecond\n".as_bytes());
+/// let mut buf = String::new();
+/// 
+/// source.read_line_into(&mut buf).unwrap();
+/// assert_eq!("first\n", buf);
+/// ```
+pub trait LineSource {
+    /// Reads a line of input, appending it to `buf`,
+    /// returning the number of bytes read, which is `0` once the input has ended.
+    fn read_line_into(&mut self, buf: &mut String) -> io::Result<usize>;
+
+    /// Reads a line of input, appending it to `buf`,
+    /// without letting `buf` grow past `limit` bytes,
+    /// returning whether the line had to be truncated to fit.
+    /// 
+    /// By default, the whole line is read before it's truncated,
+    /// which sources able to stop reading at the limit should override.
+    fn read_line_limited(&mut self, buf: &mut String, limit: usize) -> io::Result<bool> {
+        let remaining = limit.saturating_sub(buf.len());
+        let mut line = String::new();
+        self.read_line_into(&mut line)?;
+
+        let exceeded = line.len() > remaining;
+        // Truncation may split the last character, which is dropped.
+        let end = (0..=remaining.min(line.len())).rev()
+            .find(|x|line.is_char_boundary(*x))
+            .unwrap_or(0);
+
+        buf.push_str(&line[..end]);
+        Ok(exceeded)
+    }
+
+    /// Returns the delay to wait before prompting again,
+    /// after the given number of failed attempts of the `_until_*` loops of [`LineSourceExt`].
+    /// 
+    /// By default, there's no delay, which [`Sensitive`] sources override.
+    fn retry_delay(&self, _failures: u32) -> Duration {
+        Duration::ZERO
+    }
+
+    /// Reads a line of input, as [`read_line_into`](Self::read_line_into) does,
+    /// hiding it as it's typed.
+    /// 
+    /// By default, the line is read as any other, there being nothing to hide,
+    /// which [`StdinExtended`] overrides, hiding input typed at a terminal.
+    fn read_hidden_into(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.read_line_into(buf)
+    }
+}
+
+impl<T: BufRead> LineSource for T {
+    fn read_line_into(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.read_line(buf)
+    }
+
+    fn read_line_limited(&mut self, buf: &mut String, limit: usize) -> io::Result<bool> {
+        let remaining = limit.saturating_sub(buf.len());
+        let mut line = Vec::new();
+
+        // Reads a byte more than fits, to tell whether the limit was exceeded.
+        self.by_ref()
+            .take((remaining as u64).saturating_add(1))
+            .read_until(b'\n', &mut line)?;
+
+        let exceeded = line.len() > remaining;
+        line.truncate(remaining);
+
+        let line = match String::from_utf8(line) {
+            Ok(line) => line,
+            // Truncation may split the last character, which is dropped.
+            Err(err) if exceeded && err.utf8_error().error_len().is_none() => {
+                let valid = err.utf8_error().valid_up_to();
+                let mut bytes = err.into_bytes();
+                bytes.truncate(valid);
+
+                String::from_utf8(bytes).expect("bytes are valid up to this point")
+            },
+            Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+        };
+
+        buf.push_str(&line);
+        Ok(exceeded)
+    }
+}
+
+/// Locks standard input for each line read,
+/// so a shared reference can be read from.
+impl LineSource for &StdinExtended {
+    fn read_line_into(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.read_line(buf)
+    }
+
+    fn read_line_limited(&mut self, buf: &mut String, limit: usize) -> io::Result<bool> {
+        self.lock().read_line_limited(buf, limit)
+    }
+
+    fn read_hidden_into(&mut self, buf: &mut String) -> io::Result<usize> {
+        let hidden = match self.is_terminal() {
+            true => HiddenInput::hide(),
+            false => None,
+        };
+        let read = self.read_line_into(buf);
+        drop(hidden);
+
+        read
+    }
+}
+
+impl LineSource for StdinExtended {
+    fn read_line_into(&mut self, buf: &mut String) -> io::Result<usize> {
+        (&*self).read_line_into(buf)
+    }
+
+    fn read_line_limited(&mut self, buf: &mut String, limit: usize) -> io::Result<bool> {
+        (&*self).read_line_limited(buf, limit)
+    }
+
+    fn read_hidden_into(&mut self, buf: &mut String) -> io::Result<usize> {
+        (&*self).read_hidden_into(buf)
+    }
+}
+
+impl LineSource for ParseStdinExtended {
+    fn read_line_into(&mut self, buf: &mut String) -> io::Result<usize> {
+        (&self.0).read_line_into(buf)
+    }
+
+    fn read_line_limited(&mut self, buf: &mut String, limit: usize) -> io::Result<bool> {
+        (&self.0).read_line_limited(buf, limit)
+    }
+
+    fn read_hidden_into(&mut self, buf: &mut String) -> io::Result<usize> {
+        (&self.0).read_hidden_into(buf)
+    }
+}
+
+/// A [`LineSource`] flagged as sensitive, such as one prompting for a PIN,
+/// so the `_until_*` loops of [`LineSourceExt`] back off between failed attempts,
+/// waiting 250 milliseconds after the first, doubling each time, up to 8 seconds.
+/// 
+/// # Examples
+/// 
+/// ```no_run
+/// use my_rusttools::{LineSourceExt, ParseStdinExtended, Sensitive};
+/// 
+/// let pin: u16 = Sensitive::new(ParseStdinExtended::new())
+///     .read_line_until_parsed(
+///         ||println!("Please enter your PIN,"),
+///         |err|eprintln!("invalid PIN: {err}")
+///     );
+/// ```
+#[derive(Debug)]
+pub struct Sensitive<S> {
+    source: S,
+    initial: Duration,
+    max: Duration,
+}
+
+impl<S: LineSource> Sensitive<S> {
+    /// Flags `source` as sensitive, with the default delays.
+    pub fn new(source: S) -> Self {
+        Self::with_delays(source, INITIAL_RETRY_DELAY, MAX_RETRY_DELAY)
+    }
+
+    /// Flags `source` as sensitive, waiting `initial` after the first failed attempt,
+    /// doubling each time, up to `max`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use std::time::Duration;
+    /// use my_rusttools::{LineSource, Sensitive};
+    /// 
+    /// let source = Sensitive::with_delays("".as_bytes(), Duration::from_secs(1), Duration::from_secs(3));
+    /// 
+    /// assert_eq!(Duration::from_secs(2), source.retry_delay(2));
+    /// assert_eq!(Duration::from_secs(3), source.retry_delay(3));
+    /// ```
+    pub fn with_delays(source: S, initial: Duration, max: Duration) -> Self {
+        Self {
+            source,
+            initial,
+            max,
+        }
+    }
+
+    /// Unwraps the source flagged as sensitive.
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: LineSource> LineSource for Sensitive<S> {
+    fn read_line_into(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.source.read_line_into(buf)
+    }
+
+    fn read_line_limited(&mut self, buf: &mut String, limit: usize) -> io::Result<bool> {
+        self.source.read_line_limited(buf, limit)
+    }
+
+    fn retry_delay(&self, failures: u32) -> Duration {
+        backoff(self.initial, self.max, failures)
+    }
+
+    fn read_hidden_into(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.source.read_hidden_into(buf)
+    }
+}
+
+/// The reading and parsing methods of [`StdinExtended`] and [`ParseStdinExtended`],
+/// for any [`LineSource`], including trait objects of it.
+/// 
+/// The end of input is treated as an error of kind [`io::ErrorKind::UnexpectedEof`] throughout,
+/// returned by the methods returning a [`Result`].
+/// As with [`ParseStdinExtended`], the parsing methods assume a process should exit upon an IO error,
+/// including the end of input, rather than asking again.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use my_rusttools::{LineSource, LineSourceExt};
+/// 
+/// let mut source: Box<dyn LineSource> = Box::new("seven\n7\n".as_bytes());
+/// let num: u8 = source.read_line_until_parsed(||(), |err|eprintln!("invalid input: {err}"));
+/// 
+/// assert_eq!(7, num);
+/// ```
+pub trait LineSourceExt: LineSource {
+    /// Reads a line of input, appending it to a new buffer.
+    fn read_line_new_string(&mut self) -> io::Result<String> {
+        let mut ret = String::new();
+
+        self.read_line_into(&mut ret).map(|_|ret)
+    }
+
+    /// Reads a line of input as a secret, hidden where the source can hide it,
+    /// as [`StdinExtended::read_secret`] does.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error of kind [`io::ErrorKind::UnexpectedEof`] if input has ended,
+    /// or [`io::ErrorKind::Interrupted`] if Ctrl-C is pressed while input is hidden.
+    fn read_secret(&mut self) -> io::Result<String> {
+        let mut line = String::new();
+        self.read_hidden_into(&mut line)?;
+
+        match line {
+            line if line.is_empty() => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "input ended before a secret was read")),
+            line if line.ends_with(INTERRUPT) => Err(io::Error::new(io::ErrorKind::Interrupted, "reading a secret was interrupted")),
+            line => Ok(line.trim_end_matches(['\r', '\n']).to_string()),
+        }
+    }
+
+    /// Repeatedly reads a secret twice, until both entries match,
+    /// as [`StdinExtended::read_secret_confirmed`] does.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns the first error of either entry, as [`read_secret`](Self::read_secret) does.
+    fn read_secret_confirmed<F, G, H>(&mut self, mut notif: F, mut confirm_notif: G, mut mismatch_notif: H) -> io::Result<String> where
+    F: FnMut(),
+    G: FnMut(),
+    H: FnMut(Duration), {
+        let mut failures = 0;
+
+        loop {
+            notif();
+            let secret = self.read_secret()?;
+            confirm_notif();
+            let confirmed = self.read_secret()?;
+
+            if constant_time_eq(secret.as_bytes(), confirmed.as_bytes()) {
+                return Ok(secret);
+            }
+
+            failures += 1;
+            let delay = backoff(INITIAL_RETRY_DELAY, MAX_RETRY_DELAY, failures);
+            mismatch_notif(delay);
+            thread::sleep(delay);
+        }
+    }
+
+    /// Repeatedly reads a number of lines within the range specified,
+    /// to a new buffer, as [`StdinExtended::read_lines`] does.
+    fn read_lines<U: RangeBounds<usize>, F, EF>(&mut self, bounds: U, notif: F, err_notif: EF) -> io::Result<String> where
+    F: FnMut(&str),
+    EF: FnMut(&io::Error, &str) -> ControlFlow<()> {
+        self.read_lines_limited(bounds, usize::MAX, notif, err_notif)
+    }
+
+    /// Repeatedly reads a number of lines within the range specified,
+    /// to a new buffer, reading no more than `max_bytes` of input in total,
+    /// as [`StdinExtended::read_lines_limited`] does.
+    /// 
+    /// # Errors
+    /// 
+    /// If the input exceeds `max_bytes`, an error of kind [`io::ErrorKind::InvalidData`] is returned,
+    /// wrapping a [`LinesLimitExceeded`], which holds the input read up to the limit.
+    /// If input ends before enough lines are read, an error of kind [`io::ErrorKind::UnexpectedEof`] is returned.
+    fn read_lines_limited<U: RangeBounds<usize>, F, EF>(&mut self, bounds: U, max_bytes: usize, mut notif: F, mut err_notif: EF) -> io::Result<String> where
     F: FnMut(&str),
     EF: FnMut(&io::Error, &str) -> ControlFlow<()> {
         let mut ret = String::new();
@@ -126,11 +845,24 @@ impl StdinExtended {
             }
 
             notif(ret.as_str());
+            let read = ret.len();
 
-            if let Err(err) = self.read_line(&mut ret) {
-                if let ControlFlow::Break(()) = err_notif(&err, ret.as_str()) {
+            match self.read_line_limited(&mut ret, max_bytes) {
+                Ok(false) if ret.len() == read && line_count < start => break Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "input ended before enough lines were read"
+                )),
+                Ok(false) => (),
+                Ok(true) => break Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    LinesLimitExceeded {
+                        limit: max_bytes,
+                        input: ret,
+                    }
+                )),
+                Err(err) => if let ControlFlow::Break(()) = err_notif(&err, ret.as_str()) {
                     break Err(err);
-                }
+                },
             }
 
             let new_line_count = ret.trim().lines().filter(|x|!x.is_empty()).count();
@@ -146,40 +878,319 @@ impl StdinExtended {
             line_count = new_line_count;
         }
     }
-}
 
-impl Deref for StdinExtended {
-    type Target = io::Stdin;
+    /// Reads a line of input,
+    /// parsing it as a row of values, separated by `delim`,
+    /// as [`parse_csv_row`] does.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the line can't be read, input has ended,
+    /// or a value can't be parsed.
+    fn read_csv_row<T>(&mut self, delim: char) -> Result<Vec<T>, CsvRowError> where
+    T: FromStr,
+    T::Err: Display, {
+        parse_csv_row(&read_csv_line(self)?, delim)
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+    /// Reads a line of input,
+    /// parsing it as a record of values, separated by `delim`,
+    /// keyed by the names of a previously read `header` row,
+    /// as [`parse_csv_record`] does.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the line can't be read, input has ended,
+    /// a value can't be parsed, or the record doesn't have a value for each name of the header.
+    fn read_csv_record<T>(&mut self, header: &[String], delim: char) -> Result<HashMap<String, T>, CsvRowError> where
+    T: FromStr,
+    T::Err: Display, {
+        parse_csv_record(header, &read_csv_line(self)?, delim)
     }
-}
 
-impl DerefMut for StdinExtended {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+    /// Attempts to parse the line of input read,
+    /// exiting the process if it can't be read, or input has ended.
+    fn read_line_parse<T: FromStr>(&mut self) -> Result<T, T::Err> {
+        read_line_required(self)
+            .unwrap_or_else(|err|exit_on_error(err))
+            .trim()
+            .parse()
+    }
+
+    /// Repeatedly reads a line of input, until it's parsed.
+    fn read_line_until_parsed<T, F, E>(&mut self, mut notif: F, mut err_notif: E) -> T where
+    T: FromStr,
+    F: FnMut(),
+    E: FnMut(T::Err), {
+        let mut failures = 0;
+
+        loop {
+            notif();
+
+            match self.read_line_parse() {
+                Ok(parsed) => return parsed,
+                Err(err) => err_notif(err),
+            }
+
+            failures += 1;
+            thread::sleep(self.retry_delay(failures));
+        }
+    }
+
+    /// Repeatedly reads a line of input, until it's parsed,
+    /// or `notif` breaks, or input ends, returning [`None`].
+    fn try_read_line_until_parsed<T, F, E>(&mut self, mut notif: F, mut err_notif: E) -> Option<T> where
+    T: FromStr,
+    F: FnMut() -> ControlFlow<()>,
+    E: FnMut(T::Err), {
+        let mut failures = 0;
+
+        loop {
+            if let ControlFlow::Break(()) = notif() {
+                return None;
+            }
+
+            match read_line_or_cancel(self)?.trim().parse() {
+                Ok(parsed) => return Some(parsed),
+                Err(err) => err_notif(err),
+            }
+
+            failures += 1;
+            thread::sleep(self.retry_delay(failures));
+        }
+    }
+
+    /// Repeatedly reads a line of input,
+    /// until the return value from the passed closure of a [`Some`] enum,
+    /// exiting the process if a line can't be read, or input has ended.
+    fn read_line_until_mapped<T, F, G>(&mut self, mut f: F, mut notif: G) -> T where
+    F: FnMut(String) -> Option<T>,
+    G: FnMut(), {
+        let mut failures = 0;
+
+        loop {
+            notif();
+
+            let uinp = read_line_required(self)
+                .map_or_else(|err|exit_on_error(err), &mut f);
+
+            if let Some(ret) = uinp {
+                return ret;
+            }
+
+            failures += 1;
+            thread::sleep(self.retry_delay(failures));
+        }
+    }
+
+    /// Repeatedly reads a line of input,
+    /// until the return value from the passed closure of a [`Some`] enum,
+    /// or `notif` breaks, or input ends, returning [`None`].
+    fn try_read_line_until_mapped<T, F, G>(&mut self, mut f: F, mut notif: G) -> Option<T> where
+    F: FnMut(String) -> Option<T>,
+    G: FnMut() -> ControlFlow<()>, {
+        let mut failures = 0;
+
+        loop {
+            if let ControlFlow::Break(()) = notif() {
+                return None;
+            }
+
+            let uinp = f(read_line_or_cancel(self)?);
+
+            if uinp.is_some() {
+                return uinp;
+            }
+
+            failures += 1;
+            thread::sleep(self.retry_delay(failures));
+        }
+    }
+
+    /// Repeatedly reads a line of input,
+    /// until a yes or no answer is read,
+    /// as [`parse_human_bool`] parses them.
+    fn read_line_until_bool<F: FnMut()>(&mut self, notif: F) -> bool {
+        self.read_line_until_mapped(|x|parse_human_bool(&x), notif)
+    }
+
+    /// Repeatedly reads a line of input,
+    /// until a yes or no answer is read, as [`parse_human_bool`] parses them,
+    /// or `notif` breaks, or input ends, returning [`None`].
+    fn try_read_line_until_bool<F: FnMut() -> ControlFlow<()>>(&mut self, notif: F) -> Option<bool> {
+        self.try_read_line_until_mapped(|x|parse_human_bool(&x), notif)
+    }
+
+    /// Repeatedly reads a line of input,
+    /// listing the names of the variants of `E` after each notification,
+    /// until one of them is parsed.
+    fn read_line_parse_enum<E, F>(&mut self, mut notif: F) -> E where
+    E: FromStr + VariantNames,
+    F: FnMut(), {
+        self.read_line_until_mapped(
+            |x|x.trim().parse().ok(),
+            ||{
+                notif();
+                println!("({})", E::VARIANTS.join("/"));
+            }
+        )
+    }
+
+    /// Repeatedly reads a line of input,
+    /// listing the names of the variants of `E` after each notification,
+    /// until one of them is parsed,
+    /// or `notif` breaks, or input ends, returning [`None`].
+    fn try_read_line_parse_enum<E, F>(&mut self, mut notif: F) -> Option<E> where
+    E: FromStr + VariantNames,
+    F: FnMut() -> ControlFlow<()>, {
+        self.try_read_line_until_mapped(
+            |x|x.trim().parse().ok(),
+            ||{
+                let flow = notif();
+
+                if flow.is_continue() {
+                    println!("({})", E::VARIANTS.join("/"));
+                }
+                flow
+            }
+        )
+    }
+
+    /// Prints the options passed, numbered from `1`,
+    /// then repeatedly reads a line of input,
+    /// until the number of an option is read,
+    /// returning its index.
+    /// 
+    /// # Panics
+    /// 
+    /// Will panic if `options` is empty, there being nothing to select.
+    fn select_from<T, F>(&mut self, options: &[T], mut notif: F) -> usize where
+    T: Display,
+    F: FnMut(), {
+        print_options(options);
+        self.read_line_until_mapped(|x|option_index(&x, options.len()), &mut notif)
+    }
+
+    /// Prints the options passed, numbered from `1`,
+    /// then repeatedly reads a line of input,
+    /// until the number of an option is read, returning its index,
+    /// or `notif` breaks, or input ends, returning [`None`].
+    /// 
+    /// # Panics
+    /// 
+    /// Will panic if `options` is empty, there being nothing to select.
+    fn try_select_from<T, F>(&mut self, options: &[T], notif: F) -> Option<usize> where
+    T: Display,
+    F: FnMut() -> ControlFlow<()>, {
+        print_options(options);
+        self.try_read_line_until_mapped(|x|option_index(&x, options.len()), notif)
+    }
+
+    /// Repeatedly reads a line of input, completing lines ending with a tab,
+    /// typed before pressing enter, with `completer`,
+    /// until a line is read without one, or completed by a single candidate,
+    /// returning it without its line ending.
+    /// 
+    /// `notif` is passed the candidates of each line which wasn't completed,
+    /// which is empty if there weren't any.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if a line can't be read,
+    /// or of kind [`io::ErrorKind::UnexpectedEof`] if input has ended.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use my_rusttools::{Completer, LineSourceExt};
+    /// 
+    /// let completer: &Completer = &|partial|["src/", "stats.txt"].iter()
+    ///     .filter(|x|x.starts_with(partial))
+    ///     .map(|x|x.to_string())
+    ///     .collect();
+    /// 
+    /// let mut source = "s\t\nsr\t\n".as_bytes();
+    /// let path = source.read_line_completed(completer, |candidates|println!("{}", candidates.join("  ")));
+    /// 
+    /// assert_eq!("src/", path.unwrap());
+    /// ```
+    fn read_line_completed<F>(&mut self, completer: &Completer, mut notif: F) -> io::Result<String> where
+    F: FnMut(&[String]), {
+        loop {
+            let line = read_line_required(self)?;
+            let line = line.trim_end_matches(['\r', '\n']);
+            let Some(partial) = line.strip_suffix('\t') else {
+                return Ok(line.to_string());
+            };
+
+            match completer(partial).as_slice() {
+                [candidate] => return Ok(candidate.clone()),
+                candidates => notif(candidates),
+            }
+        }
     }
 }
 
-impl Read for StdinExtended {
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.0.read(buf)
+impl<T: LineSource + ?Sized> LineSourceExt for T {}
+
+/// Reads a line of input from `source`,
+/// treating the end of input as an error of kind [`io::ErrorKind::UnexpectedEof`].
+fn read_line_required<S: LineSource + ?Sized>(source: &mut S) -> io::Result<String> {
+    match source.read_line_new_string()? {
+        line if line.is_empty() => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "input ended before a line was read")),
+        line => Ok(line),
     }
 }
 
-impl AsRawFd for StdinExtended {
-    fn as_raw_fd(&self) -> std::os::unix::prelude::RawFd {
-        self.0.as_raw_fd()
+/// Reads a line of input from `source`, returning [`None`] if input has ended,
+/// and exiting the process if it can't be read.
+fn read_line_or_cancel<S: LineSource + ?Sized>(source: &mut S) -> Option<String> {
+    match read_line_required(source) {
+        Ok(line) => Some(line),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => None,
+        Err(err) => exit_on_error(err),
     }
 }
 
-impl Default for StdinExtended {
-    fn default() -> Self {
-        Self::new()
+/// Prints `options`, numbered from `1`, for selecting from.
+/// 
+/// # Panics
+/// 
+/// Will panic if `options` is empty, there being nothing to select.
+fn print_options<T: Display>(options: &[T]) {
+    assert!(!options.is_empty(), "options must not be empty");
+
+    options.iter()
+        .enumerate()
+        .for_each(|(i, x)|println!("{} {}", style::bold(format!("{})", i + 1)), x));
+}
+
+/// Parses `line` as the number of one of `len` options, returning its index.
+fn option_index(line: &str, len: usize) -> Option<usize> {
+    match line.trim().parse::<usize>() {
+        Ok(x @ 1..) if x <= len => Some(x - 1),
+        _ => None,
     }
 }
 
+/// Reports an error reading input, and exits the process.
+fn exit_on_error(err: io::Error) -> ! {
+    eprintln!("input error: {}", err);
+    process::exit(1);
+}
+
+/// Reads a line of input from `source`, for parsing as a CSV row,
+/// treating the end of input as an error.
+fn read_csv_line<S: LineSource + ?Sized>(source: &mut S) -> Result<String, CsvRowError> {
+    read_line_required(source)
+        .map_err(|err|CsvRowError {
+            kind: match err.kind() {
+                io::ErrorKind::UnexpectedEof => CsvRowErrorKind::EndOfInput,
+                kind => CsvRowErrorKind::Io(kind),
+            },
+        })
+}
+
 /// A newtype wrapper for [`StdinExtended`],
 /// to extend it with parsing behaviour,
 /// with the assumption a process should exit upon an IO error.
@@ -191,11 +1202,11 @@ impl ParseStdinExtended {
     /// 
     /// # Examples
     /// 
-    /// ```
+    /// ```no_run
     /// use my_rusttools::ParseStdinExtended;
     /// 
     /// let uinp = ParseStdinExtended::new();
-    /// println!("{:?}", uinp.read_line_parse::<usize>>());
+    /// println!("{:?}", uinp.read_line_parse::<usize>());
     /// ```
     pub fn new() -> ParseStdinExtended {
         ParseStdinExtended(StdinExtended::new())
@@ -206,7 +1217,7 @@ impl ParseStdinExtended {
     /// 
     /// # Examples
     /// 
-    /// ```
+    /// ```no_run
     /// use my_rusttools::ParseStdinExtended;
     /// 
     /// let uinp = ParseStdinExtended::new();
@@ -218,14 +1229,7 @@ impl ParseStdinExtended {
     /// }
     /// ```
     pub fn read_line_parse<T: FromStr>(&self) -> Result<T, T::Err> {
-        self.read_line_new_string()
-            .map_or_else(
-                |err|{
-                    eprintln!("input error: {}", err);
-                    process::exit(1);
-            }, 
-            |x|x.trim().parse()
-        )
+        LineSourceExt::read_line_parse(&mut &self.0)
     }
 
     /// Repeatedly locks the handle of this type,
@@ -233,7 +1237,7 @@ impl ParseStdinExtended {
     /// 
     /// # Examples
     /// 
-    /// ```
+    /// ```no_run
     /// use my_rusttools::ParseStdinExtended;
     /// 
     /// let uinp: usize = ParseStdinExtended::new()
@@ -247,18 +1251,47 @@ impl ParseStdinExtended {
     ///     _ => println!("{uinp} isn't so small!"),
     /// }
     /// ```
-    pub fn read_line_until_parsed<T, F, E>(&self, mut notif: F, mut err_notif: E) -> T where
+    pub fn read_line_until_parsed<T, F, E>(&self, notif: F, err_notif: E) -> T where
     T: FromStr,
     F: FnMut(),
     E: FnMut(T::Err), {
-        loop {
-            notif();
+        LineSourceExt::read_line_until_parsed(&mut &self.0, notif, err_notif)
+    }
 
-            match self.read_line_parse() {
-                Ok(parsed) => return parsed,
-                Err(err) => err_notif(err),
-            }
-        }
+    /// Repeatedly locks the handle of this type,
+    /// until the line of input it reads is parsed,
+    /// or `notif` breaks, or input ends, returning [`None`].
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use std::ops::ControlFlow;
+    /// use my_rusttools::ParseStdinExtended;
+    /// 
+    /// let mut attempts = 0;
+    /// let uinp: Option<usize> = ParseStdinExtended::new()
+    ///     .try_read_line_until_parsed(
+    ///         ||{
+    ///             attempts += 1;
+    ///             println!("Please input a positive number! ({attempts} of 3)");
+    ///             match attempts {
+    ///                 ..=3 => ControlFlow::Continue(()),
+    ///                 _ => ControlFlow::Break(()),
+    ///             }
+    ///         },
+    ///         |err|eprintln!("invalid input: {err}")
+    ///     );
+    /// 
+    /// match uinp {
+    ///     Some(x) => println!("{x}, got it!"),
+    ///     None => println!("Out of attempts..."),
+    /// }
+    /// ```
+    pub fn try_read_line_until_parsed<T, F, E>(&self, notif: F, err_notif: E) -> Option<T> where
+    T: FromStr,
+    F: FnMut() -> ControlFlow<()>,
+    E: FnMut(T::Err), {
+        LineSourceExt::try_read_line_until_parsed(&mut &self.0, notif, err_notif)
     }
 
     /// Repeatedly locks the handle of this type,
@@ -266,7 +1299,7 @@ impl ParseStdinExtended {
     /// 
     /// # Examples
     /// 
-    /// ```
+    /// ```no_run
     /// use my_rusttools::ParseStdinExtended;
     /// 
     /// let uinp = ParseStdinExtended::new()
@@ -281,22 +1314,191 @@ impl ParseStdinExtended {
     /// 
     /// println!("{uinp}");
     /// ```
-    pub fn read_line_until_mapped<T, F, G>(&self, mut f: F, mut notif: G) -> T where
+    pub fn read_line_until_mapped<T, F, G>(&self, f: F, notif: G) -> T where
     F: FnMut(String) -> Option<T>,
     G: FnMut(), {
-        loop {
-            notif();
+        LineSourceExt::read_line_until_mapped(&mut &self.0, f, notif)
+    }
 
-            let uinp = self.read_line_new_string()
-                .map_or_else(|err|{
-                        eprintln!("input error: {}", err);
-                        process::exit(1);
-                    }, &mut f);
+    /// Repeatedly locks the handle of this type,
+    /// until the return value from the passed closure of a [`Some`] enum,
+    /// or `notif` breaks, or input ends, returning [`None`].
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use std::{ops::ControlFlow, time::{Duration, Instant}};
+    /// use my_rusttools::ParseStdinExtended;
+    /// 
+    /// let deadline = Instant::now() + Duration::from_secs(30);
+    /// let uinp = ParseStdinExtended::new()
+    ///     .try_read_line_until_mapped(
+    ///         |x|(x.trim() == "go").then_some(()),
+    ///         ||match Instant::now() < deadline {
+    ///             true => {
+    ///                 println!("Please enter go,");
+    ///                 ControlFlow::Continue(())
+    ///             },
+    ///             false => ControlFlow::Break(()),
+    ///         }
+    ///     );
+    /// 
+    /// println!("{uinp:?}");
+    /// ```
+    pub fn try_read_line_until_mapped<T, F, G>(&self, f: F, notif: G) -> Option<T> where
+    F: FnMut(String) -> Option<T>,
+    G: FnMut() -> ControlFlow<()>, {
+        LineSourceExt::try_read_line_until_mapped(&mut &self.0, f, notif)
+    }
 
-            if let Some(ret) = uinp {
-                return ret;
-            }
-        }
+    /// Repeatedly locks the handle of this type,
+    /// until a yes or no answer is read,
+    /// as [`parse_human_bool`] parses them.
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use my_rusttools::ParseStdinExtended;
+    /// 
+    /// let again = ParseStdinExtended::new()
+    ///     .read_line_until_bool(||println!("Play again? y(es)/n(o),"));
+    /// 
+    /// println!("{again}");
+    /// ```
+    pub fn read_line_until_bool<F: FnMut()>(&self, notif: F) -> bool {
+        LineSourceExt::read_line_until_bool(&mut &self.0, notif)
+    }
+
+    /// Repeatedly locks the handle of this type,
+    /// until a yes or no answer is read, as [`parse_human_bool`] parses them,
+    /// or `notif` breaks, or input ends, returning [`None`].
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use std::ops::ControlFlow;
+    /// use my_rusttools::ParseStdinExtended;
+    /// 
+    /// let again = ParseStdinExtended::new()
+    ///     .try_read_line_until_bool(||{
+    ///         println!("Play again? y(es)/n(o),");
+    ///         ControlFlow::Continue(())
+    ///     });
+    /// 
+    /// println!("{}", again.unwrap_or(false));
+    /// ```
+    pub fn try_read_line_until_bool<F: FnMut() -> ControlFlow<()>>(&self, notif: F) -> Option<bool> {
+        LineSourceExt::try_read_line_until_bool(&mut &self.0, notif)
+    }
+
+    /// Repeatedly locks the handle of this type,
+    /// listing the names of the variants of `E` after each notification,
+    /// until one of them is parsed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use std::str::FromStr;
+    /// use my_rusttools::{ParseStdinExtended, traits::VariantNames};
+    /// 
+    /// enum Mode {
+    ///     Train,
+    ///     Test,
+    /// }
+    /// 
+    /// impl VariantNames for Mode {
+    ///     const VARIANTS: &'static [&'static str] = &["train", "test"];
+    /// }
+    /// 
+    /// impl FromStr for Mode {
+    ///     type Err = ();
+    /// 
+    ///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+    ///         match s {
+    ///             "train" => Ok(Self::Train),
+    ///             "test" => Ok(Self::Test),
+    ///             _ => Err(()),
+    ///         }
+    ///     }
+    /// }
+    /// 
+    /// let mode: Mode = ParseStdinExtended::new()
+    ///     .read_line_parse_enum(||println!("Please choose a mode,"));
+    /// ```
+    pub fn read_line_parse_enum<E, F>(&self, notif: F) -> E where
+    E: FromStr + VariantNames,
+    F: FnMut(), {
+        LineSourceExt::read_line_parse_enum(&mut &self.0, notif)
+    }
+
+    /// Repeatedly locks the handle of this type,
+    /// listing the names of the variants of `E` after each notification,
+    /// until one of them is parsed,
+    /// or `notif` breaks, or input ends, returning [`None`],
+    /// as [`read_line_parse_enum`](Self::read_line_parse_enum) does otherwise.
+    pub fn try_read_line_parse_enum<E, F>(&self, notif: F) -> Option<E> where
+    E: FromStr + VariantNames,
+    F: FnMut() -> ControlFlow<()>, {
+        LineSourceExt::try_read_line_parse_enum(&mut &self.0, notif)
+    }
+
+    /// Prints the options passed, numbered from `1`,
+    /// then repeatedly locks the handle of this type,
+    /// until the number of an option is read,
+    /// returning its index.
+    /// 
+    /// # Panics
+    /// 
+    /// Will panic if `options` is empty, there being nothing to select.
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use my_rusttools::ParseStdinExtended;
+    /// 
+    /// let colours = ["Red", "Green", "Blue"];
+    /// let selected = ParseStdinExtended::new()
+    ///     .select_from(&colours, ||println!("Please choose a colour,"));
+    /// 
+    /// println!("{} it is!", colours[selected]);
+    /// ```
+    pub fn select_from<T, F>(&self, options: &[T], notif: F) -> usize where
+    T: Display,
+    F: FnMut(), {
+        LineSourceExt::select_from(&mut &self.0, options, notif)
+    }
+
+    /// Prints the options passed, numbered from `1`,
+    /// then repeatedly locks the handle of this type,
+    /// until the number of an option is read, returning its index,
+    /// or `notif` breaks, or input ends, returning [`None`].
+    /// 
+    /// # Panics
+    /// 
+    /// Will panic if `options` is empty, there being nothing to select.
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// use std::ops::ControlFlow;
+    /// use my_rusttools::ParseStdinExtended;
+    /// 
+    /// let colours = ["Red", "Green", "Blue"];
+    /// let selected = ParseStdinExtended::new()
+    ///     .try_select_from(&colours, ||{
+    ///         println!("Please choose a colour,");
+    ///         ControlFlow::Continue(())
+    ///     });
+    /// 
+    /// match selected {
+    ///     Some(x) => println!("{} it is!", colours[x]),
+    ///     None => println!("Maybe next time..."),
+    /// }
+    /// ```
+    pub fn try_select_from<T, F>(&self, options: &[T], notif: F) -> Option<usize> where
+    T: Display,
+    F: FnMut() -> ControlFlow<()>, {
+        LineSourceExt::try_select_from(&mut &self.0, options, notif)
     }
 }
 
@@ -330,4 +1532,58 @@ impl Default for ParseStdinExtended {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// A completion callback, returning the candidates which complete a partial line of input,
+/// for binaries to offer completion of paths or commands,
+/// with [`LineSourceExt::read_line_completed`].
+pub type Completer = dyn Fn(&str) -> Vec<String>;
+
+/// The default filesystem completer,
+/// returning the paths which complete `partial`, sorted,
+/// with directories ending in `/`.
+/// 
+/// Paths are completed relative to the current directory,
+/// unless `partial` is absolute. Hidden entries are only completed
+/// when `partial` names them with a leading `.`.
+/// 
+/// # Examples
+/// 
+/// ```no_run
+/// use my_rusttools::complete_path;
+/// 
+/// // Such as `["src/", "stats.txt"]`.
+/// println!("{:?}", complete_path("s"));
+/// ```
+pub fn complete_path(partial: &str) -> Vec<String> {
+    let (dir, prefix) = match partial.rfind('/') {
+        Some(i) => partial.split_at(i + 1),
+        None => ("", partial),
+    };
+    let read_dir = match dir {
+        "" => fs::read_dir("."),
+        _ => fs::read_dir(dir),
+    };
+
+    let mut candidates = read_dir.into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|x|{
+            let name = x.file_name().into_string().ok()?;
+
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                return None;
+            }
+
+            let suffix = match x.file_type().is_ok_and(|x|x.is_dir()) {
+                true => "/",
+                false => "",
+            };
+
+            Some(format!("{}{}{}", dir, name, suffix))
+        })
+        .collect::<Vec<_>>();
+    candidates.sort();
+
+    candidates
+}
@@ -0,0 +1,278 @@
+//! Translating text into, and out of, Pig Latin.
+use std::{
+    borrow::{Borrow, Cow},
+    io::{self, BufRead, Write},
+};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+static VOWELS: &str = "aAeEiIoOuU";
+
+/// Options for how [`pigify_with`] translates words.
+/// 
+/// The default options translate as [`pigify`] does.
+/// 
+/// # Example
+/// 
+/// ```
+/// use my_rusttools::{pigify_with, ConsonantMode, PigifyOptions};
+/// 
+/// let options = PigifyOptions {
+///     consonants: ConsonantMode::Cluster,
+///     vowel_suffix: String::from("way"),
+///     ..PigifyOptions::default()
+/// };
+/// 
+/// assert_eq!("ing-stray and-way", pigify_with("string and", &options));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PigifyOptions {
+    /// Which consonants are moved to the end of words beginning with them.
+    pub consonants: ConsonantMode,
+    /// The suffix for words beginning with a vowel, `"hay"` by default,
+    /// `"way"` and `"yay"` being common alternatives.
+    pub vowel_suffix: String,
+    /// Whether the suffix is separated from the word by a hyphen.
+    pub hyphen: bool,
+    /// Whether a capital moved to the end of a word is moved to its new beginning,
+    /// such as `"The"` becoming `"He-tay"`, rather than `"he-Tay"`.
+    pub preserve_case: bool,
+}
+
+/// How many of the consonants beginning a word [`pigify_with`] moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConsonantMode {
+    /// Only the first grapheme, such as `"string"` becoming `"tring-say"`.
+    #[default]
+    FirstGrapheme,
+    /// The whole cluster of consonants, such as `"string"` becoming `"ing-stray"`.
+    /// 
+    /// A `y` after the first grapheme is treated as a vowel,
+    /// and a `u` following a `q` as part of the cluster.
+    Cluster,
+}
+
+impl Default for PigifyOptions {
+    fn default() -> Self {
+        Self {
+            consonants: ConsonantMode::FirstGrapheme,
+            vowel_suffix: String::from("hay"),
+            hyphen: true,
+            preserve_case: false,
+        }
+    }
+}
+
+/// Roughly translates the provided string
+/// into Pig Latin!
+/// 
+/// # Example
+/// 
+/// ```
+/// use my_rusttools::pigify;
+/// 
+/// let pigified = pigify("Example");
+/// assert_eq!("Example-hay", pigified.as_str());
+/// ```
+pub fn pigify(convert: &str) -> String {
+    pigify_with(convert, &PigifyOptions::default())
+}
+
+/// Translates the provided string into Pig Latin,
+/// as `options` specify.
+/// 
+/// # Example
+/// 
+/// ```
+/// use my_rusttools::{pigify_with, PigifyOptions};
+/// 
+/// let options = PigifyOptions {
+///     hyphen: false,
+///     preserve_case: true,
+///     ..PigifyOptions::default()
+/// };
+/// 
+/// assert_eq!("Hetay endhay", pigify_with("The end", &options));
+/// ```
+pub fn pigify_with(convert: &str, options: &PigifyOptions) -> String {
+    pigify_tokens(convert.trim(), options)
+        .collect()
+}
+
+/// Lazily translates the provided string into Pig Latin,
+/// returning the translation of each word, and what's between them, in turn.
+/// 
+/// Only words are allocated for, the rest being borrowed from `convert`.
+/// 
+/// # Example
+/// 
+/// ```
+/// use std::borrow::Cow;
+/// use my_rusttools::{pigify, pigify_iter};
+/// 
+/// let mut pigified = pigify_iter("Hello, world!");
+/// 
+/// assert_eq!(Some(Cow::Owned(String::from("ello-Hay"))), pigified.next());
+/// assert_eq!(Some(Cow::Borrowed(",")), pigified.next());
+/// assert_eq!(pigify("Hello, world!"), pigify_iter("Hello, world!").collect::<String>());
+/// ```
+pub fn pigify_iter(convert: &str) -> impl Iterator<Item = Cow<'_, str>> {
+    pigify_tokens(convert.trim(), PigifyOptions::default())
+}
+
+/// Translates the text read from `reader` into Pig Latin,
+/// writing it to `writer` a line at a time,
+/// so the whole text is never held in memory.
+/// 
+/// Unlike [`pigify`], the text's leading and trailing whitespace is kept.
+/// 
+/// # Errors
+/// 
+/// Will return [`Err`] if `reader` can't be read, or isn't valid UTF-8,
+/// or `writer` can't be written to.
+/// 
+/// # Example
+/// 
+/// ```
+/// use my_rusttools::pigify_to;
+/// 
+/// let mut pigified = Vec::new();
+/// pigify_to(&mut pigified, "the end\nExample\n".as_bytes()).unwrap();
+/// 
+/// assert_eq!("he-tay end-hay\nExample-hay\n", String::from_utf8(pigified).unwrap());
+/// ```
+pub fn pigify_to<W: Write, R: BufRead>(writer: &mut W, mut reader: R) -> io::Result<()> {
+    let options = PigifyOptions::default();
+    let mut line = String::new();
+
+    while reader.read_line(&mut line)? != 0 {
+        pigify_tokens(&line, &options)
+            .try_for_each(|x|writer.write_all(x.as_bytes()))?;
+        line.clear();
+    }
+
+    Ok(())
+}
+
+/// Splits `convert` at its word boundaries, translating its words as `options` specify.
+fn pigify_tokens<'a, O>(convert: &'a str, options: O) -> impl Iterator<Item = Cow<'a, str>>
+where
+    O: Borrow<PigifyOptions> + 'a, {
+        convert.split_word_bounds()
+            .map(move|x|match x.contains(char::is_alphabetic) {
+                true => Cow::Owned(pigify_word(x, options.borrow())),
+                false => Cow::Borrowed(x), // Guard for cases where the item isn't a word.
+            })
+    }
+
+/// Translates a single word, as [`pigify_with`] does.
+fn pigify_word(word: &str, options: &PigifyOptions) -> String {
+    let graphs = word.graphemes(true).collect::<Vec<_>>(); // Splits the item into it's graphemes.
+    let is_vowel = |x: &str|x.contains(|y|VOWELS.contains(y));
+
+    let moved = match options.consonants {
+        ConsonantMode::FirstGrapheme => usize::from(!is_vowel(graphs[0])),
+        ConsonantMode::Cluster => graphs.iter()
+            .enumerate()
+            .position(|(i, x)|is_vowel(x) && !(i > 0 && graphs[i - 1].eq_ignore_ascii_case("q") && x.eq_ignore_ascii_case("u"))
+                || i > 0 && x.eq_ignore_ascii_case("y"))
+            .unwrap_or(graphs.len()),
+    };
+
+    let mut header = graphs[..moved].concat();
+    let mut rest = graphs[moved..].concat();
+    let mut suffix = match moved {
+        0 => options.vowel_suffix.clone(),
+        _ => String::from("ay"),
+    };
+
+    if options.preserve_case && word.starts_with(char::is_uppercase) {
+        let shouted = word.chars().count() > 1 && word.chars().filter(|x|x.is_alphabetic()).all(char::is_uppercase);
+
+        match (shouted, moved) {
+            (true, _) => suffix = suffix.to_uppercase(),
+            (false, x) if x == 0 || rest.is_empty() => (),
+            (false, _) => {
+                header = header.to_lowercase();
+                rest = capitalise(&rest);
+            },
+        }
+    }
+
+    let hyphen = match options.hyphen && !rest.is_empty() {
+        true => "-",
+        false => "",
+    };
+
+    format!("{}{}{}{}", rest, hyphen, header, suffix)
+}
+
+/// Uppercases the first character of `word`.
+fn capitalise(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(x) => x.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Translates text [`pigify`] translated into Pig Latin
+/// back into English.
+/// 
+/// Words beginning with `h` and then a vowel
+/// translate into the same Pig Latin as the rest of the word would,
+/// such as `"hello"` and `"ello"` both becoming `"ello-hay"`,
+/// so come back without their `h`.
+/// 
+/// # Example
+/// 
+/// ```
+/// use my_rusttools::unpigify;
+/// 
+/// assert_eq!("Example", unpigify("Example-hay"));
+/// assert_eq!("the end", unpigify("he-tay end-hay"));
+/// ```
+pub fn unpigify(convert: &str) -> String {
+    let is_word = |x: &str|x.contains(char::is_alphabetic);
+
+    // Returns the grapheme moved to the end of a word, if `x` is one, followed by "ay".
+    fn moved_graph(x: &str) -> Option<&str> {
+        x.strip_suffix("ay")
+            .filter(|x|x.graphemes(true).count() == 1)
+    }
+
+    let words = convert.trim()
+        .split_word_bounds()
+        .collect::<Vec<_>>();
+    let mut ret = String::with_capacity(convert.len());
+    let mut i = 0;
+
+    while i < words.len() {
+        match words[i..] {
+            [x, "-", y, ..] if is_word(x) && is_word(y) && moved_graph(y).is_some() => {
+                match (y, x.graphemes(true).next()) {
+                    ("hay", Some(header_graph)) if header_graph.contains(|z|VOWELS.contains(z)) => ret.push_str(x), // The word began with a vowel, so only had "-hay" appended.
+                    _ => {
+                        ret.push_str(moved_graph(y).unwrap_or_default());
+                        ret.push_str(x);
+                    },
+                }
+
+                i += 3;
+            },
+            // Single grapheme words beginning with a consonant have no hyphen, having nothing before it.
+            [x, ..] if moved_graph(x).is_some_and(|y|!y.contains(|z|VOWELS.contains(z))) => {
+                ret.push_str(moved_graph(x).unwrap_or(x));
+                i += 1;
+            },
+            [x, ..] => {
+                ret.push_str(x);
+                i += 1;
+            },
+            [] => break,
+        }
+    }
+
+    ret
+}
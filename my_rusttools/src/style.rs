@@ -0,0 +1,102 @@
+//! Styling for terminal output, with ANSI escape codes,
+//! which are left out when standard output isn't a terminal,
+//! or the `NO_COLOR` environment variable is set.
+use std::{
+    env,
+    fmt::Display,
+    io::{self, IsTerminal},
+};
+
+/// The colours text can be styled in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Colour {
+    /// The foreground colour's SGR code.
+    fn code(self) -> u8 {
+        30 + self as u8
+    }
+}
+
+/// Returns whether output should be styled,
+/// being when standard output is a terminal,
+/// and `NO_COLOR` isn't set to a non-empty value.
+pub fn enabled() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").map_or(true, |x|x.is_empty())
+}
+
+/// Returns `code` when output is styled,
+/// and an empty string otherwise.
+fn escape(code: impl Display) -> String {
+    match enabled() {
+        true => format!("\x1B[{}", code),
+        false => String::new(),
+    }
+}
+
+/// Wraps `text` in the SGR `code`, resetting the style after it.
+fn sgr(text: impl Display, code: impl Display) -> String {
+    match enabled() {
+        true => format!("\x1B[{}m{}\x1B[0m", code, text),
+        false => text.to_string(),
+    }
+}
+
+/// Styles `text` in bold.
+///
+/// # Examples
+/// ```
+/// use my_rusttools::style;
+///
+/// println!("{}", style::bold("Important!"));
+/// ```
+pub fn bold(text: impl Display) -> String {
+    sgr(text, 1)
+}
+
+/// Styles `text` in `colour`.
+///
+/// # Examples
+/// ```
+/// use my_rusttools::style::{self, Colour};
+///
+/// println!("{}", style::colour("You win!", Colour::Green));
+/// ```
+pub fn colour(text: impl Display, colour: Colour) -> String {
+    sgr(text, colour.code())
+}
+
+/// Returns the escape code which clears the current line,
+/// returning the cursor to its start.
+pub fn clear_line() -> String {
+    match enabled() {
+        true => format!("\r{}", escape("2K")),
+        false => String::new(),
+    }
+}
+
+/// Returns the escape code which moves the cursor up `lines` lines.
+pub fn cursor_up(lines: usize) -> String {
+    match lines {
+        0 => String::new(),
+        _ => escape(format!("{}A", lines)),
+    }
+}
+
+/// Returns the escape code which clears the screen,
+/// moving the cursor to its top left.
+pub fn clear_screen() -> String {
+    match enabled() {
+        true => format!("{}{}", escape("2J"), escape("H")),
+        false => String::new(),
+    }
+}
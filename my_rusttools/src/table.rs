@@ -0,0 +1,126 @@
+//! Rendering rows of values as aligned tables,
+//! for presentable command line output.
+use std::fmt::Display;
+
+/// The formats tables can be rendered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableFormat {
+    /// A table bordered with `+`, `-` and `|`.
+    #[default]
+    Ascii,
+    /// A GitHub flavoured Markdown table.
+    Markdown,
+}
+
+/// Renders `rows` as a table, under `headers`, in `format`.
+///
+/// Each row has a cell for each header,
+/// with cells past the last header left out, and missing cells left empty.
+/// Columns whose cells all begin with a digit are aligned right,
+/// and all others left.
+///
+/// # Examples
+/// ```
+/// use my_rusttools::{render_table, TableFormat};
+///
+/// let rows = [["apples", "12"], ["pears", "3"]];
+///
+/// assert_eq!(
+///     "+--------+-------+\n\
+///      | fruit  | count |\n\
+///      +--------+-------+\n\
+///      | apples |    12 |\n\
+///      | pears  |     3 |\n\
+///      +--------+-------+\n",
+///     render_table(&["fruit", "count"], rows, TableFormat::Ascii),
+/// );
+/// assert_eq!(
+///     "| fruit  | count |\n\
+///      |:-------|------:|\n\
+///      | apples |    12 |\n\
+///      | pears  |     3 |\n",
+///     render_table(&["fruit", "count"], rows, TableFormat::Markdown),
+/// );
+/// ```
+pub fn render_table<H, R>(headers: &[H], rows: R, format: TableFormat) -> String
+where
+    H: Display,
+    R: IntoIterator,
+    R::Item: IntoIterator,
+    <R::Item as IntoIterator>::Item: Display, {
+        let escape = |x: String|match format {
+            TableFormat::Ascii => x,
+            TableFormat::Markdown => x.replace('|', "\\|"),
+        };
+
+        let headers = headers.iter()
+            .map(|x|escape(x.to_string()))
+            .collect::<Vec<_>>();
+        let rows = rows.into_iter()
+            .map(|row|{
+                let mut cells = row.into_iter()
+                    .take(headers.len())
+                    .map(|x|escape(x.to_string()))
+                    .collect::<Vec<_>>();
+                cells.resize(headers.len(), String::new());
+                cells
+            })
+            .collect::<Vec<_>>();
+
+        let widths = (0..headers.len())
+            .map(|i|rows.iter()
+                .chain([&headers])
+                .map(|x|x[i].chars().count())
+                .max()
+                .unwrap_or(0)
+            )
+            .collect::<Vec<_>>();
+        let right_aligned = (0..headers.len())
+            .map(|i|!rows.is_empty() && rows.iter().all(|x|x[i].starts_with(|x: char|x.is_ascii_digit())))
+            .collect::<Vec<_>>();
+
+        let render_row = |row: &[String]|{
+            let cells = row.iter()
+                .zip(&widths)
+                .zip(&right_aligned)
+                .map(|((x, width), right)|match right {
+                    true => format!(" {:>width$} ", x),
+                    false => format!(" {:<width$} ", x),
+                })
+                .collect::<Vec<_>>();
+
+            format!("|{}|\n", cells.join("|"))
+        };
+
+        let mut table = String::new();
+
+        match format {
+            TableFormat::Ascii => {
+                let border = widths.iter()
+                    .map(|x|"-".repeat(x + 2))
+                    .collect::<Vec<_>>();
+                let border = format!("+{}+\n", border.join("+"));
+
+                table.push_str(&border);
+                table.push_str(&render_row(&headers));
+                table.push_str(&border);
+                rows.iter().for_each(|x|table.push_str(&render_row(x)));
+                table.push_str(&border);
+            },
+            TableFormat::Markdown => {
+                let divider = widths.iter()
+                    .zip(&right_aligned)
+                    .map(|(x, right)|match right {
+                        true => format!("{}:", "-".repeat(x + 1)),
+                        false => format!(":{}", "-".repeat(x + 1)),
+                    })
+                    .collect::<Vec<_>>();
+
+                table.push_str(&render_row(&headers));
+                table.push_str(&format!("|{}|\n", divider.join("|")));
+                rows.iter().for_each(|x|table.push_str(&render_row(x)));
+            },
+        }
+
+        table
+    }
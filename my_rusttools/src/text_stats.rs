@@ -0,0 +1,64 @@
+//! Statistics about the words and graphemes of text.
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::traits::SummariseCollection;
+
+/// Statistics about a text's words and graphemes,
+/// as returned by [`text_stats`].
+///
+/// Words are compared case-insensitively,
+/// so `"The"` and `"the"` are the same word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStats {
+    /// The number of words.
+    pub word_count: usize,
+    /// The number of different words.
+    pub unique_words: usize,
+    /// The number of graphemes, including those which aren't in words.
+    pub grapheme_count: usize,
+    /// The mean number of graphemes in a word, `0.0` if there are no words.
+    pub average_word_length: f64,
+    /// The most common word, lowercased, with the number of times it appears.
+    ///
+    /// Of words appearing equally often, the first alphabetically is chosen.
+    pub most_common_word: Option<(String, usize)>,
+}
+
+/// Counts the words and graphemes of `text`.
+///
+/// # Examples
+/// ```
+/// use my_rusttools::text_stats;
+///
+/// let stats = text_stats("The cat saw the dog.");
+///
+/// assert_eq!(5, stats.word_count);
+/// assert_eq!(4, stats.unique_words);
+/// assert_eq!(20, stats.grapheme_count);
+/// assert_eq!(3.0, stats.average_word_length);
+/// assert_eq!(Some((String::from("the"), 2)), stats.most_common_word);
+/// ```
+pub fn text_stats(text: &str) -> TextStats {
+    let words = text.unicode_words()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    let word_graphemes = words.iter()
+        .map(|x|x.graphemes(true).count())
+        .sum::<usize>();
+    let counts = words.iter().count_items();
+
+    let most_common_word = counts.iter()
+        .max_by(|x, y|x.1.cmp(y.1).then_with(||y.0.cmp(x.0)))
+        .map(|(word, count)|(word.to_string(), *count));
+
+    TextStats {
+        word_count: words.len(),
+        unique_words: counts.len(),
+        grapheme_count: text.graphemes(true).count(),
+        average_word_length: match words.len() {
+            0 => 0.0,
+            x => word_graphemes as f64 / x as f64,
+        },
+        most_common_word,
+    }
+}
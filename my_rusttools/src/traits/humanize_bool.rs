@@ -1,6 +1,6 @@
 //! A small convinience implementation,
 //! with methods for conveting booleans into
-//! string referances.
+//! string referances, and back.
 
 /// A interface, intended to provide convenience methods,
 /// for conveting boolean values into related string terms.
@@ -69,4 +69,50 @@ impl HumanizeBooleans for bool {
             "Off"
         }
     }
-}
\ No newline at end of file
+}
+
+/// Parses the human terms for booleans,
+/// as [`HumanizeBooleans`] converts them into, back into booleans.
+/// 
+/// `yes`, `y`, `on`, `true` and `1` are parsed as `true`,
+/// and `no`, `n`, `off`, `false` and `0` as `false`,
+/// ignoring case and surrounding whitespace.
+/// 
+/// # Examples
+/// ```
+/// use my_rusttools::traits::parse_human_bool;
+/// 
+/// assert_eq!(Some(true), parse_human_bool("Yes"));
+/// assert_eq!(Some(false), parse_human_bool(" OFF\n"));
+/// assert_eq!(None, parse_human_bool("maybe"));
+/// ```
+pub fn parse_human_bool(s: &str) -> Option<bool> {
+    match s.trim().to_lowercase().as_str() {
+        "yes" | "y" | "on" | "true" | "1" => Some(true),
+        "no" | "n" | "off" | "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// A interface, intended to provide a convenience method,
+/// for converting string terms into related boolean values.
+pub trait DehumanizeBool {
+    /// Converts a string term into a boolean,
+    /// as [`parse_human_bool`] does.
+    /// 
+    /// # Examples
+    /// ```
+    /// use my_rusttools::traits::{DehumanizeBool, HumanizeBooleans};
+    /// 
+    /// assert_eq!(Some(true), true.on_off().dehumanize_bool());
+    /// assert_eq!(None, "".dehumanize_bool());
+    /// ```
+    fn dehumanize_bool(&self) -> Option<bool>;
+}
+
+impl<T: AsRef<str> + ?Sized> DehumanizeBool for T {
+    #[inline]
+    fn dehumanize_bool(&self) -> Option<bool> {
+        parse_human_bool(self.as_ref())
+    }
+}
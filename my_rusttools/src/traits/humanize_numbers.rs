@@ -0,0 +1,125 @@
+//! Convenience methods for displaying numbers,
+//! as percentages, ordinals, and with separated thousands.
+
+/// An interface, providing convenience methods,
+/// for converting numbers into strings which read more naturally.
+pub trait HumanizeNumbers {
+    /// Converts a ratio into a percentage,
+    /// rounded to `decimals` decimal places.
+    ///
+    /// # Examples
+    /// ```
+    /// use my_rusttools::traits::HumanizeNumbers;
+    ///
+    /// assert_eq!("25.6%", 0.256.as_percentage(1));
+    /// assert_eq!("200%", 2.as_percentage(0));
+    /// ```
+    #[allow(clippy::wrong_self_convention)] // Numbers are `Copy`, so are cheap to take by value.
+    fn as_percentage(self, decimals: usize) -> String;
+
+    /// Converts a number into a string,
+    /// with its thousands separated by commas.
+    ///
+    /// # Examples
+    /// ```
+    /// use my_rusttools::traits::HumanizeNumbers;
+    ///
+    /// assert_eq!("1,234,567", 1234567.thousands_separated());
+    /// assert_eq!("-9,876.5", (-9876.5).thousands_separated());
+    /// ```
+    fn thousands_separated(self) -> String;
+}
+
+/// An interface, providing a convenience method,
+/// for converting integers into ordinals.
+pub trait HumanizeOrdinals {
+    /// Converts an integer into its ordinal,
+    /// such as `1st`, `2nd` or `11th`.
+    ///
+    /// # Examples
+    /// ```
+    /// use my_rusttools::traits::HumanizeOrdinals;
+    ///
+    /// assert_eq!("1st", 1.ordinal());
+    /// assert_eq!("12th", 12.ordinal());
+    /// assert_eq!("23rd", 23.ordinal());
+    /// ```
+    fn ordinal(self) -> String;
+}
+
+/// Inserts commas between the thousands of the integer part
+/// of a formatted number.
+fn separate_thousands(formatted: &str) -> String {
+    let (sign, unsigned) = match formatted.strip_prefix('-') {
+        Some(x) => ("-", x),
+        None => ("", formatted),
+    };
+    let (integer, fraction) = match unsigned.find('.') {
+        Some(i) => unsigned.split_at(i),
+        None => (unsigned, ""),
+    };
+
+    // Non-finite floats, such as `NaN`, have no digits to separate.
+    if !integer.chars().all(|x|x.is_ascii_digit()) {
+        return formatted.to_string();
+    }
+
+    let separated = integer.chars()
+        .enumerate()
+        .fold(String::with_capacity(integer.len() * 4 / 3), |mut acc, (i, x)|{
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                acc.push(',');
+            }
+            acc.push(x);
+            acc
+        });
+
+    format!("{}{}{}", sign, separated, fraction)
+}
+
+macro_rules! impl_humanize_numbers {
+    ($($t:ty),*) => {
+        $(
+            impl HumanizeNumbers for $t {
+                #[inline]
+                fn as_percentage(self, decimals: usize) -> String {
+                    format!("{:.*}%", decimals, self as f64 * 100.0)
+                }
+
+                #[inline]
+                fn thousands_separated(self) -> String {
+                    separate_thousands(&self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_humanize_ordinals {
+    ($($t:ty),*) => {
+        $(
+            impl HumanizeOrdinals for $t {
+                #[allow(unused_comparisons)]
+                fn ordinal(self) -> String {
+                    let magnitude = match self < 0 {
+                        true => (self as i128).unsigned_abs(),
+                        false => self as u128,
+                    };
+
+                    let suffix = match (magnitude % 10, magnitude % 100) {
+                        (_, 11..=13) => "th",
+                        (1, _) => "st",
+                        (2, _) => "nd",
+                        (3, _) => "rd",
+                        _ => "th",
+                    };
+
+                    format!("{}{}", self, suffix)
+                }
+            }
+        )*
+    };
+}
+
+impl_humanize_numbers!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+impl_humanize_ordinals!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
@@ -0,0 +1,148 @@
+//! Convenience methods for displaying durations and byte sizes,
+//! in the units which read most naturally.
+use std::time::Duration;
+
+/// The formats durations and byte sizes can be humanized in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HumanFormat {
+    /// Abbreviated units, such as `1m 30s`, or `1.5 KiB`.
+    #[default]
+    Short,
+    /// Units in full, such as `1 minute, 30 seconds`, or `1.5 kibibytes`.
+    Long,
+}
+
+/// An interface, providing a convenience method,
+/// for converting durations into strings which read naturally.
+pub trait HumanizeDuration {
+    /// Converts a duration into a string, in `format`.
+    ///
+    /// Durations of a minute or more are written in whole days, hours, minutes and seconds,
+    /// leaving out units which are zero.
+    /// Shorter durations are written in their largest unit,
+    /// to a decimal place.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use my_rusttools::traits::{HumanFormat, HumanizeDuration};
+    ///
+    /// assert_eq!("1m 30s", Duration::from_secs(90).humanize(HumanFormat::Short));
+    /// assert_eq!("1 minute, 30 seconds", Duration::from_secs(90).humanize(HumanFormat::Long));
+    /// assert_eq!("12.5ms", Duration::from_micros(12_500).humanize(HumanFormat::Short));
+    /// ```
+    fn humanize(&self, format: HumanFormat) -> String;
+}
+
+/// An interface, providing a convenience method,
+/// for converting numbers of bytes into strings which read naturally.
+pub trait HumanizeBytes {
+    /// Converts a number of bytes into a string, in `format`,
+    /// in the largest binary unit it has a whole one of,
+    /// to a decimal place.
+    ///
+    /// # Examples
+    /// ```
+    /// use my_rusttools::traits::{HumanFormat, HumanizeBytes};
+    ///
+    /// assert_eq!("1.5 KiB", 1536u64.humanize_bytes(HumanFormat::Short));
+    /// assert_eq!("1.5 kibibytes", 1536u64.humanize_bytes(HumanFormat::Long));
+    /// assert_eq!("1 byte", 1u8.humanize_bytes(HumanFormat::Long));
+    /// ```
+    fn humanize_bytes(self, format: HumanFormat) -> String;
+}
+
+/// Formats `value` to a decimal place, leaving off a trailing `.0`.
+fn one_decimal(value: f64) -> String {
+    let formatted = format!("{:.1}", value);
+
+    match formatted.strip_suffix(".0") {
+        Some(x) => x.to_string(),
+        None => formatted,
+    }
+}
+
+/// Formats `value` in `unit`, from its short and long names,
+/// pluralising the long name unless `value` is one.
+fn with_unit(value: &str, (short, long): (&str, &str), format: HumanFormat, short_separator: &str) -> String {
+    match format {
+        HumanFormat::Short => format!("{}{}{}", value, short_separator, short),
+        HumanFormat::Long if value == "1" => format!("{} {}", value, long),
+        HumanFormat::Long => format!("{} {}s", value, long),
+    }
+}
+
+impl HumanizeDuration for Duration {
+    fn humanize(&self, format: HumanFormat) -> String {
+        const LARGE_UNITS: [(u64, (&str, &str)); 4] = [
+            (86_400, ("d", "day")),
+            (3_600, ("h", "hour")),
+            (60, ("m", "minute")),
+            (1, ("s", "second")),
+        ];
+        const SMALL_UNITS: [(f64, (&str, &str)); 4] = [
+            (1.0, ("s", "second")),
+            (1e-3, ("ms", "millisecond")),
+            (1e-6, ("µs", "microsecond")),
+            (1e-9, ("ns", "nanosecond")),
+        ];
+
+        if self.as_secs() < 60 {
+            let seconds = self.as_secs_f64();
+            let (scale, unit) = SMALL_UNITS.into_iter()
+                .find(|(scale, _)|seconds >= *scale)
+                .unwrap_or(SMALL_UNITS[0]); // Zero reads most naturally in seconds.
+
+            return with_unit(&one_decimal(seconds / scale), unit, format, "");
+        }
+
+        let mut remaining = self.as_secs();
+        let parts = LARGE_UNITS.into_iter()
+            .filter_map(|(size, unit)|{
+                let count = remaining / size;
+                remaining %= size;
+
+                (count > 0).then(||with_unit(&count.to_string(), unit, format, ""))
+            })
+            .collect::<Vec<_>>();
+
+        match format {
+            HumanFormat::Short => parts.join(" "),
+            HumanFormat::Long => parts.join(", "),
+        }
+    }
+}
+
+macro_rules! impl_humanize_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl HumanizeBytes for $t {
+                fn humanize_bytes(self, format: HumanFormat) -> String {
+                    const UNITS: [(&str, &str); 7] = [
+                        ("B", "byte"),
+                        ("KiB", "kibibyte"),
+                        ("MiB", "mebibyte"),
+                        ("GiB", "gibibyte"),
+                        ("TiB", "tebibyte"),
+                        ("PiB", "pebibyte"),
+                        ("EiB", "exbibyte"),
+                    ];
+
+                    let bytes = self as u128;
+                    let exponent = (0..UNITS.len())
+                        .rev()
+                        .find(|x|bytes >= 1 << (10 * x))
+                        .unwrap_or(0);
+                    let value = match exponent {
+                        0 => bytes.to_string(),
+                        x => one_decimal(bytes as f64 / (1u128 << (10 * x)) as f64),
+                    };
+
+                    with_unit(&value, UNITS[exponent], format, " ")
+                }
+            }
+        )*
+    };
+}
+
+impl_humanize_bytes!(u8, u16, u32, u64, u128, usize);
@@ -1,6 +1,14 @@
 //! General tool traits.
 mod summarise_collection;
 mod humanize_bool;
+mod humanize_numbers;
+mod humanize_units;
+mod text_transform;
+mod variant_names;
 
 pub use humanize_bool::*;
+pub use humanize_numbers::*;
+pub use humanize_units::*;
 pub use summarise_collection::*;
+pub use text_transform::*;
+pub use variant_names::*;
@@ -77,6 +77,48 @@ where
         /// ```
         fn count_items(self) -> HashMap<Self::Item, usize>;
 
+        /// Counts the number of values in a collection
+        /// under each key `key_fn` projects them to.
+        /// 
+        /// # Examples
+        /// ```
+        /// use my_rusttools::traits::SummariseCollection;
+        /// 
+        /// let a = ["One", "Two", "Three", "Four"];
+        /// 
+        /// let iter = a.iter();
+        /// let counts = iter.group_counts_by(|x|x.len());
+        /// 
+        /// assert_eq!(Some(&2), counts.get(&3));
+        /// assert_eq!(Some(&1), counts.get(&4));
+        /// assert_eq!(Some(&1), counts.get(&5));
+        /// ```
+        fn group_counts_by<K, F>(self, key_fn: F) -> HashMap<K, usize>
+        where
+            K: Eq + Hash,
+            F: FnMut(&Self::Item) -> K;
+
+        /// Groups the values of a collection
+        /// under each key `key_fn` projects them to,
+        /// keeping the order they appeared in.
+        /// 
+        /// # Examples
+        /// ```
+        /// use my_rusttools::traits::SummariseCollection;
+        /// 
+        /// let a = [1, 2, 3, 4, 5];
+        /// 
+        /// let iter = a.into_iter();
+        /// let groups = iter.group_items_by(|x|x % 2 == 0);
+        /// 
+        /// assert_eq!(Some(&vec![1, 3, 5]), groups.get(&false));
+        /// assert_eq!(Some(&vec![2, 4]), groups.get(&true));
+        /// ```
+        fn group_items_by<K, F>(self, key_fn: F) -> HashMap<K, Vec<Self::Item>>
+        where
+            K: Eq + Hash,
+            F: FnMut(&Self::Item) -> K;
+
         /// Finds the most common item in a collection.
         /// 
         /// If multiple items are the most common,
@@ -181,6 +223,92 @@ where
                     .into_iter()
                     .min_by(|x, y|x.1.cmp(&y.1))
             }
+
+        /// Finds the proportion of a collection each value makes up.
+        /// 
+        /// Returns a [`HashMap`] with keys defined as the type,
+        /// `Self::Item`, with the proportions of the collection
+        /// each value makes up, from `0.0` to `1.0`, as a `f64`.
+        /// 
+        /// # Examples
+        /// ```
+        /// use my_rusttools::traits::SummariseCollection;
+        /// 
+        /// let a = ["One", "Two", "Two", "Two"];
+        /// 
+        /// let iter = a.iter();
+        /// let frequencies = iter.frequencies();
+        /// 
+        /// assert_eq!(Some(&0.25), frequencies.get(&a[0]));
+        /// assert_eq!(Some(&0.75), frequencies.get(&a[1]));
+        /// ```
+        fn frequencies(self) -> HashMap<Self::Item, f64>
+        where
+            Self: Sized {
+                let counts = self.count_items();
+                let total = counts.values().sum::<usize>() as f64;
+
+                counts.into_iter()
+                    .map(|(item, count)|(item, count as f64 / total))
+                    .collect()
+            }
+
+        /// Calculates the Shannon entropy of a collection, in bits,
+        /// measuring how evenly spread its values are.
+        /// 
+        /// Collections of a single value, or no values, have an entropy of `0.0`,
+        /// with `n` values appearing equally often having an entropy of `log2(n)`.
+        /// 
+        /// # Examples
+        /// ```
+        /// use my_rusttools::traits::SummariseCollection;
+        /// 
+        /// let even = ["One", "Two", "Three", "Four"];
+        /// let skewed = ["One", "One", "One", "Two"];
+        /// 
+        /// assert_eq!(2.0, even.iter().entropy());
+        /// assert!(skewed.iter().entropy() < 1.0);
+        /// ```
+        fn entropy(self) -> f64
+        where
+            Self: Sized {
+                self.frequencies()
+                    .into_values()
+                    .map(|x|-x * x.log2())
+                    .sum::<f64>()
+                    .max(0.0) // Avoids returning `-0.0`, for collections of a single value.
+            }
+
+        /// Finds the proportion of a collection made up by
+        /// its `k` most common values.
+        /// 
+        /// Empty collections return `0.0`.
+        /// 
+        /// # Examples
+        /// ```
+        /// use my_rusttools::traits::SummariseCollection;
+        /// 
+        /// let a = ["One", "Two", "Two", "Three", "Three", "Three", "Four", "Four"];
+        /// 
+        /// assert_eq!(0.375, a.iter().coverage_of_top(1));
+        /// assert_eq!(0.625, a.iter().coverage_of_top(2));
+        /// assert_eq!(1.0, a.iter().coverage_of_top(10));
+        /// ```
+        fn coverage_of_top(self, k: usize) -> f64
+        where
+            Self: Sized {
+                let mut counts = self.count_items()
+                    .into_values()
+                    .collect::<Vec<_>>();
+                let total = counts.iter().sum::<usize>();
+
+                if total == 0 {
+                    return 0.0;
+                }
+
+                counts.sort_unstable_by(|x, y|y.cmp(x));
+                counts.into_iter().take(k).sum::<usize>() as f64 / total as f64
+            }
     }
 
 impl<T: Iterator> SummariseCollection for T 
@@ -199,4 +327,27 @@ where
 
             ret
         }
-    }
\ No newline at end of file
+
+        fn group_counts_by<K, F>(self, mut key_fn: F) -> HashMap<K, usize>
+        where
+            K: Eq + Hash,
+            F: FnMut(&T::Item) -> K, {
+                self.map(|x|key_fn(&x))
+                    .count_items()
+            }
+
+        fn group_items_by<K, F>(self, mut key_fn: F) -> HashMap<K, Vec<T::Item>>
+        where
+            K: Eq + Hash,
+            F: FnMut(&T::Item) -> K, {
+                let mut ret: HashMap<K, Vec<T::Item>> = HashMap::new();
+
+                for item in self {
+                    ret.entry(key_fn(&item))
+                        .or_default()
+                        .push(item);
+                }
+
+                ret
+            }
+    }
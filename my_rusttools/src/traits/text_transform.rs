@@ -0,0 +1,164 @@
+//! A trait for toy transformations of text,
+//! such as Pig Latin, or ciphers,
+//! so one can be swapped for another.
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{pigify_with, unpigify, PigifyOptions};
+
+/// An interface for transforming text into new text.
+///
+/// Functions taking a `&str` and returning a `String`,
+/// such as [`pigify`](crate::pigify), implement it,
+/// as do the types of this module.
+///
+/// # Examples
+/// ```
+/// use my_rusttools::{pigify, traits::{Rot13, SpongeCase, TextTransform}};
+///
+/// let transforms: [&dyn TextTransform; 3] = [&pigify, &Rot13, &SpongeCase];
+/// let transformed = transforms.map(|x|x.transform("Hello"));
+///
+/// assert_eq!(["ello-Hay", "Uryyb", "hElLo"], transformed);
+/// ```
+pub trait TextTransform {
+    /// Transforms `text` into new text.
+    fn transform(&self, text: &str) -> String;
+}
+
+impl<F: Fn(&str) -> String> TextTransform for F {
+    fn transform(&self, text: &str) -> String {
+        self(text)
+    }
+}
+
+/// Translates text into Pig Latin, with the options it holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pigify(pub PigifyOptions);
+
+impl TextTransform for Pigify {
+    fn transform(&self, text: &str) -> String {
+        pigify_with(text, &self.0)
+    }
+}
+
+/// Translates text from Pig Latin, as [`unpigify`] does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Unpigify;
+
+impl TextTransform for Unpigify {
+    fn transform(&self, text: &str) -> String {
+        unpigify(text)
+    }
+}
+
+/// Rotates ASCII letters 13 places through the alphabet,
+/// transforming text back when applied twice.
+///
+/// # Examples
+/// ```
+/// use my_rusttools::traits::{Rot13, TextTransform};
+///
+/// assert_eq!("Uryyb, jbeyq!", Rot13.transform("Hello, world!"));
+/// assert_eq!("Hello, world!", Rot13.transform(&Rot13.transform("Hello, world!")));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rot13;
+
+impl TextTransform for Rot13 {
+    fn transform(&self, text: &str) -> String {
+        text.chars()
+            .map(|x|match x {
+                'a'..='z' => ((x as u8 - b'a' + 13) % 26 + b'a') as char,
+                'A'..='Z' => ((x as u8 - b'A' + 13) % 26 + b'A') as char,
+                _ => x,
+            })
+            .collect()
+    }
+}
+
+/// Replaces letters with the numbers resembling them.
+///
+/// # Examples
+/// ```
+/// use my_rusttools::traits::{Leetspeak, TextTransform};
+///
+/// assert_eq!("L337 5p34k", Leetspeak.transform("Leet speak"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Leetspeak;
+
+impl TextTransform for Leetspeak {
+    fn transform(&self, text: &str) -> String {
+        text.chars()
+            .map(|x|match x.to_ascii_lowercase() {
+                'a' => '4',
+                'e' => '3',
+                'i' => '1',
+                'o' => '0',
+                's' => '5',
+                't' => '7',
+                _ => x,
+            })
+            .collect()
+    }
+}
+
+/// Capitalises the first letter of each word,
+/// lowercasing the rest.
+///
+/// # Examples
+/// ```
+/// use my_rusttools::traits::{TextTransform, TitleCase};
+///
+/// assert_eq!("The Quick Brown Fox's Den", TitleCase.transform("the QUICK brown fox's den"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TitleCase;
+
+impl TextTransform for TitleCase {
+    fn transform(&self, text: &str) -> String {
+        text.split_word_bounds()
+            .flat_map(|x|{
+                let mut chars = x.chars();
+                let first = chars.next().map(char::to_uppercase);
+
+                first.into_iter()
+                    .flatten()
+                    .chain(chars.flat_map(char::to_lowercase))
+            })
+            .collect()
+    }
+}
+
+/// Alternates the case of letters, starting lowercase,
+/// as in the mocking sponge meme.
+///
+/// # Examples
+/// ```
+/// use my_rusttools::traits::{SpongeCase, TextTransform};
+///
+/// assert_eq!("iT's NoT fUnNy", SpongeCase.transform("It's not funny"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpongeCase;
+
+impl TextTransform for SpongeCase {
+    fn transform(&self, text: &str) -> String {
+        text.chars()
+            .scan(false, |upper, x|{
+                if !x.is_alphabetic() {
+                    return Some(vec![x]);
+                }
+
+                let cased = match *upper {
+                    true => x.to_uppercase().collect(),
+                    false => x.to_lowercase().collect(),
+                };
+                *upper = !*upper;
+
+                Some(cased)
+            })
+            .flatten()
+            .collect()
+    }
+}
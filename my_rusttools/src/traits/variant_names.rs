@@ -0,0 +1,38 @@
+//! A trait for listing the names of an enum's variants,
+//! such as for prompting which can be chosen.
+
+/// An interface, providing the names of a type's variants,
+/// as they are parsed with [`FromStr`](std::str::FromStr).
+///
+/// # Examples
+/// ```
+/// use std::str::FromStr;
+/// use my_rusttools::traits::VariantNames;
+///
+/// enum Activation {
+///     Sigmoid,
+///     Relu,
+/// }
+///
+/// impl VariantNames for Activation {
+///     const VARIANTS: &'static [&'static str] = &["sigmoid", "relu"];
+/// }
+///
+/// impl FromStr for Activation {
+///     type Err = ();
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         match s {
+///             "sigmoid" => Ok(Self::Sigmoid),
+///             "relu" => Ok(Self::Relu),
+///             _ => Err(()),
+///         }
+///     }
+/// }
+///
+/// assert!(Activation::VARIANTS.iter().all(|x|x.parse::<Activation>().is_ok()));
+/// ```
+pub trait VariantNames {
+    /// The names of the variants.
+    const VARIANTS: &'static [&'static str];
+}
@@ -16,6 +16,31 @@ fn fizzbuzz_indexes() {
     assert!(fizzbuzz().take(15).eq(first_15));
 }
 
+#[test]
+fn fizzbuzz_ranges() {
+    assert!(fizzbuzz_range(1..=100).eq(fizzbuzz().take(100)));
+    assert_eq!(fizzbuzz_range(usize::MAX - 1..=usize::MAX).count(), 2);
+}
+
+#[test]
+fn fizzbuzz_builder() {
+    assert!(FizzBuzzBuilder::classic().build().take(15).eq(fizzbuzz().take(15)));
+    assert!(FizzBuzzBuilder::new().start(4).end(6).build().eq(["4", "5", "6"]));
+    assert!(FizzBuzzBuilder::new().rule(2, "Even").start(10).end(9).build().eq(Vec::<String>::new()));
+
+    let near_max = FizzBuzzBuilder::new()
+        .rule(1, "One")
+        .start(usize::MAX - 1)
+        .build();
+    assert!(near_max.eq(["One", "One"]));
+}
+
+#[test]
+#[should_panic]
+fn fizzbuzz_builder_zero_divisor() {
+    let _ = FizzBuzzBuilder::new().rule(0, "Never");
+}
+
 #[test]
 fn correct_intervals() {
     let bar = ["", "", "", "", "Bar"]
@@ -23,6 +48,8 @@ fn correct_intervals() {
         .map(str::to_owned);
 
     assert!(repeat_interval("Foo", 3).take(3).eq(["", "", "Foo"]));
+    assert!(repeat_interval_or(0, 7, 2).take(4).eq([0, 7, 0, 7]));
+    assert!(repeat_interval_or("-", "Foo", 1).take(2).eq(["Foo", "Foo"]));
     assert!(repeat_interval_with(||String::from("Bar"), 5).take(5).eq(bar));
 
     let foobar = repeat_values(&[("A", 3), ("B", 5)]);
@@ -35,4 +62,68 @@ fn correct_intervals() {
 #[ignore = "really long process times, attemping usize overflow"]
 fn fizzbuzz_is_infinite() {
     fizzbuzz().skip(usize::MAX).for_each(|x|println!("{}", x));
-}
\ No newline at end of file
+}
+#[test]
+fn finite_factories_reverse() {
+    let mut primes = sieve_primes(30);
+    assert_eq!(primes.len(), 10);
+    assert_eq!(primes.next_back(), Some(29));
+    assert_eq!(primes.len(), 9);
+
+    assert!(FizzBuzzBuilder::classic().start(13).end(15).build().rev().eq(["FizzBuzz", "14", "13"]));
+    assert!(repeat_values(&[("A", 1), ("B", 2)]).rev().eq(["B", "B", "A"]));
+
+    let mut empty = sieve_primes(1);
+    assert_eq!(empty.next(), None);
+    assert_eq!(empty.next(), None);
+}
+
+#[test]
+fn recurrences() {
+    let fibonacci = recurrence([0u64, 1], |[a, b]|a + b);
+    assert_eq!(fibonacci.take(94).last(), Some(12_200_160_415_121_876_738));
+
+    let doubling = recurrence([1u8], |[x]|x * 2);
+    assert!(doubling.take(8).eq([1, 2, 4, 8, 16, 32, 64, 128]));
+
+    let constant = recurrence([], |[]: &[u8; 0]|7);
+    assert!(constant.take(2).eq([7, 7]));
+}
+
+#[test]
+fn truth_tables() {
+    assert!(truth_table(0).eq([vec![]]));
+    assert!(truth_table(2).eq([
+        vec![false, false],
+        vec![false, true],
+        vec![true, false],
+        vec![true, true],
+    ]));
+    assert_eq!(truth_table(10).len(), 1024);
+    assert_eq!(truth_table_f64(3).next_back(), Some(vec![1.0, 1.0, 1.0]));
+}
+
+#[test]
+#[should_panic]
+fn truth_table_too_many_inputs() {
+    let _ = truth_table(usize::BITS);
+}
+
+#[test]
+fn gray_codes_differ_by_one_bit() {
+    let codes = gray_codes(8).collect::<Vec<_>>();
+
+    assert_eq!(codes.len(), 256);
+    assert!(codes.windows(2).all(|x|(x[0] ^ x[1]).count_ones() == 1));
+
+    let mut sorted = codes.clone();
+    sorted.sort();
+    assert!(sorted.into_iter().eq(0..256));
+}
+
+#[test]
+fn binary_string_counting() {
+    assert!(binary_strings(0).eq([""]));
+    assert_eq!(binary_strings(4).nth(5), Some("0101".to_string()));
+    assert_eq!(binary_strings(4).next_back(), Some("1111".to_string()));
+}
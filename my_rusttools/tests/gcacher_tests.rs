@@ -63,4 +63,80 @@ fn gcacher_inner_deconstruction() {
     let (instancer, cache) = cache.into_inner();
     assert_eq!(cache.get(&2), Some(&4));
     assert_eq!(instancer(&2), 4);
-}
\ No newline at end of file
+}
+
+#[test]
+fn gcacher_cache_transformation() {
+    let mut cache = GCacher::new(|x: &usize|x * x);
+    (1..=3).for_each(|x|{
+        cache.value_from(x);
+    });
+
+    let halved = cache.clone().map_values(|x|x as f64 / 2.0);
+    assert_eq!(halved.get(&3), Some(&4.5));
+
+    let parity = cache.transform_keys(|x|x % 2);
+    assert_eq!(parity.len(), 2);
+    assert_eq!(parity.get(&0), Some(&4));
+}
+
+#[test]
+fn gcacher_value_from_with() {
+    let mut cache = GCacher::new(|x: &usize|x * x);
+    assert_eq!(cache.value_from_with(3, |_|9), &9);
+    assert!(cache.contains_key_cached(&3));
+
+    // Cached values are kept, without calling `alt`.
+    assert_eq!(cache.value_from_with(3, |_|unreachable!()), &9);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "different value")]
+fn gcacher_value_from_with_mismatch() {
+    let mut cache = GCacher::new(|x: &usize|x * x);
+    cache.value_from_with(3, |_|10);
+}
+
+#[test]
+fn gcacher_timing() {
+    let mut cache = GCacher::with_timing(|x: &u64|{
+        std::thread::sleep(std::time::Duration::from_millis(*x));
+        x * x
+    });
+    // Far apart, so scheduling delays can't reorder them.
+    cache.value_from(0);
+    cache.value_from(60);
+    cache.value_from(30);
+
+    let slowest = cache.slowest_keys(2);
+    assert_eq!(slowest.iter().map(|x|*x.0).collect::<Vec<_>>(), vec![60, 30]);
+    assert!(cache.total_timing() >= std::time::Duration::from_millis(90));
+
+    assert_eq!((cache.hits(), cache.misses()), (0, 3));
+
+    // Hits aren't re-timed.
+    let timing = cache.timing(&60);
+    cache.value_from(60);
+    assert_eq!(cache.timing(&60), timing);
+    assert_eq!((cache.hits(), cache.misses()), (1, 3));
+
+    cache.remove(&60);
+    assert_eq!(cache.timing(&60), None);
+    assert_eq!(cache.len(), 2);
+
+    cache.clear();
+    assert_eq!((cache.hits(), cache.misses()), (0, 0));
+}
+
+#[test]
+fn gcacher_snapshot() {
+    let mut cache = GCacher::new(|x: &usize|x * x);
+    cache.value_from(2);
+
+    let snapshot = cache.snapshot();
+    cache.value_from(3);
+
+    assert_eq!(snapshot.len(), 1);
+    assert_eq!(cache.len(), 2);
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+use my_rusttools::traits::{parse_human_bool, DehumanizeBool, HumanFormat, HumanizeBooleans, HumanizeBytes, HumanizeDuration, HumanizeNumbers, HumanizeOrdinals};
+
+#[test]
+fn percentages() {
+    assert_eq!("50%", 0.5.as_percentage(0));
+    assert_eq!("33.33%", (1.0 / 3.0).as_percentage(2));
+    assert_eq!("-12.5%", (-0.125f32).as_percentage(1));
+    assert_eq!("100.0%", 1u8.as_percentage(1));
+}
+
+#[test]
+fn thousands() {
+    assert_eq!("0", 0.thousands_separated());
+    assert_eq!("999", 999u16.thousands_separated());
+    assert_eq!("1,000", 1000.thousands_separated());
+    assert_eq!("-100,000", (-100_000i64).thousands_separated());
+    assert_eq!("18,446,744,073,709,551,615", u64::MAX.thousands_separated());
+    assert_eq!("1,234.5678", 1234.5678.thousands_separated());
+    assert_eq!("NaN", f64::NAN.thousands_separated());
+    assert_eq!("-inf", f64::NEG_INFINITY.thousands_separated());
+}
+
+#[test]
+fn ordinals() {
+    let ordinals = [0, 1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 101, 111, 112, 1003]
+        .map(HumanizeOrdinals::ordinal);
+
+    assert_eq!(["0th", "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "23rd", "101st", "111th", "112th", "1003rd"], ordinals);
+    assert_eq!("-1st", (-1i8).ordinal());
+    assert_eq!("-128th", i8::MIN.ordinal());
+    assert_eq!("255th", u8::MAX.ordinal());
+}
+
+#[test]
+fn durations() {
+    let short = |x: Duration|x.humanize(HumanFormat::Short);
+    let long = |x: Duration|x.humanize(HumanFormat::Long);
+
+    assert_eq!("0s", short(Duration::ZERO));
+    assert_eq!("950ns", short(Duration::from_nanos(950)));
+    assert_eq!("1.5µs", short(Duration::from_nanos(1_500)));
+    assert_eq!("250ms", short(Duration::from_millis(250)));
+    assert_eq!("1.5s", short(Duration::from_millis(1_500)));
+    assert_eq!("1h 1s", short(Duration::from_secs(3_601)));
+    assert_eq!("2d 3h 4m 5s", short(Duration::from_secs(2 * 86_400 + 3 * 3_600 + 4 * 60 + 5)));
+
+    assert_eq!("0 seconds", long(Duration::ZERO));
+    assert_eq!("1 millisecond", long(Duration::from_millis(1)));
+    assert_eq!("1 hour, 1 second", long(Duration::from_secs(3_601)));
+    assert_eq!("2 days, 1 minute", long(Duration::from_secs(2 * 86_400 + 60)));
+}
+
+#[test]
+fn byte_sizes() {
+    assert_eq!("0 B", 0u8.humanize_bytes(HumanFormat::Short));
+    assert_eq!("1023 B", 1023u16.humanize_bytes(HumanFormat::Short));
+    assert_eq!("1 KiB", 1024u32.humanize_bytes(HumanFormat::Short));
+    assert_eq!("1.5 MiB", (3usize << 19).humanize_bytes(HumanFormat::Short));
+    assert_eq!("16 EiB", u64::MAX.humanize_bytes(HumanFormat::Short));
+
+    assert_eq!("0 bytes", 0u8.humanize_bytes(HumanFormat::Long));
+    assert_eq!("1 kibibyte", 1024u64.humanize_bytes(HumanFormat::Long));
+    assert_eq!("2.5 gibibytes", (5u64 << 29).humanize_bytes(HumanFormat::Long));
+}
+
+#[test]
+fn human_bools_round_trip() {
+    for x in [true, false] {
+        assert_eq!(Some(x), x.yes_no().dehumanize_bool());
+        assert_eq!(Some(x), x.yes_no_short().to_string().dehumanize_bool());
+        assert_eq!(Some(x), x.on_off().dehumanize_bool());
+        assert_eq!(Some(x), x.to_string().dehumanize_bool());
+    }
+}
+
+#[test]
+fn human_bools() {
+    assert_eq!(Some(true), parse_human_bool("TRUE"));
+    assert_eq!(Some(true), parse_human_bool(" 1 "));
+    assert_eq!(Some(false), parse_human_bool("No"));
+    assert_eq!(Some(false), parse_human_bool("0"));
+    assert_eq!(None, parse_human_bool("yep"));
+    assert_eq!(None, parse_human_bool("2"));
+}
@@ -1,6 +1,10 @@
 #![allow(unused_comparisons)]
-use std::ops::{ControlFlow, RangeBounds};
-use my_rusttools::{StdinExtended, ParseStdinExtended};
+use std::{
+    io::{self, Cursor, IsTerminal},
+    ops::{ControlFlow, RangeBounds},
+    time::{Duration, Instant},
+};
+use my_rusttools::{complete_path, constant_time_eq, parse_csv_record, parse_csv_row, Completer, CsvRowErrorKind, LineSource, LineSourceExt, Sensitive, StdinExtended, ParseStdinExtended, LinesLimitExceeded};
 
 #[test]
 #[ignore = "input testing"]
@@ -52,4 +56,275 @@ fn lines_test() {
         .count();
 
     assert!((1..4).contains(&lines));
-}
\ No newline at end of file
+}
+
+#[test]
+fn limited_lines_test() {
+    let mut source = Cursor::new("short\nthis line runs past the limit\n");
+    let lines = source.read_lines_limited(1..=3, 16, |_|(), |_, _|ControlFlow::Break(()))
+        .expect_err("input should exceed the limit");
+
+    let exceeded = lines.get_ref()
+        .and_then(|x|x.downcast_ref::<LinesLimitExceeded>())
+        .expect("error should be a `LinesLimitExceeded`");
+
+    assert_eq!("short\nthis line ", exceeded.input);
+
+    // Input within the limit is read as usual.
+    let mut source = Cursor::new("short\nlines\n");
+    assert_eq!(source.read_lines_limited(1..=2, 16, |_|(), |_, _|ControlFlow::Break(())).unwrap(), "short\nlines\n");
+}
+
+#[test]
+fn select_from_test() {
+    let options = ["First", "Second", "Third"];
+    let mut source = Cursor::new("0\n4\nsecond\n 2 \n");
+    let mut prompts = 0;
+
+    let selected = source.select_from(&options, ||prompts += 1);
+
+    assert_eq!(selected, 1);
+    assert_eq!(prompts, 4);
+}
+
+#[derive(Debug, PartialEq)]
+enum Shape {
+    Circle,
+    Square,
+}
+
+impl my_rusttools::traits::VariantNames for Shape {
+    const VARIANTS: &'static [&'static str] = &["circle", "square"];
+}
+
+impl std::str::FromStr for Shape {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "circle" => Ok(Self::Circle),
+            "square" => Ok(Self::Square),
+            _ => Err(()),
+        }
+    }
+}
+
+#[test]
+fn parse_enum_test() {
+    let mut source = Cursor::new("triangle\nSquare\n square\ncircle\n");
+    let mut prompts = 0;
+
+    let shape: Shape = source.read_line_parse_enum(||prompts += 1);
+
+    assert_eq!(shape, Shape::Square);
+    assert_eq!(prompts, 3);
+}
+
+#[test]
+fn path_completion() {
+    let dir = std::env::temp_dir().join(format!("my_rusttools_path_completion_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("alpha")).unwrap();
+    std::fs::write(dir.join("also.txt"), "").unwrap();
+    std::fs::write(dir.join("beta.txt"), "").unwrap();
+    std::fs::write(dir.join(".alpine"), "").unwrap();
+
+    let base = format!("{}/", dir.display());
+    let completer: &Completer = &complete_path;
+
+    assert_eq!(completer(&format!("{}al", base)), vec![format!("{}alpha/", base), format!("{}also.txt", base)]);
+    assert_eq!(completer(&format!("{}.al", base)), vec![format!("{}.alpine", base)]);
+    assert!(completer(&format!("{}gamma", base)).is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn completed_lines() {
+    let dir = std::env::temp_dir().join(format!("my_rusttools_completed_lines_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("alpha")).unwrap();
+    std::fs::write(dir.join("also.txt"), "").unwrap();
+
+    let base = format!("{}/", dir.display());
+    let script = format!("{0}al\t\n{0}gamma\t\n{0}alp\t\n{0}al\n", base);
+    let mut source: Box<dyn LineSource> = Box::new(Cursor::new(script));
+    let mut offered = Vec::new();
+
+    let completed = source.read_line_completed(&complete_path, |x|offered.push(x.to_vec()));
+    assert_eq!(completed.unwrap(), format!("{}alpha/", base));
+    assert_eq!(offered, vec![vec![format!("{}alpha/", base), format!("{}also.txt", base)], vec![]]);
+
+    // Lines without a tab are returned as they are.
+    assert_eq!(source.read_line_completed(&complete_path, |_|()).unwrap(), format!("{}al", base));
+    assert_eq!(source.read_line_completed(&complete_path, |_|()).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn swappable_line_sources() {
+    let mut sources: Vec<Box<dyn LineSource>> = vec![
+        Box::new("scripted\n".as_bytes()),
+        Box::new(StdinExtended::new()),
+    ];
+    let mut buf = String::new();
+
+    assert_eq!(sources[0].read_line_into(&mut buf).unwrap(), 9);
+    assert_eq!(sources[0].read_line_into(&mut buf).unwrap(), 0);
+    assert_eq!(buf, "scripted\n");
+}
+
+#[test]
+fn line_source_methods_through_trait_objects() {
+    let script = "two\n2\nmaybe\nyes\n0\n3\nx, y\n1, 2\nfirst\nsecond\n\nrest\n";
+    let mut source: Box<dyn LineSource> = Box::new(Cursor::new(script));
+    let mut prompts = 0;
+
+    let num: u8 = source.read_line_until_parsed(||prompts += 1, |_|());
+    assert_eq!(num, 2);
+    assert_eq!(prompts, 2);
+
+    assert!(source.read_line_until_bool(||()));
+    assert_eq!(source.select_from(&["a", "b", "c"], ||()), 2);
+
+    let header: Vec<String> = source.read_csv_row(',').unwrap();
+    let record = source.read_csv_record::<u8>(&header, ',').unwrap();
+    assert_eq!(record.get("y"), Some(&2));
+
+    let lines = source.read_lines(1..=3, |_|(), |_, _|ControlFlow::Break(())).unwrap();
+    assert_eq!(lines.trim_end(), "first\nsecond");
+
+    let err = source.read_lines_limited(1..=3, 2, |_|(), |_, _|ControlFlow::Break(())).unwrap_err();
+    let exceeded = err.get_ref().and_then(|x|x.downcast_ref::<LinesLimitExceeded>()).unwrap();
+    assert_eq!(exceeded.input, "re");
+    // Reading stops at the limit, leaving the rest of the line.
+    assert_eq!(source.read_line_new_string().unwrap(), "t\n");
+
+    assert_eq!(source.read_csv_row::<u8>(',').unwrap_err().kind(), &CsvRowErrorKind::EndOfInput);
+}
+
+#[test]
+fn end_of_input_is_an_error() {
+    let mut source: Box<dyn LineSource> = Box::new(Cursor::new("one\n"));
+    let err = source.read_lines(2..=3, |_|(), |_, _|ControlFlow::Break(())).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    // Enough lines were read before input ended.
+    let mut source = Cursor::new("one\ntwo\n");
+    assert_eq!(source.read_lines(1..=3, |_|(), |_, _|ControlFlow::Break(())).unwrap(), "one\ntwo\n");
+
+    let mut source = Cursor::new("");
+    assert_eq!(source.read_line_completed(&complete_path, |_|()).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(source.read_csv_row::<u8>(',').unwrap_err().kind(), &CsvRowErrorKind::EndOfInput);
+}
+
+#[test]
+fn sensitive_sources_back_off() {
+    let delay = Duration::from_millis(20);
+    let mut source: Box<dyn LineSource> = Box::new(Sensitive::with_delays(Cursor::new("one\ntwo\n3\n"), delay, delay * 3 / 2));
+
+    assert_eq!(source.retry_delay(1), delay);
+    assert_eq!(source.retry_delay(2), delay * 3 / 2);
+    assert_eq!(Cursor::new("").retry_delay(2), Duration::ZERO);
+
+    let start = Instant::now();
+    let num: u8 = source.read_line_until_parsed(||(), |_|());
+
+    assert_eq!(num, 3);
+    assert!(start.elapsed() >= delay * 5 / 2);
+}
+
+#[test]
+fn secrets_require_input() {
+    // Only meaningful when standard input is closed, as when run non-interactively.
+    if io::stdin().is_terminal() {
+        return;
+    }
+
+    let err = StdinExtended::new().read_secret().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn csv_rows() {
+    assert_eq!(parse_csv_row::<String>(" a |b| c \r\n", '|'), Ok(vec!["a".to_string(), "b".to_string(), "c".to_string()]));
+
+    let err = parse_csv_row::<f64>("1.5,x", ',').unwrap_err();
+    assert!(matches!(err.kind(), CsvRowErrorKind::Parse { column: 1, .. }));
+    assert!(err.to_string().starts_with("column 2 could not be parsed"));
+}
+
+#[test]
+fn csv_records() {
+    let header = ["name".to_string(), "age".to_string()];
+    let record = parse_csv_record::<String>(&header, "Ada, 36", ',').unwrap();
+    assert_eq!(record.get("name").map(String::as_str), Some("Ada"));
+    assert_eq!(record.get("age").map(String::as_str), Some("36"));
+
+    let err = parse_csv_record::<String>(&header, "Ada", ',').unwrap_err();
+    assert_eq!(err.kind(), &CsvRowErrorKind::FieldCount { expected: 2, found: 1 });
+}
+
+#[test]
+fn constant_time_comparison() {
+    assert!(constant_time_eq(b"", b""));
+    assert!(constant_time_eq(b"secret", b"secret"));
+    assert!(!constant_time_eq(b"secret", b"secreT"));
+    assert!(!constant_time_eq(b"secret", b"secrets"));
+}
+
+#[test]
+fn secret_confirmed_test() {
+    let mut source = Cursor::new("hunter2\nhunter3\nhunter2\r\nhunter2\n");
+    let mut delays = Vec::new();
+
+    let secret = source.read_secret_confirmed(||(), ||(), |delay|delays.push(delay))
+        .expect("input error");
+
+    assert_eq!(secret, "hunter2");
+    assert_eq!(delays, vec![Duration::from_millis(250)]);
+
+    // Input ending between the entries ends the attempt.
+    let mut source = Cursor::new("hunter2\n");
+    let err = source.read_secret_confirmed(||(), ||(), |_|()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn cancelable_until_parsed_test() {
+    let mut source: Box<dyn LineSource> = Box::new(Cursor::new("one\ntwo\nthree\n4\n"));
+    let mut attempts = 0;
+    let num: Option<usize> = source.try_read_line_until_parsed(
+        ||{
+            attempts += 1;
+            match attempts {
+                ..=2 => ControlFlow::Continue(()),
+                _ => ControlFlow::Break(()),
+            }
+        },
+        |_|()
+    );
+
+    assert_eq!(num, None);
+    assert_eq!(attempts, 3);
+    assert_eq!(source.try_read_line_until_parsed::<usize, _, _>(||ControlFlow::Continue(()), |_|()), Some(4));
+}
+
+#[test]
+fn cancelable_loops_end_with_input() {
+    let go = ||ControlFlow::Continue(());
+    let mut source: Box<dyn LineSource> = Box::new(Cursor::new(""));
+
+    assert_eq!(source.try_read_line_until_parsed::<usize, _, _>(go, |_|()), None);
+    assert_eq!(source.try_read_line_until_mapped(Some, go), None);
+    assert_eq!(source.try_read_line_until_bool(go), None);
+    assert!(source.try_read_line_parse_enum::<Shape, _>(go).is_none());
+    assert_eq!(source.try_select_from(&["a", "b"], go), None);
+
+    // Invalid lines are asked again, until input ends.
+    let mut source = Cursor::new("maybe\n3\n");
+    assert_eq!(source.try_read_line_until_bool(go), None);
+    let mut source = Cursor::new("0\n2\n");
+    assert_eq!(source.try_select_from(&["a", "b"], go), Some(1));
+}
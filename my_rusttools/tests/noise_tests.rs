@@ -0,0 +1,32 @@
+#![cfg(feature = "rand")]
+use my_rusttools::factories::{gaussian_jittered, jittered};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn uniform_jitter_is_bounded() {
+    let noisy = jittered(0..1000, 0.5, StdRng::seed_from_u64(0));
+
+    assert!(noisy.zip(0..1000).all(|(x, i)|(x - i as f64).abs() <= 0.5));
+}
+
+#[test]
+fn no_jitter_without_amplitude() {
+    assert!(jittered([1.0, 2.0], 0.0, StdRng::seed_from_u64(0)).eq([1.0, 2.0]));
+    assert!(gaussian_jittered([1.0, 2.0], 0.0, StdRng::seed_from_u64(0)).eq([1.0, 2.0]));
+}
+
+#[test]
+fn gaussian_jitter_spread() {
+    let noisy = gaussian_jittered([0.0; 10_000], 2.0, StdRng::seed_from_u64(0)).collect::<Vec<_>>();
+    let mean = noisy.iter().sum::<f64>() / noisy.len() as f64;
+    let variance = noisy.iter().map(|x|(x - mean).powi(2)).sum::<f64>() / noisy.len() as f64;
+
+    assert!(mean.abs() < 0.1);
+    assert!((variance.sqrt() - 2.0).abs() < 0.1);
+}
+
+#[test]
+#[should_panic]
+fn negative_amplitude() {
+    let _ = jittered([1.0], -1.0, StdRng::seed_from_u64(0));
+}
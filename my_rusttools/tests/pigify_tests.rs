@@ -0,0 +1,112 @@
+use quickcheck::{quickcheck, TestResult};
+use std::io::Read;
+use my_rusttools::{pigify, pigify_iter, pigify_to, pigify_with, unpigify, ConsonantMode, PigifyOptions};
+
+#[test]
+fn pigify_behaviour() {
+    assert_eq!("Example-hay", pigify("Example"));
+    assert_eq!("ello-Hay, orld-way!", pigify("Hello, world!"));
+    assert_eq!("xay", pigify("x"));
+    assert_eq!(pigify("The string"), pigify_with("The string", &PigifyOptions::default()));
+}
+
+#[test]
+fn pigify_iter_behaviour() {
+    let text = "  The quick, brown fox.  ";
+
+    assert_eq!(pigify(text), pigify_iter(text).collect::<String>());
+    assert!(pigify_iter("").next().is_none());
+}
+
+#[test]
+fn pigify_to_behaviour() {
+    let mut output = Vec::new();
+    pigify_to(&mut output, "  the end\n\nExample".as_bytes()).unwrap();
+    assert_eq!("  he-tay end-hay\n\nExample-hay", String::from_utf8(output).unwrap());
+
+    // Large inputs are translated a line at a time.
+    let lines = "the quick brown fox\n".as_bytes().repeat(10_000);
+    let mut output = Vec::new();
+    pigify_to(&mut output, lines.as_slice()).unwrap();
+    assert_eq!("he-tay uick-qay rown-bay ox-fay\n".repeat(10_000), String::from_utf8(output).unwrap());
+
+    let invalid = [0xff, 0xfe].chain("text".as_bytes());
+    assert!(pigify_to(&mut Vec::new(), std::io::BufReader::new(invalid)).is_err());
+}
+
+#[test]
+fn pigify_with_clusters() {
+    let options = PigifyOptions {
+        consonants: ConsonantMode::Cluster,
+        ..PigifyOptions::default()
+    };
+
+    assert_eq!("ing-stray", pigify_with("string", &options));
+    assert_eq!("een-quay", pigify_with("queen", &options));
+    assert_eq!("ythm-rhay", pigify_with("rhythm", &options));
+    assert_eq!("ellow-yay", pigify_with("yellow", &options));
+    assert_eq!("nthay", pigify_with("nth", &options));
+    assert_eq!("apple-hay", pigify_with("apple", &options));
+}
+
+#[test]
+fn pigify_with_suffix_hyphen_and_case() {
+    let options = PigifyOptions {
+        vowel_suffix: String::from("way"),
+        hyphen: false,
+        preserve_case: true,
+        ..PigifyOptions::default()
+    };
+
+    assert_eq!("Appleway", pigify_with("Apple", &options));
+    assert_eq!("Hetay", pigify_with("The", &options));
+    assert_eq!("HETAY EXAMPLEWAY", pigify_with("THE EXAMPLE", &options));
+    assert_eq!("Xay", pigify_with("X", &options));
+}
+
+#[test]
+fn unpigify_behaviour() {
+    assert_eq!("Example", unpigify("Example-hay"));
+    assert_eq!("the", unpigify("he-tay"));
+    assert_eq!("x", unpigify("xay"));
+    assert_eq!("Hello, world!", unpigify("ello-Hay, orld-way!"));
+    assert_eq!("x-ray well-known", unpigify(&pigify("x-ray well-known")));
+    assert_eq!("", unpigify(""));
+}
+
+#[test]
+fn unpigify_h_ambiguity() {
+    // "hello" and "ello" translate into the same Pig Latin.
+    assert_eq!(pigify("hello"), pigify("ello"));
+    assert_eq!("ello", unpigify(&pigify("hello")));
+    assert_eq!("hth", unpigify(&pigify("hth")));
+}
+
+quickcheck! {
+    fn unpigify_inverts_pigify(word: String) -> TestResult {
+        let word = word.chars()
+            .filter(char::is_ascii_alphabetic)
+            .collect::<String>();
+        let starts_with_h_vowel = word.to_lowercase().starts_with('h')
+            && word.chars().nth(1).is_some_and(|x|"aeiouAEIOU".contains(x));
+
+        match word.is_empty() || starts_with_h_vowel {
+            true => TestResult::discard(),
+            false => TestResult::from_bool(unpigify(&pigify(&word)) == word),
+        }
+    }
+
+    fn unpigify_inverts_pigify_sentences(words: Vec<String>) -> TestResult {
+        let words = words.iter()
+            .map(|x|x.chars().filter(char::is_ascii_alphabetic).collect::<String>())
+            .filter(|x|!x.is_empty())
+            .collect::<Vec<_>>();
+
+        if words.iter().any(|x|x.to_lowercase().starts_with('h')) {
+            return TestResult::discard();
+        }
+
+        let sentence = words.join(" ");
+        TestResult::from_bool(unpigify(&pigify(&sentence)) == sentence)
+    }
+}
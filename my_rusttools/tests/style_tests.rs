@@ -0,0 +1,13 @@
+use my_rusttools::style::{self, Colour};
+
+#[test]
+fn styles_are_left_out_without_colour() {
+    std::env::set_var("NO_COLOR", "1");
+
+    assert!(!style::enabled());
+    assert_eq!("bold", style::bold("bold"));
+    assert_eq!("red", style::colour("red", Colour::Red));
+    assert_eq!("", style::clear_line());
+    assert_eq!("", style::cursor_up(2));
+    assert_eq!("", style::clear_screen());
+}
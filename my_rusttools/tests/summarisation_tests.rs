@@ -57,4 +57,62 @@ fn tally_test() {
     assert_eq!(2, c.iter().tally_item(&TestType2));
 
     println!("{:?}", c[0]);
-}
\ No newline at end of file
+}
+#[test]
+fn frequencies_behaviour() {
+    let a = [1, 2, 2, 3, 3, 3, 3, 3];
+    let b: [i32; 0] = [];
+
+    let frequencies_a = a.iter().frequencies();
+    assert_eq!(Some(&0.125), frequencies_a.get(&1));
+    assert_eq!(Some(&0.25), frequencies_a.get(&2));
+    assert_eq!(Some(&0.625), frequencies_a.get(&3));
+    assert_eq!(1.0, frequencies_a.values().sum::<f64>());
+
+    assert!(b.iter().frequencies().is_empty());
+}
+
+#[test]
+fn entropy_behaviour() {
+    let b: [i32; 0] = [];
+
+    assert_eq!(0.0, b.iter().entropy());
+    assert_eq!(0.0, ["One"; 3].iter().entropy());
+    assert_eq!(1.0, [true, false].iter().entropy());
+    assert_eq!(3.0, (0..8).entropy());
+    assert!((0.811 - [1, 1, 1, 2].iter().entropy()).abs() < 0.001);
+}
+
+#[test]
+fn coverage_behaviour() {
+    let a = ["One", "Two", "Two", "Three", "Three", "Three"];
+    let b: [&str; 0] = [];
+
+    assert_eq!(0.0, a.iter().coverage_of_top(0));
+    assert_eq!(0.5, a.iter().coverage_of_top(1));
+    assert!((5.0 / 6.0 - a.iter().coverage_of_top(2)).abs() < f64::EPSILON);
+    assert_eq!(1.0, a.iter().coverage_of_top(3));
+    assert_eq!(0.0, b.iter().coverage_of_top(1));
+}
+
+#[test]
+fn group_counts_behaviour() {
+    let a = ["apple", "avocado", "banana", "blueberry", "cherry"];
+    let b: [&str; 0] = [];
+
+    let counts_a = a.iter().group_counts_by(|x|x.chars().next());
+    assert_eq!(3, counts_a.len());
+    assert_eq!(Some(&2), counts_a.get(&Some('a')));
+    assert_eq!(Some(&1), counts_a.get(&Some('c')));
+
+    assert!(b.iter().group_counts_by(|x|x.len()).is_empty());
+}
+
+#[test]
+fn group_items_behaviour() {
+    let a = ["apple", "avocado", "banana", "blueberry", "cherry"];
+
+    let groups_a = a.into_iter().group_items_by(|x|x.len() > 6);
+    assert_eq!(Some(&vec!["apple", "banana", "cherry"]), groups_a.get(&false));
+    assert_eq!(Some(&vec!["avocado", "blueberry"]), groups_a.get(&true));
+}
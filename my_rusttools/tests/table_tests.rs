@@ -0,0 +1,28 @@
+use my_rusttools::{render_table, TableFormat};
+
+#[test]
+fn ragged_rows() {
+    let rows = vec![vec!["a"], vec!["b", "2", "extra"]];
+
+    assert_eq!(
+        render_table(&["name", "n"], rows, TableFormat::Ascii),
+        "+------+---+\n| name | n |\n+------+---+\n| a    |   |\n| b    | 2 |\n+------+---+\n",
+    );
+}
+
+#[test]
+fn markdown_escapes_pipes() {
+    let rows = [["a|b"]];
+
+    assert_eq!(
+        render_table(&["cell"], rows, TableFormat::Markdown),
+        "| cell |\n|:-----|\n| a\\|b |\n",
+    );
+}
+
+#[test]
+fn empty_tables() {
+    let rows: [[&str; 1]; 0] = [];
+
+    assert_eq!(render_table(&["x"], rows, TableFormat::Ascii), "+---+\n| x |\n+---+\n+---+\n");
+}
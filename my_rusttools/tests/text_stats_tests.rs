@@ -0,0 +1,33 @@
+use my_rusttools::text_stats;
+
+#[test]
+fn text_stats_behaviour() {
+    let stats = text_stats("Apple banana, apple! Cherry banana.");
+
+    assert_eq!(5, stats.word_count);
+    assert_eq!(3, stats.unique_words);
+    assert_eq!(35, stats.grapheme_count);
+    assert_eq!(5.6, stats.average_word_length);
+    // "apple" and "banana" are equally common, so the first alphabetically is chosen.
+    assert_eq!(Some((String::from("apple"), 2)), stats.most_common_word);
+}
+
+#[test]
+fn text_stats_graphemes() {
+    let stats = text_stats("café ne\u{301}e 👍🏽");
+
+    assert_eq!(2, stats.word_count);
+    assert_eq!(10, stats.grapheme_count);
+    assert_eq!(3.5, stats.average_word_length);
+}
+
+#[test]
+fn text_stats_empty() {
+    let stats = text_stats(" ... ");
+
+    assert_eq!(0, stats.word_count);
+    assert_eq!(0, stats.unique_words);
+    assert_eq!(5, stats.grapheme_count);
+    assert_eq!(0.0, stats.average_word_length);
+    assert_eq!(None, stats.most_common_word);
+}
@@ -0,0 +1,50 @@
+use my_rusttools::{
+    pigify,
+    traits::{Leetspeak, Pigify, Rot13, SpongeCase, TextTransform, TitleCase, Unpigify},
+    ConsonantMode,
+    PigifyOptions,
+};
+
+#[test]
+fn transforms_are_swappable() {
+    let transforms: Vec<Box<dyn TextTransform>> = vec![
+        Box::new(pigify),
+        Box::new(Pigify::default()),
+        Box::new(Rot13),
+        Box::new(Leetspeak),
+        Box::new(TitleCase),
+        Box::new(SpongeCase),
+    ];
+
+    let transformed = transforms.iter()
+        .map(|x|x.transform("the test"))
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec!["he-tay est-tay", "he-tay est-tay", "gur grfg", "7h3 7357", "The Test", "tHe TeSt"], transformed);
+}
+
+#[test]
+fn pigify_transforms() {
+    let clusters = Pigify(PigifyOptions {
+        consonants: ConsonantMode::Cluster,
+        ..PigifyOptions::default()
+    });
+
+    assert_eq!("ing-stray", clusters.transform("string"));
+    assert_eq!("the end", Unpigify.transform(&Pigify::default().transform("the end")));
+}
+
+#[test]
+fn rot13_round_trips() {
+    let text = "The Quick Brown Fox, jumps over the lazy dog! 123";
+
+    assert_eq!(text, Rot13.transform(&Rot13.transform(text)));
+    assert_eq!("nopqrstuvwxyzabcdefghijklm", Rot13.transform("abcdefghijklmnopqrstuvwxyz"));
+}
+
+#[test]
+fn case_transforms() {
+    assert_eq!("", TitleCase.transform(""));
+    assert_eq!("Élan Vital", TitleCase.transform("élan VITAL"));
+    assert_eq!("aBc-DeF 1 gH", SpongeCase.transform("abc-def 1 gh"));
+}
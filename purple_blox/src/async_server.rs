@@ -0,0 +1,147 @@
+//! An asynchronous server, running connections as tokio tasks,
+//! in place of the thread pool.
+use std::{io, net, sync::Arc};
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    time,
+};
+
+use crate::{
+    config::ServerConfig,
+    request::Request,
+    router::Router,
+    server::{self, ServerError, Sites},
+};
+
+/// Serves connections from `listener` with `router`,
+/// spawning a tokio task for each, instead of dispatching them to a thread pool.
+///
+/// Must be called from within a tokio runtime.
+/// Handlers are shared with [`run`], so are called synchronously,
+/// holding up the runtime's thread until they return,
+/// and streamed bodies are read in full before being sent.
+///
+/// Runs until the returned future is dropped.
+///
+/// # Errors
+///
+/// Will return [`Err`] if `listener` can't be registered with the runtime.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::net::TcpListener;
+/// use purple_blox::{config::ServerConfig, router::Router};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config = ServerConfig::default();
+///     let listener = TcpListener::bind(config.addr()).unwrap();
+///
+///     purple_blox::async_server::run_async(listener, Router::new(), config).await.unwrap();
+/// }
+/// ```
+///
+/// [`run`]: crate::run
+pub async fn run_async(listener: net::TcpListener, router: Router, config: ServerConfig) -> Result<(), ServerError> {
+    listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(listener)?;
+
+    let sites = Arc::new(Sites::new(router));
+    let config = Arc::new(config);
+
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(_) => continue,
+        };
+
+        let sites = Arc::clone(&sites);
+        let config = Arc::clone(&config);
+
+        tokio::spawn(async move{
+            server::report(handle_connection(stream, &sites, &config).await);
+        });
+    }
+}
+
+/// Serves the requests sent over `stream`,
+/// as [`run`](crate::run) does.
+async fn handle_connection(stream: TcpStream, sites: &Sites, config: &ServerConfig) -> Result<(), ServerError> {
+    let peer = stream.peer_addr().ok();
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let mut idle_timeout = config.read_timeout;
+
+    loop {
+        // Waits for the first bytes of the next request,
+        // closing quietly if the client closes the connection or goes idle.
+        if !matches!(time::timeout(idle_timeout, reader.fill_buf()).await, Ok(Ok(x)) if !x.is_empty()) {
+            return Ok(());
+        }
+
+        let request = match time::timeout(config.read_timeout, read_raw(&mut reader, config)).await {
+            Ok(Ok(raw)) => Request::read_limited(&mut raw.as_slice(), config.max_header_size, config.max_body_size),
+            Ok(Err(err)) => Err(err.into()),
+            Err(_) => Err(io::Error::from(io::ErrorKind::TimedOut).into()),
+        };
+
+        let (mut response, keep_alive, include_body, chunked) = crate::respond(request, peer, sites, config, false);
+
+        let mut buffer = Vec::new();
+        response.write_to(&mut buffer, include_body, chunked)?;
+        writer.write_all(&buffer).await?;
+
+        if !keep_alive {
+            return Ok(());
+        }
+
+        idle_timeout = config.keep_alive_timeout;
+    }
+}
+
+/// Reads the raw bytes of a request's head,
+/// and the body its `Content-Length` describes,
+/// to be parsed by [`Request::read_limited`].
+///
+/// Reading stops once the configured limits are exceeded,
+/// leaving the request for the parser to refuse.
+async fn read_raw<R: AsyncBufRead + Unpin>(reader: &mut R, config: &ServerConfig) -> io::Result<Vec<u8>> {
+    let mut raw = Vec::new();
+    let mut length = 0;
+
+    loop {
+        let start = raw.len();
+        let remaining = config.max_header_size.saturating_add(1) - start;
+
+        if remaining == 0 {
+            return Ok(raw);
+        }
+
+        if AsyncReadExt::take(&mut *reader, remaining as u64).read_until(b'\n', &mut raw).await? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        let line = String::from_utf8_lossy(&raw[start..]);
+
+        if line.trim_end_matches(['\r', '\n']).is_empty() {
+            break;
+        }
+
+        // An invalid length is left for `Request::read_from` to reject.
+        if let Some((_, value)) = line.split_once(':').filter(|(name, _)|name.eq_ignore_ascii_case("content-length")) {
+            length = value.trim().parse().unwrap_or(0);
+        }
+    }
+
+    if length > config.max_body_size {
+        return Ok(raw);
+    }
+
+    let start = raw.len();
+    raw.resize(start + length, 0);
+    reader.read_exact(&mut raw[start..]).await?;
+
+    Ok(raw)
+}
@@ -0,0 +1,124 @@
+//! Negotiation and encoding for compressed responses,
+//! used by the [`compress`] middleware.
+//!
+//! [`compress`]: crate::middleware::compress
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use flate2::{
+    write::{GzEncoder, ZlibEncoder},
+    Compression,
+};
+
+/// A content coding responses can be compressed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Gzip,
+    Deflate,
+}
+
+impl Encoding {
+    /// Compresses `data` with the encoding.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the encoder fails.
+    pub fn encode(self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Encoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            },
+            // HTTP's `deflate` is the zlib format, rather than raw deflate.
+            Encoding::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            },
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+        }.fmt(f)
+    }
+}
+
+/// Picks the encoding to use for a response,
+/// from the value of a request's `Accept-Encoding` header.
+///
+/// Encodings are preferred by their quality value,
+/// with ties going to `gzip`, and a quality of `0` refusing an encoding.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::compression::{negotiate, Encoding};
+///
+/// assert_eq!(Some(Encoding::Gzip), negotiate("deflate, gzip"));
+/// assert_eq!(Some(Encoding::Deflate), negotiate("gzip;q=0.5, deflate"));
+/// assert_eq!(Some(Encoding::Gzip), negotiate("*"));
+/// assert_eq!(None, negotiate("gzip;q=0, br"));
+/// ```
+pub fn negotiate(accept_encoding: &str) -> Option<Encoding> {
+    let mut gzip = None;
+    let mut deflate = None;
+    let mut wildcard = None;
+
+    for coding in accept_encoding.split(',') {
+        let mut params = coding.split(';').map(str::trim);
+        let name = params.next().unwrap_or_default().to_ascii_lowercase();
+        let quality = params
+            .find_map(|x|x.strip_prefix("q="))
+            .map_or(Some(1.0), |x|x.parse::<f32>().ok())
+            .unwrap_or(0.0);
+
+        match name.as_str() {
+            "gzip" | "x-gzip" => gzip = Some(quality),
+            "deflate" => deflate = Some(quality),
+            "*" => wildcard = Some(quality),
+            _ => (),
+        }
+    }
+
+    let gzip = gzip.or(wildcard).unwrap_or(0.0);
+    let deflate = deflate.or(wildcard).unwrap_or(0.0);
+
+    match (gzip, deflate) {
+        (gzip, deflate) if gzip > 0.0 && gzip >= deflate => Some(Encoding::Gzip),
+        (_, deflate) if deflate > 0.0 => Some(Encoding::Deflate),
+        _ => None,
+    }
+}
+
+/// Returns `true` if responses of the given MIME type are worth compressing,
+/// such as text, and `false` for already compressed formats, such as images.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::compression::is_compressible;
+///
+/// assert!(is_compressible("text/html; charset=utf-8"));
+/// assert!(is_compressible("application/json"));
+/// assert!(!is_compressible("image/png"));
+/// ```
+pub fn is_compressible(mime_type: &str) -> bool {
+    let essence = mime_type.split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    essence.starts_with("text/")
+        || essence.ends_with("+json")
+        || essence.ends_with("+xml")
+        || matches!(essence.as_str(), "application/json" | "application/javascript" | "application/xml" | "application/wasm" | "image/svg+xml" | "image/x-icon" | "font/ttf" | "font/otf")
+}
@@ -0,0 +1,448 @@
+//! Configuration for running the server.
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt,
+    fs,
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+use crate::response::Status;
+
+/// The prefix of the environment variables read by [`ServerConfig::override_from`].
+pub const ENV_PREFIX: &str = "PURPLE_BLOX_";
+
+/// Settings controlling how [`run`] serves connections,
+/// and where the site it serves is found.
+///
+/// Settings can be loaded from a TOML file with [`load`],
+/// where every key is optional, defaulting to the values of [`Default`],
+/// and timeouts are given in seconds:
+///
+/// ```toml
+/// bind_address = "0.0.0.0"
+/// port = 8080
+/// site_dir = "purple_blox/site"
+/// threads = 8
+/// queue_capacity = 1024
+/// read_timeout = 30
+/// keep_alive_timeout = 5
+/// shutdown_timeout = 30
+/// index_page = "index/index.html"
+///
+/// [error_pages]
+/// 404 = "not_found/not_found.html"
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::config::ServerConfig;
+///
+/// let config = ServerConfig {
+///     threads: 8,
+///     ..Default::default()
+/// };
+/// ```
+///
+/// [`run`]: crate::run
+/// [`load`]: ServerConfig::load
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    /// The address the server listens on.
+    pub bind_address: IpAddr,
+
+    /// The port the server listens on.
+    pub port: u16,
+
+    /// The directory the site's files are served from.
+    pub site_dir: PathBuf,
+
+    /// The number of worker threads connections are dispatched to.
+    ///
+    /// When `0`, connections are handled one at a time,
+    /// on the thread accepting them.
+    pub threads: usize,
+
+    /// The most connections waiting for a worker thread,
+    /// beyond which new connections are refused with a `503`.
+    ///
+    /// When `0`, the queue is unbounded.
+    pub queue_capacity: usize,
+
+    /// How long reading a request may block,
+    /// before the client is considered to have timed out.
+    pub read_timeout: Duration,
+
+    /// How long an idle connection is kept open,
+    /// waiting for its next request.
+    ///
+    /// When zero, connections are closed after their first response.
+    /// In single-threaded mode, an idle connection blocks every other client,
+    /// so a short timeout, or none, is recommended.
+    pub keep_alive_timeout: Duration,
+
+    /// How long shutting down waits for in-flight connections to finish,
+    /// before giving up on them.
+    pub shutdown_timeout: Duration,
+
+    /// The most bytes a request's line and headers may take up,
+    /// beyond which the client is sent a `431`.
+    pub max_header_size: usize,
+
+    /// The most bytes a request's body may take up,
+    /// beyond which the client is sent a `413`, without it being read.
+    pub max_body_size: usize,
+
+    /// The page served for `/`, relative to the site directory.
+    pub index_page: PathBuf,
+
+    /// Whether directories without an `index.html`
+    /// are served a listing of their contents, rather than a `404`.
+    pub directory_listing: bool,
+
+    /// Pages served in place of empty error responses,
+    /// keyed by status code, relative to the site directory.
+    pub error_pages: BTreeMap<u16, PathBuf>,
+}
+
+/// The layout of a configuration file,
+/// where any missing key keeps its default.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    bind_address: Option<IpAddr>,
+    port: Option<u16>,
+    site_dir: Option<PathBuf>,
+    threads: Option<usize>,
+    queue_capacity: Option<usize>,
+    read_timeout: Option<Seconds>,
+    keep_alive_timeout: Option<Seconds>,
+    shutdown_timeout: Option<Seconds>,
+    max_header_size: Option<usize>,
+    max_body_size: Option<usize>,
+    index_page: Option<PathBuf>,
+    directory_listing: Option<bool>,
+    #[serde(default)]
+    error_pages: BTreeMap<String, PathBuf>,
+}
+
+/// A timeout in a configuration file,
+/// which TOML distinguishes between whole and fractional numbers of.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Seconds {
+    Whole(i64),
+    Fractional(f64),
+}
+
+impl ServerConfig {
+    /// Reads the configuration file at `path`,
+    /// then applies any overrides from the environment.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the file can't be read,
+    /// isn't valid TOML, or has a setting of the wrong type,
+    /// or if an environment variable is invalid.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|err|ConfigError::new(ConfigErrorKind::Io(err.kind()), path.display()))?;
+
+        Self::from_toml(&contents)?
+            .override_from(env_vars()?)
+    }
+
+    /// Creates a configuration from the default settings,
+    /// with any overrides from the environment applied.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if an environment variable is invalid.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::default().override_from(env_vars()?)
+    }
+
+    /// Parses a configuration from TOML,
+    /// with missing settings keeping their defaults.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if `toml` isn't valid TOML,
+    /// has an unknown key, or a setting of the wrong type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use purple_blox::config::ServerConfig;
+    ///
+    /// let config = ServerConfig::from_toml("port = 8080\nread_timeout = 2.5").unwrap();
+    ///
+    /// assert_eq!(8080, config.port);
+    /// assert_eq!(Duration::from_millis(2500), config.read_timeout);
+    /// assert_eq!(ServerConfig::default().threads, config.threads);
+    /// ```
+    pub fn from_toml(toml: &str) -> Result<Self, ConfigError> {
+        let file: ConfigFile = toml::from_str(toml)
+            .map_err(|err|ConfigError::new(ConfigErrorKind::Syntax, err.message()))?;
+
+        let default = Self::default();
+        let mut error_pages = default.error_pages;
+
+        // TOML keys are always strings, so status codes are parsed here.
+        for (code, page) in file.error_pages {
+            let code = code.parse()
+                .map_err(|_|ConfigError::new(ConfigErrorKind::InvalidErrorStatus, &code))?;
+
+            error_pages.insert(code, page);
+        }
+
+        Ok(Self {
+            bind_address: file.bind_address.unwrap_or(default.bind_address),
+            port: file.port.unwrap_or(default.port),
+            site_dir: file.site_dir.unwrap_or(default.site_dir),
+            threads: file.threads.unwrap_or(default.threads),
+            queue_capacity: file.queue_capacity.unwrap_or(default.queue_capacity),
+            read_timeout: seconds("read_timeout", file.read_timeout, default.read_timeout)?,
+            keep_alive_timeout: seconds("keep_alive_timeout", file.keep_alive_timeout, default.keep_alive_timeout)?,
+            shutdown_timeout: seconds("shutdown_timeout", file.shutdown_timeout, default.shutdown_timeout)?,
+            max_header_size: file.max_header_size.unwrap_or(default.max_header_size),
+            max_body_size: file.max_body_size.unwrap_or(default.max_body_size),
+            index_page: file.index_page.unwrap_or(default.index_page),
+            directory_listing: file.directory_listing.unwrap_or(default.directory_listing),
+            error_pages,
+        })
+    }
+
+    /// Applies the settings given by variables prefixed with [`ENV_PREFIX`],
+    /// such as `PURPLE_BLOX_PORT`, ignoring any other variables.
+    ///
+    /// Every setting but the error pages can be overridden,
+    /// by its name in uppercase.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if a variable has an invalid value,
+    /// or doesn't name a setting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::config::ServerConfig;
+    ///
+    /// let config = ServerConfig::default()
+    ///     .override_from([("PURPLE_BLOX_THREADS", "2"), ("HOME", "/root")])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(2, config.threads);
+    /// ```
+    pub fn override_from<I, K, V>(mut self, vars: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>, {
+            for (key, value) in vars {
+                let (key, value) = (key.as_ref(), value.as_ref());
+                let name = match key.strip_prefix(ENV_PREFIX) {
+                    Some(name) => name,
+                    None => continue,
+                };
+
+                let invalid = ||ConfigError::new(ConfigErrorKind::InvalidVariable, key);
+
+                match name {
+                    "BIND_ADDRESS" => self.bind_address = value.parse().map_err(|_|invalid())?,
+                    "PORT" => self.port = value.parse().map_err(|_|invalid())?,
+                    "SITE_DIR" => self.site_dir = value.into(),
+                    "THREADS" => self.threads = value.parse().map_err(|_|invalid())?,
+                    "QUEUE_CAPACITY" => self.queue_capacity = value.parse().map_err(|_|invalid())?,
+                    "READ_TIMEOUT" => self.read_timeout = parse_seconds(value).ok_or_else(invalid)?,
+                    "KEEP_ALIVE_TIMEOUT" => self.keep_alive_timeout = parse_seconds(value).ok_or_else(invalid)?,
+                    "SHUTDOWN_TIMEOUT" => self.shutdown_timeout = parse_seconds(value).ok_or_else(invalid)?,
+                    "MAX_HEADER_SIZE" => self.max_header_size = value.parse().map_err(|_|invalid())?,
+                    "MAX_BODY_SIZE" => self.max_body_size = value.parse().map_err(|_|invalid())?,
+                    "INDEX_PAGE" => self.index_page = value.into(),
+                    "DIRECTORY_LISTING" => self.directory_listing = value.parse().map_err(|_|invalid())?,
+                    // Picked up by the binary, to find the file to load.
+                    "CONFIG" => (),
+                    _ => return Err(invalid()),
+                }
+            }
+
+            Ok(self)
+        }
+
+    /// Checks the settings can be served with,
+    /// such that the site directory and its pages exist.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the read timeout is zero,
+    /// the site directory or a page is missing,
+    /// or an error page is given for a status which isn't an error.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.read_timeout.is_zero() {
+            return Err(ConfigError::new(ConfigErrorKind::InvalidTimeout, "read_timeout"));
+        }
+
+        if !self.site_dir.is_dir() {
+            return Err(ConfigError::new(ConfigErrorKind::MissingSiteDir, self.site_dir.display()));
+        }
+
+        for (code, page) in &self.error_pages {
+            match Status::from_code(*code) {
+                Some(x) if x.code() >= 400 => (),
+                _ => return Err(ConfigError::new(ConfigErrorKind::InvalidErrorStatus, code)),
+            }
+
+            self.check_page(page)?;
+        }
+
+        self.check_page(&self.index_page)
+    }
+
+    /// Returns the socket address the server listens on.
+    pub fn addr(&self) -> SocketAddr {
+        SocketAddr::new(self.bind_address, self.port)
+    }
+
+    /// Returns the path of a page within the site directory.
+    pub fn page_path(&self, page: impl AsRef<Path>) -> PathBuf {
+        self.site_dir.join(page)
+    }
+
+    fn check_page(&self, page: &Path) -> Result<(), ConfigError> {
+        let path = self.page_path(page);
+
+        match path.is_file() {
+            true => Ok(()),
+            false => Err(ConfigError::new(ConfigErrorKind::MissingPage, path.display())),
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: Ipv4Addr::LOCALHOST.into(),
+            port: 7878,
+            site_dir: "purple_blox/site".into(),
+            threads: 4,
+            queue_capacity: 1024,
+            read_timeout: Duration::from_secs(30),
+            keep_alive_timeout: Duration::from_secs(5),
+            shutdown_timeout: Duration::from_secs(30),
+            max_header_size: 8 * 1024,
+            max_body_size: 1024 * 1024,
+            index_page: "index/index.html".into(),
+            directory_listing: false,
+            error_pages: BTreeMap::from([
+                (400, "bad_request/bad_request.html".into()),
+                (404, "not_found/not_found.html".into()),
+            ]),
+        }
+    }
+}
+
+/// Converts an optional number of seconds from a configuration file,
+/// keeping `default` when it's missing.
+fn seconds(name: &str, secs: Option<Seconds>, default: Duration) -> Result<Duration, ConfigError> {
+    let secs = match secs {
+        Some(Seconds::Whole(x)) => Some(x as f64),
+        Some(Seconds::Fractional(x)) => Some(x),
+        None => None,
+    };
+
+    match secs {
+        Some(x) => Duration::try_from_secs_f64(x)
+            .map_err(|_|ConfigError::new(ConfigErrorKind::InvalidTimeout, name)),
+        None => Ok(default),
+    }
+}
+
+/// Returns the environment variables prefixed with [`ENV_PREFIX`],
+/// or an error if one of their values isn't valid UTF-8.
+///
+/// Other variables are skipped, whatever they contain,
+/// as a name which isn't valid UTF-8 can't be one of ours.
+fn env_vars() -> Result<Vec<(String, String)>, ConfigError> {
+    env::vars_os()
+        .filter_map(|(key, value)|Some((key.into_string().ok()?, value)))
+        .filter(|(key, _)|key.starts_with(ENV_PREFIX))
+        .map(|(key, value)|match value.into_string() {
+            Ok(value) => Ok((key, value)),
+            Err(_) => Err(ConfigError::new(ConfigErrorKind::InvalidVariable, key)),
+        })
+        .collect()
+}
+
+fn parse_seconds(s: &str) -> Option<Duration> {
+    s.parse()
+        .ok()
+        .and_then(|x|Duration::try_from_secs_f64(x).ok())
+}
+
+/// The error type for loading and validating a [`ServerConfig`].
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    kind: ConfigErrorKind,
+    detail: String,
+}
+
+/// A list specifying the categories of [`ConfigError`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigErrorKind {
+    /// The configuration file could not be read.
+    Io(io::ErrorKind),
+    /// The configuration file was not valid, or had a setting of the wrong type.
+    Syntax,
+    /// An environment variable had an invalid value, or named no setting.
+    InvalidVariable,
+    /// A timeout was negative, or zero where that isn't allowed.
+    InvalidTimeout,
+    /// The site directory does not exist.
+    MissingSiteDir,
+    /// A configured page does not exist in the site directory.
+    MissingPage,
+    /// An error page was given for a status code which isn't an error.
+    InvalidErrorStatus,
+}
+
+impl ConfigError {
+    fn new(kind: ConfigErrorKind, detail: impl fmt::Display) -> Self {
+        Self {
+            kind,
+            detail: detail.to_string(),
+        }
+    }
+
+    pub fn kind(&self) -> &ConfigErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self.kind {
+            ConfigErrorKind::Io(kind) => return write!(f, "config file could not be read: {}: {}", kind, self.detail),
+            ConfigErrorKind::Syntax => "invalid config file",
+            ConfigErrorKind::InvalidVariable => "invalid environment variable",
+            ConfigErrorKind::InvalidTimeout => "invalid timeout",
+            ConfigErrorKind::MissingSiteDir => "site directory not found",
+            ConfigErrorKind::MissingPage => "page not found",
+            ConfigErrorKind::InvalidErrorStatus => "error page given for a non-error status",
+        };
+
+        write!(f, "{}: {}", description, self.detail)
+    }
+}
+
+impl std::error::Error for ConfigError {}
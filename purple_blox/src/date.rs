@@ -0,0 +1,115 @@
+//! Formatting and parsing of HTTP dates,
+//! as used by `Last-Modified` and `If-Modified-Since`.
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// Formats `time` as an HTTP date, in the IMF-fixdate format,
+/// truncated to whole seconds.
+///
+/// Times before the Unix epoch are formatted as the epoch.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use purple_blox::date::format;
+///
+/// let time = UNIX_EPOCH + Duration::from_secs(784111777);
+/// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", format(time));
+/// ```
+pub fn format(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let days = secs / 86400;
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        DAYS[((days + 4) % 7) as usize],
+        day,
+        MONTHS[month as usize - 1],
+        year,
+        secs % 86400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    )
+}
+
+/// Parses an HTTP date in the IMF-fixdate format,
+/// returning `None` if it's malformed, before the Unix epoch,
+/// or after the year 9999.
+///
+/// The obsolete RFC 850 and asctime formats aren't supported.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use purple_blox::date::parse;
+///
+/// let time = UNIX_EPOCH + Duration::from_secs(784111777);
+/// assert_eq!(Some(time), parse("Sun, 06 Nov 1994 08:49:37 GMT"));
+/// assert_eq!(None, parse("Sunday, 06-Nov-94 08:49:37 GMT"));
+/// ```
+pub fn parse(date: &str) -> Option<SystemTime> {
+    let mut parts = date.trim()
+        .split_once(", ")?
+        .1
+        .split(' ');
+
+    let day: u64 = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|&x|x == month)? as u64 + 1;
+    let year: u64 = parts.next()?.parse().ok()?;
+
+    let mut time = parts.next()?.split(':').map(|x|x.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (time.next()??, time.next()??, time.next()??);
+
+    if parts.next() != Some("GMT") || parts.next().is_some() || time.next().is_some() {
+        return None;
+    }
+
+    if !(1..=31).contains(&day) || !(1970..=9999).contains(&year) || hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+
+    let secs = days_from_civil(year, month, day)?
+        .checked_mul(86400)?
+        .checked_add(hours * 3600 + minutes * 60 + seconds)?;
+    UNIX_EPOCH.checked_add(Duration::from_secs(secs))
+}
+
+/// Converts days since the Unix epoch to a `(year, month, day)` date,
+/// using Howard Hinnant's algorithm.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Converts a date from 1970 onwards to days since the Unix epoch,
+/// the inverse of [`civil_from_days`],
+/// returning `None` if the count overflows.
+fn days_from_civil(year: u64, month: u64, day: u64) -> Option<u64> {
+    let year = year - u64::from(month <= 2);
+    let era = year / 400;
+    let yoe = year - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era.checked_mul(146097)?
+        .checked_add(doe)?
+        .checked_sub(719468)
+}
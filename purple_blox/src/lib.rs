@@ -1,54 +1,173 @@
-mod pool;
+#[cfg(feature = "tokio")]
+pub mod async_server;
+#[cfg(feature = "compression")]
+pub mod compression;
+pub mod config;
+pub mod date;
+pub mod middleware;
+pub mod pool;
+pub mod request;
+pub mod response;
+pub mod router;
+pub mod server;
+pub mod static_files;
+pub mod template;
+pub mod testing;
+pub mod url;
 
 use std::{
-    net,
-    io::prelude::*,
-    fs, 
-    path::Path
+    net::{self, SocketAddr},
+    io::{self, BufRead, BufReader},
+    panic::{self, AssertUnwindSafe},
+    fs,
+    path::{Path, PathBuf},
 };
 
-// Internal value
-const SITE_DIR: &str = "purple_blox/site";
-const GET: &[u8; 16] = b"GET / HTTP/1.1\r\n";
+use config::ServerConfig;
+use request::{Method, Request, RequestError};
+use response::{Response, Status};
+use router::Router;
+use server::{Server, ServerError, ServerErrorKind, ShutdownHandle, Sites};
+use static_files::StaticFiles;
 
-// Page directories
-const INDEX: &str = "index/index.html";
-const NOT_FOUND: &str = "not_found/not_found.html";
+/// Serves connections from `listener` with `router`,
+/// dispatching each to a worker thread,
+/// unless `config` specifies no threads.
+/// 
+/// See [`Server`] for a server which can be shut down.
+///
+/// # Errors
+///
+/// Will return [`Err`] if the listener's local address can't be read,
+/// or the worker threads couldn't be started.
+pub fn run(listener: net::TcpListener, router: Router, config: ServerConfig) -> Result<(), ServerError> {
+    Server::new(listener, router, config)?.run()
+}
+
+/// Creates a `Router` serving the configured index page,
+/// falling back to the site's files,
+/// with empty error responses replaced by the configured error pages.
+pub fn site_router(config: &ServerConfig) -> Router {
+    let pages = config.clone();
+
+    let mut router = Router::new();
+    router.get("/", page(Status::Ok, config.page_path(&config.index_page)))
+        .fallback(StaticFiles::new(&config.site_dir).with_listing(config.directory_listing).into_handler())
+        .middleware(move |req, next|error_page(&pages, next.run(req)));
+
+    router
+}
+
+/// Creates a handler which responds with `status`,
+/// and the contents of the page at `filepath`.
+///
+/// Responds with a `500` if the page can't be read.
+pub fn page(status: Status, filepath: impl Into<PathBuf>) -> impl Fn(&Request) -> Response + Send + Sync + 'static {
+    let filepath = filepath.into();
+    move |_|html_page(status, &filepath).unwrap_or_else(|_|Response::internal_server_error())
+}
+
+fn html_page(status: Status, filepath: &Path) -> io::Result<Response> {
+    let response = Response::new(status)
+        .header("Content-Type", "text/html; charset=utf-8")
+        .body(fs::read(filepath)?);
 
-// Response codes
-const OK: &str = "200 OK";
-const ERROR_404: &str = "404 NOT FOUND";
+    Ok(response)
+}
 
-pub fn run(listener: net::TcpListener) {
-    let pool = pool::ThreadPool::new(4)
-        .unwrap();
+/// Replaces the body of an empty error response,
+/// with the page configured for its status.
+fn error_page(config: &ServerConfig, response: Response) -> Response {
+    let page = match config.error_pages.get(&response.status().code()) {
+        Some(page) if response.contents().is_empty() && !response.is_streaming() => config.page_path(page),
+        _ => return response,
+    };
 
-    listener.incoming()
-        .filter_map(Result::ok)
-        .for_each(|x|pool.execute(||handle_connection(x)))
+    match html_page(response.status(), &page) {
+        Ok(page) => response.headers()
+            .iter()
+            .fold(page, |acc, (name, value)|acc.header(name, value.as_str()))
+            .header("Content-Type", "text/html; charset=utf-8"),
+        Err(_) => response,
+    }
 }
 
-fn handle_connection(mut stream: net::TcpStream) {
-    let mut buffer = [0; 1024];
+/// Handles a request read from a connection,
+/// returning the response, whether to keep the connection alive,
+/// and whether to include the response's body, or chunk it.
+pub(crate) fn respond(request: Result<Request, RequestError>, peer: Option<SocketAddr>, sites: &Sites, config: &ServerConfig, closing: bool) -> (Response, bool, bool, bool) {
+    let (mut response, keep_alive, include_body, chunked) = match request {
+        Ok(mut request) => {
+            let keep_alive = wants_keep_alive(&request);
+            let include_body = request.method() != Method::Head;
+            // HTTP/1.0 clients don't understand chunked bodies.
+            let chunked = request.version() != "HTTP/1.0";
+            request.set_peer_addr(peer);
+            let router = sites.route(request.header("Host"));
+
+            // A panicking handler answers with a 500, rather than taking the connection's thread down.
+            let response = panic::catch_unwind(AssertUnwindSafe(||router.handle(request)))
+                .unwrap_or_else(|payload|{
+                    eprintln!("Handler panicked: {}", pool::panic_message(&*payload));
+                    error_page(config, ServerError::new(ServerErrorKind::Internal).into())
+                });
 
-    stream.read(&mut buffer).unwrap();
-    
-    let (status, filepath) = if buffer.starts_with(GET) {
-        (OK, INDEX)
-    } else {
-        (ERROR_404, NOT_FOUND)
+            (response, keep_alive, include_body, chunked)
+        },
+        Err(err) => (error_page(config, ServerError::from(err).into()), false, true, true),
     };
 
-    let filepath = Path::new(SITE_DIR).join(filepath);
-    let contents = fs::read_to_string(filepath).unwrap();
+    // Without chunking, the end of an unbounded stream is marked by closing the connection.
+    let keep_alive = keep_alive
+        && (chunked || !response.is_unbounded())
+        && !config.keep_alive_timeout.is_zero()
+        && !closing;
+    response.set_header("Connection", if keep_alive { "keep-alive" } else { "close" });
 
-    let response = format!(
-        "HTTPS/1.1 {}\r\nContent-Length: {}\r\n\r\n{}", 
-        status, 
-        contents.len(), 
-        contents
-    );
+    (response, keep_alive, include_body, chunked)
+}
 
-    stream.write(response.as_bytes()).unwrap();
-    stream.flush().unwrap();
-}
\ No newline at end of file
+/// Serves the requests sent over `stream`,
+/// until the client closes it, asks for it to be closed,
+/// or it sits idle for longer than the keep-alive timeout.
+pub(crate) fn handle_connection(stream: net::TcpStream, sites: &Sites, config: &ServerConfig, shutdown: &ShutdownHandle) -> Result<(), ServerError> {
+    let mut reader = BufReader::new(&stream);
+    let mut idle_timeout = config.read_timeout;
+
+    loop {
+        // Waits for the first bytes of the next request,
+        // closing quietly if the client closes the connection or goes idle.
+        stream.set_read_timeout(Some(idle_timeout))?;
+
+        if !matches!(reader.fill_buf(), Ok(x) if !x.is_empty()) {
+            return Ok(());
+        }
+
+        stream.set_read_timeout(Some(config.read_timeout))?;
+
+        let request = Request::read_limited(&mut reader, config.max_header_size, config.max_body_size);
+        let (mut response, keep_alive, include_body, chunked) = respond(request, stream.peer_addr().ok(), sites, config, shutdown.is_shutdown());
+
+        response.write_to(&stream, include_body, chunked)?;
+
+        if !keep_alive {
+            return Ok(());
+        }
+
+        idle_timeout = config.keep_alive_timeout;
+    }
+}
+
+/// Returns `true` if the client wants the connection kept open after `request`,
+/// defaulting to `true` for HTTP/1.1 and `false` for HTTP/1.0.
+fn wants_keep_alive(request: &Request) -> bool {
+    let connection = request.header("connection")
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let mut options = connection.split(',').map(str::trim);
+
+    match request.version() {
+        "HTTP/1.0" => options.any(|x|x == "keep-alive"),
+        _ => !options.any(|x|x == "close"),
+    }
+}
@@ -1,8 +1,53 @@
-use std::net;
-use purple_blox;
+use std::{env, io, net, path::Path, process};
+use purple_blox::{
+    config::{ServerConfig, ENV_PREFIX},
+    middleware,
+    server::Server,
+};
+
+const CONFIG_FILE: &str = "purple_blox/purple_blox.toml";
+
+/// The smallest body worth compressing, in bytes.
+#[cfg(feature = "compression")]
+const COMPRESSION_THRESHOLD: usize = 1024;
 
 fn main() {
-    let listener = net::TcpListener::bind("127.0.0.1:7878").unwrap();
+    let config = load_config().unwrap_or_else(|err|{
+        eprintln!("Invalid configuration: {}", err);
+        process::exit(1);
+    });
+
+    let listener = net::TcpListener::bind(config.addr()).unwrap_or_else(|err|{
+        eprintln!("Couldn't bind to {}: {}", config.addr(), err);
+        process::exit(1);
+    });
+
+    let mut router = purple_blox::site_router(&config);
+    router.middleware(middleware::access_log(io::stdout()));
+
+    #[cfg(feature = "compression")]
+    router.middleware(middleware::compress(COMPRESSION_THRESHOLD));
+
+    let server = Server::new(listener, router, config).unwrap();
+
+    server.shutdown_on_ctrl_c().unwrap();
+
+    if let Err(err) = server.run() {
+        eprintln!("Server failed: {}", err);
+        process::exit(1);
+    }
+}
+
+/// Loads the configuration file named by `PURPLE_BLOX_CONFIG`,
+/// or the default file when it exists,
+/// falling back to the default settings and environment otherwise.
+fn load_config() -> Result<ServerConfig, purple_blox::config::ConfigError> {
+    let config = match env::var(format!("{}CONFIG", ENV_PREFIX)) {
+        Ok(path) => ServerConfig::load(path)?,
+        Err(_) if Path::new(CONFIG_FILE).is_file() => ServerConfig::load(CONFIG_FILE)?,
+        Err(_) => ServerConfig::from_env()?,
+    };
 
-    purple_blox::run(listener);
+    config.validate()?;
+    Ok(config)
 }
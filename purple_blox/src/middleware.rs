@@ -0,0 +1,247 @@
+//! Layers wrapping the handling of requests,
+//! and the middleware shipped with the server.
+use std::{
+    collections::HashMap,
+    io::Write,
+    net::IpAddr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use my_rusttools::traits::{HumanFormat, HumanizeDuration};
+
+use crate::{
+    request::Request,
+    response::{Response, Status},
+    router::Router,
+};
+
+/// A boxed middleware layer, as stored by a [`Router`].
+pub type Middleware = Box<dyn Fn(Request, Next<'_>) -> Response + Send + Sync + 'static>;
+
+/// The remainder of a middleware chain,
+/// ending with the router's handlers.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::{
+///     middleware::Next,
+///     request::Request,
+///     response::Response,
+///     router::Router,
+/// };
+///
+/// let mut router = Router::new();
+/// router.get("/", |_: &Request|Response::ok())
+///     .middleware(|req: Request, next: Next<'_>|next.run(req).header("X-Powered-By", "purple_blox"));
+///
+/// let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+/// assert_eq!(Some("purple_blox"), router.handle(request).get_header("x-powered-by"));
+/// ```
+pub struct Next<'a> {
+    middleware: &'a [Middleware],
+    router: &'a Router,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(middleware: &'a [Middleware], router: &'a Router) -> Self {
+        Self {
+            middleware,
+            router,
+        }
+    }
+
+    /// Passes `request` to the next layer of the chain,
+    /// returning its response.
+    pub fn run(self, request: Request) -> Response {
+        match self.middleware.split_first() {
+            Some((layer, rest)) => layer(request, Next::new(rest, self.router)),
+            None => self.router.dispatch(request),
+        }
+    }
+}
+
+/// Creates a middleware layer, writing a line to `target` for each request,
+/// recording the client's address, the request's method and path,
+/// the response status, and how long the request took to handle.
+///
+/// Failures writing to `target` are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use purple_blox::{middleware, router::Router};
+///
+/// let mut router = Router::new();
+/// router.middleware(middleware::access_log(io::stdout()));
+/// ```
+pub fn access_log<W>(target: W) -> impl Fn(Request, Next<'_>) -> Response + Send + Sync + 'static
+where
+    W: Write + Send + 'static, {
+        let target = Mutex::new(target);
+
+        move |req, next|{
+            let start = Instant::now();
+            let peer = req.peer_addr()
+                .map_or_else(||String::from("-"), |x|x.to_string());
+            let method = req.method();
+            let path = req.path().to_owned();
+
+            let response = next.run(req);
+
+            if let Ok(mut target) = target.lock() {
+                let _ = writeln!(target, "{} \"{} {}\" {} {}", peer, method, path, response.status().code(), start.elapsed().humanize(HumanFormat::Short));
+            }
+
+            response
+        }
+    }
+
+/// Creates a middleware layer limiting how often each client can make requests,
+/// answering those over the limit with a `429 Too Many Requests`,
+/// and a `Retry-After` header saying when to try again.
+///
+/// Clients are told apart by their IP address,
+/// each having a bucket of up to `burst` requests,
+/// refilled by one request every `interval`.
+/// Requests without a peer address, such as those not read from a connection,
+/// aren't limited.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use purple_blox::{middleware, router::Router};
+///
+/// let mut router = Router::new();
+/// router.middleware(middleware::rate_limit(20, Duration::from_secs(1)));
+/// ```
+pub fn rate_limit(burst: u32, interval: Duration) -> impl Fn(Request, Next<'_>) -> Response + Send + Sync + 'static {
+    let limiter = Mutex::new(RateLimiter {
+        buckets: HashMap::new(),
+        swept: Instant::now(),
+    });
+    let burst = f64::from(burst.max(1));
+    let interval = interval.max(Duration::from_nanos(1));
+
+    move |req, next|{
+        let ip = match req.peer_addr() {
+            Some(x) => x.ip(),
+            None => return next.run(req),
+        };
+
+        let wait = limiter.lock()
+            .map_or(None, |mut x|x.take(ip, burst, interval));
+
+        match wait {
+            None => next.run(req),
+            Some(wait) => Response::new(Status::TooManyRequests)
+                .header("Retry-After", wait.as_secs_f64().ceil().max(1.0).to_string()),
+        }
+    }
+}
+
+/// The token buckets of clients seen recently.
+struct RateLimiter {
+    buckets: HashMap<IpAddr, Bucket>,
+    /// When full buckets were last forgotten.
+    swept: Instant,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Takes a token from the client's bucket,
+    /// or returns how long until one is available, if it's empty.
+    fn take(&mut self, ip: IpAddr, burst: f64, interval: Duration) -> Option<Duration> {
+        let now = Instant::now();
+        let refill = |x: &Bucket|(x.tokens + now.duration_since(x.updated).as_secs_f64() / interval.as_secs_f64()).min(burst);
+
+        // Full buckets are the same as new ones, so can be forgotten,
+        // stopping clients which have gone quiet from building up.
+        if now.duration_since(self.swept) >= interval.mul_f64(burst) {
+            self.buckets.retain(|_, x|refill(x) < burst);
+            self.swept = now;
+        }
+
+        let bucket = self.buckets
+            .entry(ip)
+            .or_insert(Bucket {
+                tokens: burst,
+                updated: now,
+            });
+
+        bucket.tokens = refill(bucket);
+        bucket.updated = now;
+
+        match bucket.tokens >= 1.0 {
+            true => {
+                bucket.tokens -= 1.0;
+                None
+            },
+            false => Some(interval.mul_f64(1.0 - bucket.tokens)),
+        }
+    }
+}
+
+/// Creates a middleware layer compressing responses with `gzip` or `deflate`,
+/// as negotiated with the request's `Accept-Encoding` header.
+///
+/// Only bodies of compressible MIME types, at least `min_size` bytes long,
+/// and without a `Content-Encoding` already, are compressed.
+/// Streamed bodies, and partial content, are left as they are.
+/// Responses of compressible types get `Vary: Accept-Encoding`,
+/// whether they're compressed or not,
+/// so caches keep the two versions apart.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::{middleware, router::Router};
+///
+/// let mut router = Router::new();
+/// router.middleware(middleware::compress(1024));
+/// ```
+#[cfg(feature = "compression")]
+pub fn compress(min_size: usize) -> impl Fn(Request, Next<'_>) -> Response + Send + Sync + 'static {
+    use crate::compression;
+
+    move |req, next|{
+        let encoding = req.header("accept-encoding")
+            .and_then(compression::negotiate);
+        let response = next.run(req);
+
+        let compressible = response.get_header("Content-Type")
+            .is_some_and(compression::is_compressible);
+
+        if !compressible {
+            return response;
+        }
+
+        let vary = match response.get_header("Vary") {
+            Some(x) if x.split(',').any(|x|x.trim().eq_ignore_ascii_case("accept-encoding")) => x.to_owned(),
+            Some(x) => format!("{}, Accept-Encoding", x),
+            None => String::from("Accept-Encoding"),
+        };
+        let response = response.header("Vary", vary);
+
+        let encoding = match encoding {
+            Some(x) if response.contents().len() >= min_size
+                && !response.is_streaming()
+                && response.get_header("Content-Range").is_none()
+                && response.get_header("Content-Encoding").is_none() => x,
+            _ => return response,
+        };
+
+        match encoding.encode(response.contents()) {
+            Ok(body) => response.header("Content-Encoding", encoding.to_string())
+                .body(body),
+            Err(_) => response,
+        }
+    }
+}
@@ -1,15 +1,18 @@
 use std::{
+    any::Any,
+    collections::{BTreeMap, VecDeque},
+    panic::{self, AssertUnwindSafe},
     ops,
-    fmt, 
-    thread, 
+    fmt,
+    io,
+    thread,
+    time::{Duration, Instant},
     sync::{
-        mpsc::{
-            self, 
-            Sender, 
-            Receiver
-        }, 
-        Mutex, 
-        Arc
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+        Arc,
+        Condvar,
+        Mutex,
     }
 };
 
@@ -17,89 +20,857 @@ type Message = ops::ControlFlow<(), Box<dyn FnOnce() + Send + 'static>>;
 
 pub struct ThreadPool {
     workers: Vec<Worker>,
-    pipeline: Sender<Message>
+    queue: Arc<Shared>,
+    scheduler: Arc<Scheduler>,
+    /// Started by the first scheduled job.
+    timer: Mutex<Option<thread::JoinHandle<()>>>,
+}
+
+/// A token for cancelling a job scheduled with
+/// [`ThreadPool::execute_after`] or [`ThreadPool::every`].
+///
+/// Clones of a token cancel the same job.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+/// Jobs waiting for their time to be queued,
+/// keyed by when that is, and the order they were scheduled in.
+struct Scheduler {
+    state: Mutex<Schedule>,
+    /// Signalled when a job is scheduled, or the timer is stopped.
+    wake: Condvar,
+}
+
+#[derive(Default)]
+struct Schedule {
+    tasks: BTreeMap<(Instant, u64), Task>,
+    next_id: u64,
+    stopped: bool,
+}
+
+enum Task {
+    Once(CancelToken, Box<dyn FnOnce() + Send + 'static>),
+    Every(CancelToken, Duration, Arc<dyn Fn() + Send + Sync + 'static>),
+}
+
+/// Hooks called as a [`ThreadPool`]'s workers run jobs,
+/// letting embedding applications log or measure them.
+///
+/// Every method does nothing by default, as does the `()` implementation,
+/// which pools use unless given others with [`PoolBuilder::events`].
+///
+/// # Examples
+///
+/// ```
+/// use std::{
+///     sync::atomic::{AtomicUsize, Ordering},
+///     time::Duration,
+/// };
+/// use purple_blox::pool::{PoolEvents, ThreadPool};
+///
+/// #[derive(Default)]
+/// struct Counter(AtomicUsize);
+///
+/// impl PoolEvents for Counter {
+///     fn on_job_end(&self, _worker: usize, _elapsed: Duration, _panic: Option<&str>) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let pool = ThreadPool::builder(2)
+///     .events(Counter::default())
+///     .build()
+///     .unwrap();
+/// ```
+pub trait PoolEvents: Send + Sync {
+    /// Called by a worker as it starts running a job.
+    fn on_job_start(&self, _worker: usize) {}
+
+    /// Called by a worker once a job has finished, with how long it ran for,
+    /// and the message it panicked with, if it panicked.
+    fn on_job_end(&self, _worker: usize, _elapsed: Duration, _panic: Option<&str>) {}
+
+    /// Called by a worker as it shuts down.
+    fn on_shutdown(&self, _worker: usize) {}
+}
+
+impl PoolEvents for () {}
+
+/// Pool events printing each to stdout,
+/// and panics to stderr.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrintEvents;
+
+impl PoolEvents for PrintEvents {
+    fn on_job_start(&self, worker: usize) {
+        println!("Worker {} now working on a job.", worker);
+    }
+
+    fn on_job_end(&self, worker: usize, elapsed: Duration, panic: Option<&str>) {
+        match panic {
+            Some(message) => eprintln!("Worker {} recovered from a panicking job: {}", worker, message),
+            None => println!("Worker {} finished a job in {:?}.", worker, elapsed),
+        }
+    }
+
+    fn on_shutdown(&self, worker: usize) {
+        println!("Shutting down worker {}.", worker);
+    }
+}
+
+/// A builder for [`ThreadPool`]s, created by [`ThreadPool::builder`].
+pub struct PoolBuilder {
+    threads: usize,
+    capacity: Option<usize>,
+    policy: QueuePolicy,
+    events: Box<dyn PoolEvents>,
+    name: String,
+}
+
+/// The prefix workers' thread names are given by default.
+pub const DEFAULT_THREAD_NAME: &str = "purple-blox-worker";
+
+/// How [`ThreadPool::execute`] behaves when a bounded queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueuePolicy {
+    /// Waits for a worker to take a job, making room for the new one.
+    Block,
+    /// Refuses the new job, returning an [`Err`].
+    Reject,
+    /// Discards the longest waiting job, making room for the new one.
+    DropOldest,
+}
+
+/// The state shared between the pool and its workers,
+/// including the jobs waiting for a worker.
+struct Shared {
+    messages: Mutex<VecDeque<Message>>,
+    /// The jobs among `messages`, only changed with `messages` locked,
+    /// so checking for room doesn't count them.
+    jobs: AtomicUsize,
+    /// Signalled when a message is queued.
+    available: Condvar,
+    /// Signalled when a message is taken, making room in a bounded queue.
+    space: Condvar,
+    capacity: Option<usize>,
+    policy: QueuePolicy,
+    peak: AtomicUsize,
+    dropped: AtomicUsize,
+    panicked: AtomicUsize,
+    events: Box<dyn PoolEvents>,
+    name: String,
 }
 
 impl ThreadPool {
-    /// Creates a new `ThreadPool`.
-    /// 
-    /// `workers` defines the number of threads which can be started.
-    /// 
+    /// Creates a new `ThreadPool`, with an unbounded queue.
+    ///
+    /// `threads` defines the number of threads which can be started.
+    ///
     /// # Errors
-    /// 
-    /// Will return [`Err`] if `workers` is 0.
+    ///
+    /// Will return [`Err`] if `threads` is 0.
     pub fn new(threads: usize) -> Result<Self, PoolInitialisationError> {
-        match threads > 0 {
-            true => {
-                let (tx, rx) = mpsc::channel();
-
-                let rx = Arc::new(Mutex::new(rx));
-
-                let mut workers = Vec::with_capacity(threads);
-                (0..threads)
-                    .for_each(|i|workers.push(Worker::new(i, Arc::clone(&rx))));
-
-                Ok(Self {
-                    workers: workers,
-                    pipeline: tx,
-                })
-            },
-            false => Err(PoolInitialisationError{
-                kind: PoolInitialisationErrorKind::ZeroThreads
-            })
+        Self::builder(threads).build()
+    }
+
+    /// Creates a new `ThreadPool`, queueing at most `capacity` jobs,
+    /// with `policy` deciding what happens to jobs executed while it's full.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if `threads` or `capacity` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::pool::{QueuePolicy, ThreadPool};
+    ///
+    /// let pool = ThreadPool::bounded(2, 64, QueuePolicy::Reject).unwrap();
+    ///
+    /// assert!(pool.execute(||println!("Hello from the pool!")).is_ok());
+    /// assert_eq!(Some(64), pool.capacity());
+    /// ```
+    pub fn bounded(threads: usize, capacity: usize, policy: QueuePolicy) -> Result<Self, PoolInitialisationError> {
+        Self::builder(threads)
+            .bounded(capacity, policy)
+            .build()
+    }
+
+    /// Creates a builder for a `ThreadPool` of `threads` workers,
+    /// for configuring more than [`new`] and [`bounded`] allow.
+    ///
+    /// [`new`]: ThreadPool::new
+    /// [`bounded`]: ThreadPool::bounded
+    pub fn builder(threads: usize) -> PoolBuilder {
+        PoolBuilder {
+            threads,
+            capacity: None,
+            policy: QueuePolicy::Block,
+            events: Box::new(()),
+            name: DEFAULT_THREAD_NAME.to_string(),
         }
     }
 
-    pub fn execute<F>(&self, f: F)
+    /// Queues `f` to be run by the next available worker.
+    ///
+    /// When a bounded queue is full, the pool's [`QueuePolicy`] applies.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the queue is full,
+    /// and the pool's policy is [`QueuePolicy::Reject`].
+    pub fn execute<F>(&self, f: F) -> Result<(), ExecuteError>
     where
         F: FnOnce(),
         F: Send + 'static, {
-            self.pipeline
-                .send(Message::Continue(Box::new(f)))
-                .unwrap()
+            self.queue.push(Message::Continue(Box::new(f)))
         }
+
+    /// Queues `f` to be run by the next available worker,
+    /// returning a handle for collecting its result.
+    ///
+    /// When a bounded queue is full, the pool's [`QueuePolicy`] applies.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the queue is full,
+    /// and the pool's policy is [`QueuePolicy::Reject`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::pool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::new(4).unwrap();
+    /// let handles: Vec<_> = (1..=10u64)
+    ///     .map(|x|pool.submit(move||x * x).unwrap())
+    ///     .collect();
+    ///
+    /// let total: u64 = handles.into_iter()
+    ///     .map(|x|x.join().unwrap())
+    ///     .sum();
+    ///
+    /// assert_eq!(385, total);
+    /// ```
+    pub fn submit<F, T>(&self, f: F) -> Result<JobHandle<T>, ExecuteError>
+    where
+        F: FnOnce() -> T,
+        F: Send + 'static,
+        T: Send + 'static, {
+            let (tx, rx) = mpsc::channel();
+
+            self.execute(move||{
+                match panic::catch_unwind(AssertUnwindSafe(f)) {
+                    Ok(x) => {
+                        let _ = tx.send(Ok(x));
+                    },
+                    Err(payload) => {
+                        let _ = tx.send(Err(JobError {
+                            kind: JobErrorKind::Panicked
+                        }));
+                        // Rethrown for the worker to record, as with any other panicking job.
+                        panic::resume_unwind(payload)
+                    },
+                }
+            })?;
+
+            Ok(JobHandle {
+                result: rx
+            })
+        }
+
+    /// Queues `f` to be run by the next available worker,
+    /// once `delay` has passed.
+    ///
+    /// Scheduled jobs are queued by a timer thread, started on first use,
+    /// and subject to the pool's [`QueuePolicy`] at that point,
+    /// so may be dropped if the queue is full.
+    /// Jobs still waiting for their time when the pool shuts down are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{sync::mpsc, time::Duration};
+    /// use purple_blox::pool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::new(1).unwrap();
+    /// let (tx, rx) = mpsc::channel();
+    ///
+    /// pool.execute_after(Duration::from_millis(10), move||tx.send("later").unwrap());
+    /// assert_eq!("later", rx.recv().unwrap());
+    /// ```
+    pub fn execute_after<F>(&self, delay: Duration, f: F) -> CancelToken
+    where
+        F: FnOnce(),
+        F: Send + 'static, {
+            let token = CancelToken::default();
+            self.schedule(Instant::now() + delay, Task::Once(token.clone(), Box::new(f)));
+            token
+        }
+
+    /// Queues `f` to be run every `period`, starting one period from now,
+    /// until the returned token is cancelled, or the pool shuts down.
+    ///
+    /// Runs are scheduled at a fixed rate,
+    /// so a run taking longer than `period` may overlap with the next.
+    /// As with [`execute_after`], runs arriving while the queue is full
+    /// are subject to the pool's [`QueuePolicy`].
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `period` is zero,
+    /// which would queue runs as fast as the timer could, without end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{sync::mpsc, time::Duration};
+    /// use purple_blox::pool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::new(1).unwrap();
+    /// let (tx, rx) = mpsc::channel();
+    ///
+    /// let token = pool.every(Duration::from_millis(10), move||{
+    ///     let _ = tx.send("tick");
+    /// });
+    ///
+    /// assert_eq!("tick", rx.recv().unwrap());
+    /// assert_eq!("tick", rx.recv().unwrap());
+    /// token.cancel();
+    /// ```
+    ///
+    /// [`execute_after`]: ThreadPool::execute_after
+    pub fn every<F>(&self, period: Duration, f: F) -> CancelToken
+    where
+        F: Fn(),
+        F: Send + Sync + 'static, {
+            assert!(!period.is_zero(), "periodic jobs must have a non-zero period");
+
+            let token = CancelToken::default();
+            self.schedule(Instant::now() + period, Task::Every(token.clone(), period, Arc::new(f)));
+            token
+        }
+
+    /// Adds a task to the schedule, starting the timer thread if it isn't running.
+    fn schedule(&self, at: Instant, task: Task) {
+        self.scheduler.insert(at, task);
+
+        let mut timer = self.timer.lock().unwrap();
+
+        if timer.is_none() {
+            let scheduler = Arc::clone(&self.scheduler);
+            let queue = Arc::clone(&self.queue);
+            *timer = Some(thread::spawn(move||scheduler.run(&queue)));
+        }
+    }
+
+    /// Stops the timer thread, discarding any jobs waiting for their time.
+    fn stop_timer(&self) {
+        self.scheduler.state.lock().unwrap().stopped = true;
+        self.scheduler.wake.notify_all();
+
+        if let Some(timer) = self.timer.lock().unwrap().take() {
+            let _ = timer.join();
+        }
+    }
+
+    /// Returns the number of jobs waiting for a worker.
+    pub fn queue_depth(&self) -> usize {
+        self.queue.jobs.load(Ordering::Relaxed)
+    }
+
+    /// Returns the most jobs that have been waiting for a worker at once.
+    pub fn peak_queue_depth(&self) -> usize {
+        self.queue.peak.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of jobs refused or discarded because the queue was full.
+    pub fn dropped_jobs(&self) -> usize {
+        self.queue.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of jobs which have panicked.
+    ///
+    /// A panicking job doesn't take its worker down with it,
+    /// the worker moving on to the next job.
+    pub fn panicked_jobs(&self) -> usize {
+        self.queue.panicked.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of workers whose threads are still running.
+    ///
+    /// This is only ever less than the pool's size
+    /// if a worker's thread has died outside of a job.
+    pub fn healthy_workers(&self) -> usize {
+        self.workers
+            .iter()
+            .filter(|x|x.thread.as_ref().is_some_and(|x|!x.is_finished()))
+            .count()
+    }
+
+    /// Replaces any workers whose threads have died,
+    /// returning how many were respawned.
+    ///
+    /// Replacements keep the stats of the workers they replace.
+    /// Workers whose threads couldn't be respawned are left dead.
+    pub fn rebuild(&mut self) -> usize {
+        let queue = &self.queue;
+
+        self.workers
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, x)|x.thread.as_ref().is_some_and(|x|x.is_finished()))
+            .filter_map(|(id, x)|{
+                if let Some(handle) = x.thread.take() {
+                    let _ = handle.join();
+                }
+                x.thread = Worker::spawn(id, Arc::clone(queue), Arc::clone(&x.counters)).ok();
+                x.thread.as_ref()
+            })
+            .count()
+    }
+
+    /// Returns a snapshot of each worker's stats, in order of their ids.
+    ///
+    /// A worker's stats are updated once its job has returned,
+    /// so may lag just behind a result sent by the job itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::pool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::new(2).unwrap();
+    /// let stats = pool.stats();
+    ///
+    /// assert_eq!(2, stats.len());
+    /// assert!(stats.iter().all(|x|x.jobs() == 0));
+    /// ```
+    pub fn stats(&self) -> Vec<WorkerStats> {
+        self.workers
+            .iter()
+            .enumerate()
+            .map(|(id, x)|WorkerStats {
+                id,
+                jobs: x.counters.jobs.load(Ordering::Relaxed),
+                busy: Duration::from_nanos(x.counters.busy_nanos.load(Ordering::Relaxed)),
+            })
+            .collect()
+    }
+
+    /// Returns the prefix given to the workers' thread names.
+    pub fn name(&self) -> &str {
+        &self.queue.name
+    }
+
+    /// Returns the most jobs the queue holds, if it's bounded.
+    pub fn capacity(&self) -> Option<usize> {
+        self.queue.capacity
+    }
+
+    /// Returns the policy applied when a bounded queue is full.
+    pub fn policy(&self) -> QueuePolicy {
+        self.queue.policy
+    }
+
+    /// Shuts the pool down, waiting up to `timeout`
+    /// for queued and in-flight jobs to finish.
+    ///
+    /// Returns `true` if every worker finished within the timeout,
+    /// including any which died to a panicking [`PoolEvents`] hook.
+    /// Workers which haven't are detached, and left to finish in the background.
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> bool {
+        self.stop_timer();
+        self.send_shutdown();
+
+        let deadline = Instant::now() + timeout;
+        let mut handles: Vec<_> = self.workers
+            .drain(..)
+            .filter_map(|mut x|x.thread.take())
+            .collect();
+
+        while handles.iter().any(|x|!x.is_finished()) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let (finished, unfinished): (Vec<_>, Vec<_>) = handles.drain(..)
+            .partition(|x|x.is_finished());
+
+        // Workers killed by a panicking event hook have finished too.
+        finished.into_iter()
+            .for_each(|x|{
+                let _ = x.join();
+            });
+
+        unfinished.is_empty()
+    }
+
+    /// Sends a shutdown message for each of the pool's workers.
+    ///
+    /// Being queued behind any pending jobs,
+    /// workers finish the jobs ahead of it before shutting down.
+    fn send_shutdown(&self) {
+        let mut messages = self.queue.messages.lock().unwrap();
+
+        // Shutdown messages bypass the queue's capacity,
+        // so shutting down never blocks or fails.
+        for _ in &self.workers {
+            messages.push_back(Message::Break(()));
+        }
+
+        self.queue.available.notify_all();
+    }
+}
+
+impl PoolBuilder {
+    /// Bounds the pool's queue to `capacity` jobs,
+    /// with `policy` deciding what happens to jobs executed while it's full.
+    #[must_use]
+    pub fn bounded(mut self, capacity: usize, policy: QueuePolicy) -> Self {
+        self.capacity = Some(capacity);
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the prefix of the workers' thread names,
+    /// which are suffixed with each worker's id, as in `purple-blox-worker-3`.
+    ///
+    /// Defaults to [`DEFAULT_THREAD_NAME`].
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the hooks the pool's workers call as they run jobs.
+    #[must_use]
+    pub fn events(mut self, events: impl PoolEvents + 'static) -> Self {
+        self.events = Box::new(events);
+        self
+    }
+
+    /// Creates the pool, starting its workers.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the pool has no threads,
+    /// a bounded queue has no capacity,
+    /// or a worker's thread couldn't be spawned.
+    pub fn build(self) -> Result<ThreadPool, PoolInitialisationError> {
+        if self.threads == 0 {
+            return Err(PoolInitialisationError{
+                kind: PoolInitialisationErrorKind::ZeroThreads
+            });
+        }
+
+        if self.capacity == Some(0) {
+            return Err(PoolInitialisationError{
+                kind: PoolInitialisationErrorKind::ZeroCapacity
+            });
+        }
+
+        let queue = Arc::new(Shared {
+            messages: Mutex::new(VecDeque::new()),
+            jobs: AtomicUsize::new(0),
+            available: Condvar::new(),
+            space: Condvar::new(),
+            capacity: self.capacity,
+            policy: self.policy,
+            peak: AtomicUsize::new(0),
+            dropped: AtomicUsize::new(0),
+            panicked: AtomicUsize::new(0),
+            events: self.events,
+            name: self.name,
+        });
+
+        let workers = (0..self.threads)
+            .map(|i|Worker::new(i, Arc::clone(&queue)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err|PoolInitialisationError {
+                kind: PoolInitialisationErrorKind::Spawn(err.kind())
+            })?;
+
+        Ok(ThreadPool {
+            workers,
+            queue,
+            scheduler: Arc::new(Scheduler {
+                state: Mutex::new(Schedule::default()),
+                wake: Condvar::new(),
+            }),
+            timer: Mutex::new(None),
+        })
+    }
+}
+
+impl Shared {
+    /// Queues a job, applying the queue's policy if it's full.
+    fn push(&self, message: Message) -> Result<(), ExecuteError> {
+        let mut messages = self.messages.lock().unwrap();
+
+        if let Some(capacity) = self.capacity {
+            let is_full = ||self.jobs.load(Ordering::Relaxed) >= capacity;
+
+            match self.policy {
+                QueuePolicy::Block => {
+                    while is_full() {
+                        messages = self.space.wait(messages).unwrap();
+                    }
+                },
+                QueuePolicy::Reject if is_full() => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Err(ExecuteError {
+                        kind: ExecuteErrorKind::QueueFull
+                    });
+                },
+                QueuePolicy::DropOldest if is_full() => {
+                    if let Some(oldest) = messages.iter().position(|x|x.is_continue()) {
+                        messages.remove(oldest);
+                        self.jobs.fetch_sub(1, Ordering::Relaxed);
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                },
+                _ => (),
+            }
+        }
+
+        if message.is_continue() {
+            let depth = self.jobs.fetch_add(1, Ordering::Relaxed) + 1;
+            self.peak.fetch_max(depth, Ordering::Relaxed);
+        }
+
+        messages.push_back(message);
+
+        self.available.notify_one();
+        Ok(())
+    }
+
+    /// Takes the next message, waiting for one to be queued.
+    fn pop(&self) -> Message {
+        let mut messages = self.messages.lock().unwrap();
+
+        loop {
+            match messages.pop_front() {
+                Some(message) => {
+                    if message.is_continue() {
+                        self.jobs.fetch_sub(1, Ordering::Relaxed);
+                    }
+
+                    self.space.notify_one();
+                    return message;
+                },
+                None => messages = self.available.wait(messages).unwrap(),
+            }
+        }
+    }
+}
+
+impl Scheduler {
+    fn insert(&self, at: Instant, task: Task) {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id;
+
+        state.next_id += 1;
+        state.tasks.insert((at, id), task);
+        self.wake.notify_all();
+    }
+
+    /// Queues each task as its time comes, until stopped.
+    fn run(&self, queue: &Shared) {
+        loop {
+            let mut state = self.state.lock().unwrap();
+
+            let due = loop {
+                if state.stopped {
+                    return;
+                }
+
+                let now = Instant::now();
+                state = match state.tasks.first_key_value() {
+                    Some((&(at, _), _)) if at <= now => break state.tasks.pop_first(),
+                    Some((&(at, _), _)) => self.wake.wait_timeout(state, at - now).unwrap().0,
+                    None => self.wake.wait(state).unwrap(),
+                };
+            };
+
+            // Released before queueing, which may block on a full queue.
+            drop(state);
+
+            match due {
+                Some((_, Task::Once(token, f))) if !token.is_cancelled() => {
+                    let _ = queue.push(Message::Continue(f));
+                },
+                Some(((at, _), Task::Every(token, period, f))) if !token.is_cancelled() => {
+                    let run = Arc::clone(&f);
+                    let _ = queue.push(Message::Continue(Box::new(move||run())));
+                    self.insert(at + period, Task::Every(token, period, f));
+                },
+                _ => (),
+            }
+        }
+    }
+}
+
+impl CancelToken {
+    /// Cancels the scheduled job,
+    /// stopping it from being queued again.
+    ///
+    /// Runs already queued, or running, aren't affected.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if the job has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
 }
 
 impl Drop for ThreadPool {
     fn drop(&mut self) {
-        println!("Sending shutdown messages.");
+        self.stop_timer();
 
-        for _ in &self.workers {
-            self.pipeline
-                .send(Message::Break(()))
-                .unwrap()
+        if self.workers.is_empty() {
+            return;
         }
 
+        self.send_shutdown();
+
+        // Panicking in drop would abort if already unwinding, so dead workers are skipped over.
         self.workers
             .iter_mut()
-            .filter_map(|x|x.0.take())
-            .for_each(|x|x.join().unwrap());
+            .filter_map(|x|x.thread.take())
+            .for_each(|x|{
+                let _ = x.join();
+            });
     }
 }
 
-struct Worker(Option<thread::JoinHandle<()>>);
+struct Worker {
+    thread: Option<thread::JoinHandle<()>>,
+    counters: Arc<Counters>,
+}
+
+/// A worker's running totals, updated as it finishes each job.
+#[derive(Default)]
+struct Counters {
+    jobs: AtomicUsize,
+    busy_nanos: AtomicU64,
+}
+
+/// A snapshot of a worker's stats, returned by [`ThreadPool::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkerStats {
+    id: usize,
+    jobs: usize,
+    busy: Duration,
+}
 
 impl Worker {
-    fn new(id: usize, inbox: Arc<Mutex<Receiver<Message>>>) -> Self {
-        let thread = thread::spawn(move || loop {    
-            let message = inbox.lock()
-                .unwrap()
-                .recv()
-                .unwrap();
-
-            match message {
-                Message::Continue(job) => {
-                    println!("Worker {} now working on a job.", id);
-                    job();
-                },
-                Message::Break(_) => {
-                    println!("Shutting down worker {}.", id);
-                    break;
+    fn new(id: usize, queue: Arc<Shared>) -> io::Result<Self> {
+        let counters = Arc::new(Counters::default());
+        let thread = Self::spawn(id, queue, Arc::clone(&counters))?;
+
+        Ok(Self {
+            thread: Some(thread),
+            counters,
+        })
+    }
+
+    fn spawn(id: usize, queue: Arc<Shared>, counters: Arc<Counters>) -> io::Result<thread::JoinHandle<()>> {
+        thread::Builder::new()
+            .name(format!("{}-{}", queue.name, id))
+            .spawn(move || loop {
+                match queue.pop() {
+                    Message::Continue(job) => {
+                        queue.events.on_job_start(id);
+                        let start = Instant::now();
+
+                        // Jobs are discarded after running, so any state they broke by panicking goes with them.
+                        let result = panic::catch_unwind(AssertUnwindSafe(job));
+                        let elapsed = start.elapsed();
+
+                        counters.jobs.fetch_add(1, Ordering::Relaxed);
+                        counters.busy_nanos.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+
+                        match result {
+                            Ok(_) => queue.events.on_job_end(id, elapsed, None),
+                            Err(payload) => {
+                                queue.panicked.fetch_add(1, Ordering::Relaxed);
+                                queue.events.on_job_end(id, elapsed, Some(panic_message(&*payload)));
+                            },
+                        }
+                    },
+                    Message::Break(_) => {
+                        queue.events.on_shutdown(id);
+                        break;
+                    }
                 }
-            }
-        });
+            })
+    }
+}
 
-        Self(Some(thread))
+impl WorkerStats {
+    /// Returns the worker's id, its index in the pool.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the number of jobs the worker has run,
+    /// including those which panicked.
+    pub fn jobs(&self) -> usize {
+        self.jobs
+    }
+
+    /// Returns the total time the worker has spent running jobs.
+    pub fn busy(&self) -> Duration {
+        self.busy
+    }
+}
+
+/// Returns the message a panic was raised with,
+/// when it was a string, as it is for `panic!` and friends.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(x), _) => x,
+        (_, Some(x)) => x,
+        _ => "Box<dyn Any>",
+    }
+}
+
+/// A handle to the result of a job queued with [`ThreadPool::submit`].
+#[derive(Debug)]
+pub struct JobHandle<T> {
+    result: Receiver<Result<T, JobError>>,
+}
+
+impl<T> JobHandle<T> {
+    /// Waits for the job to finish, returning its result.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the job panicked,
+    /// or was discarded before it could run.
+    pub fn join(self) -> Result<T, JobError> {
+        self.result
+            .recv()
+            .unwrap_or(Err(JobError {
+                kind: JobErrorKind::Lost
+            }))
+    }
+
+    /// Returns the job's result if it has finished,
+    /// or `None` if it's still queued or running.
+    ///
+    /// The result is only returned once,
+    /// with later calls reporting it as [`JobErrorKind::Lost`].
+    pub fn try_result(&self) -> Option<Result<T, JobError>> {
+        match self.result.try_recv() {
+            Ok(x) => Some(x),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(JobError {
+                kind: JobErrorKind::Lost
+            })),
+        }
     }
 }
 
@@ -111,7 +882,9 @@ pub struct PoolInitialisationError {
 #[non_exhaustive]
 #[derive(Debug, Clone)]
 pub enum PoolInitialisationErrorKind {
-    ZeroThreads
+    ZeroThreads,
+    ZeroCapacity,
+    Spawn(io::ErrorKind),
 }
 
 impl PoolInitialisationError {
@@ -124,7 +897,71 @@ impl fmt::Display for PoolInitialisationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.kind {
             PoolInitialisationErrorKind::ZeroThreads => "pools cannot be initialised with no threads",
-            _ => panic!("unaccounted for error type"),
+            PoolInitialisationErrorKind::ZeroCapacity => "pools cannot be initialised with a queue of no capacity",
+            PoolInitialisationErrorKind::Spawn(kind) => return write!(f, "failed to spawn a worker thread: {}", kind),
+        }.fmt(f)
+    }
+}
+
+impl std::error::Error for PoolInitialisationError {}
+
+/// The error type for [`ThreadPool::execute`].
+#[derive(Debug, Clone)]
+pub struct ExecuteError {
+    kind: ExecuteErrorKind
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecuteErrorKind {
+    QueueFull,
+}
+
+impl ExecuteError {
+    pub fn kind(&self) -> &ExecuteErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ExecuteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ExecuteErrorKind::QueueFull => "the pool's queue is full",
         }.fmt(f)
     }
 }
+
+impl std::error::Error for ExecuteError {}
+
+/// The error type for collecting a job's result, through a [`JobHandle`].
+#[derive(Debug, Clone)]
+pub struct JobError {
+    kind: JobErrorKind
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobErrorKind {
+    /// The job panicked.
+    Panicked,
+    /// The job was discarded before it finished,
+    /// such as by [`QueuePolicy::DropOldest`], or its result was already taken.
+    Lost,
+}
+
+impl JobError {
+    pub fn kind(&self) -> &JobErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            JobErrorKind::Panicked => "the job panicked",
+            JobErrorKind::Lost => "the job's result was lost",
+        }.fmt(f)
+    }
+}
+
+impl std::error::Error for JobError {}
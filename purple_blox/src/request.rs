@@ -0,0 +1,439 @@
+//! Parsing for incoming HTTP/1.1 requests.
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{self, BufRead, Read},
+    net::SocketAddr,
+    str::FromStr,
+};
+
+use crate::url;
+
+/// The method of an HTTP request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Head,
+    Post,
+    Put,
+    Delete,
+    Connect,
+    Options,
+    Trace,
+    Patch,
+}
+
+impl FromStr for Method {
+    type Err = RequestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "GET" => Ok(Method::Get),
+            "HEAD" => Ok(Method::Head),
+            "POST" => Ok(Method::Post),
+            "PUT" => Ok(Method::Put),
+            "DELETE" => Ok(Method::Delete),
+            "CONNECT" => Ok(Method::Connect),
+            "OPTIONS" => Ok(Method::Options),
+            "TRACE" => Ok(Method::Trace),
+            "PATCH" => Ok(Method::Patch),
+            _ => Err(RequestError::new(RequestErrorKind::UnknownMethod)),
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Method::Get => "GET",
+            Method::Head => "HEAD",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+            Method::Connect => "CONNECT",
+            Method::Options => "OPTIONS",
+            Method::Trace => "TRACE",
+            Method::Patch => "PATCH",
+        }.fmt(f)
+    }
+}
+
+/// A parsed HTTP/1.1 request.
+///
+/// Header names are stored lowercased,
+/// so lookups through [`header`] are case-insensitive.
+///
+/// [`header`]: Request::header
+#[derive(Debug, Clone)]
+pub struct Request {
+    method: Method,
+    target: String,
+    version: String,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+    params: HashMap<String, String>,
+    peer_addr: Option<SocketAddr>,
+}
+
+impl Request {
+    /// Reads a request from `reader`,
+    /// consuming lines until the end of the head,
+    /// followed by `Content-Length` bytes of body, when one is specified.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the stream closes before the head is complete,
+    /// the request line or a header is malformed,
+    /// the body is shorter than its `Content-Length`,
+    /// or the request has a `Transfer-Encoding`, which isn't supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::request::{Method, Request};
+    ///
+    /// let raw = b"GET /index.html HTTP/1.1\r\nHost: localhost\r\n\r\n";
+    /// let request = Request::read_from(&mut &raw[..]).unwrap();
+    ///
+    /// assert_eq!(Method::Get, request.method());
+    /// assert_eq!("/index.html", request.target());
+    /// assert_eq!(Some("localhost"), request.header("host"));
+    /// ```
+    pub fn read_from<R: BufRead>(reader: &mut R) -> Result<Self, RequestError> {
+        Self::read_limited(reader, usize::MAX, usize::MAX)
+    }
+
+    /// Reads a request from `reader`, as [`read_from`] does,
+    /// refusing a head longer than `max_header_size` bytes,
+    /// or a body longer than `max_body_size` bytes.
+    ///
+    /// Reading stops as soon as a limit is exceeded,
+    /// leaving an oversized body unread.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] as [`read_from`] does,
+    /// or if the request exceeds a limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::request::{Request, RequestErrorKind};
+    ///
+    /// let raw = "POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello";
+    /// let err = Request::read_limited(&mut raw.as_bytes(), 1024, 4).unwrap_err();
+    ///
+    /// assert_eq!(RequestErrorKind::BodyTooLarge, *err.kind());
+    /// ```
+    ///
+    /// [`read_from`]: Request::read_from
+    pub fn read_limited<R: BufRead>(reader: &mut R, max_header_size: usize, max_body_size: usize) -> Result<Self, RequestError> {
+        let mut remaining = max_header_size;
+        let request_line = read_head_line(reader, &mut remaining)?;
+
+        let mut parts = request_line.split(' ');
+        let (method, target, version) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(method), Some(target), Some(version), None) if !target.is_empty() => (method, target, version),
+            _ => return Err(RequestError::new(RequestErrorKind::MalformedRequestLine)),
+        };
+
+        if !version.starts_with("HTTP/1.") {
+            return Err(RequestError::new(RequestErrorKind::UnsupportedVersion));
+        }
+
+        let mut ret = Self {
+            method: method.parse()?,
+            target: target.to_owned(),
+            version: version.to_owned(),
+            headers: HashMap::new(),
+            body: Vec::new(),
+            params: HashMap::new(),
+            peer_addr: None,
+        };
+
+        // Header lines continue until the blank line ending the head.
+        loop {
+            let line = read_head_line(reader, &mut remaining)?;
+
+            if line.is_empty() {
+                break;
+            }
+
+            match line.split_once(':') {
+                Some((name, value)) if !name.is_empty() && !name.ends_with(char::is_whitespace) => {
+                    ret.headers.entry(name.to_ascii_lowercase())
+                        .and_modify(|x|{
+                            x.push_str(", ");
+                            x.push_str(value.trim());
+                        })
+                        .or_insert_with(||value.trim().to_owned());
+                },
+                _ => return Err(RequestError::new(RequestErrorKind::MalformedHeader)),
+            }
+        }
+
+        // Only `Content-Length` framing is supported, so a body in any other framing
+        // is refused, rather than left on the connection to be read as the next request.
+        match (ret.header("content-length"), ret.header("transfer-encoding")) {
+            (Some(_), Some(_)) => return Err(RequestError::new(RequestErrorKind::ConflictingFraming)),
+            (None, Some(_)) => return Err(RequestError::new(RequestErrorKind::UnsupportedTransferEncoding)),
+            _ => (),
+        }
+
+        let length = match ret.header("content-length") {
+            Some(length) => length.parse::<usize>()
+                .map_err(|_|RequestError::new(RequestErrorKind::InvalidContentLength))?,
+            None => 0,
+        };
+
+        if length > max_body_size {
+            return Err(RequestError::new(RequestErrorKind::BodyTooLarge));
+        }
+
+        // The body grows as it's read, so a `Content-Length` larger than what's sent
+        // costs no more than what's sent.
+        reader.take(length as u64)
+            .read_to_end(&mut ret.body)
+            .map_err(RequestError::from)?;
+
+        if ret.body.len() < length {
+            return Err(RequestError::new(RequestErrorKind::UnexpectedEof));
+        }
+
+        Ok(ret)
+    }
+
+    /// Returns the method of the request.
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    /// Returns the request target, as it appeared in the request line.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Returns the path of the request target,
+    /// without its query string.
+    pub fn path(&self) -> &str {
+        match self.target.split_once('?') {
+            Some((path, _)) => path,
+            None => &self.target,
+        }
+    }
+
+    /// Returns the parameters of the request target's query string,
+    /// percent-decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::request::Request;
+    ///
+    /// let raw = b"GET /search?q=purple%20blox&page=2 HTTP/1.1\r\n\r\n";
+    /// let query = Request::read_from(&mut &raw[..]).unwrap().query();
+    ///
+    /// assert_eq!("purple blox", query["q"]);
+    /// assert_eq!("2", query["page"]);
+    /// ```
+    pub fn query(&self) -> HashMap<String, String> {
+        match self.target.split_once('?') {
+            Some((_, query)) => url::parse_query(query),
+            None => HashMap::new(),
+        }
+    }
+
+    /// Returns the fields of an `application/x-www-form-urlencoded` body,
+    /// percent-decoded.
+    ///
+    /// Returns an empty map for requests with any other content type.
+    pub fn form(&self) -> HashMap<String, String> {
+        let is_form = self.header("content-type")
+            .and_then(|x|x.split(';').next())
+            .is_some_and(|x|x.trim().eq_ignore_ascii_case("application/x-www-form-urlencoded"));
+
+        match is_form {
+            true => url::parse_query(&String::from_utf8_lossy(&self.body)),
+            false => HashMap::new(),
+        }
+    }
+
+    /// Deserialises the body of the request from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the body isn't valid JSON for `T`,
+    /// which converts into a `400 Bad Request` [`Response`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::request::Request;
+    ///
+    /// let raw = b"POST /scores HTTP/1.1\r\nContent-Length: 7\r\n\r\n[1,2,3]";
+    /// let request = Request::read_from(&mut &raw[..]).unwrap();
+    ///
+    /// assert_eq!(vec![1, 2, 3], request.json::<Vec<u32>>().unwrap());
+    /// ```
+    ///
+    /// [`Response`]: crate::response::Response
+    #[cfg(feature = "serde_json")]
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, RequestError> {
+        serde_json::from_slice(&self.body)
+            .map_err(|_|RequestError::new(RequestErrorKind::InvalidJson))
+    }
+
+    /// Returns the protocol version of the request, such as `HTTP/1.1`.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the value of the named header, if it was sent.
+    ///
+    /// Repeated headers are combined into a single comma separated value.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Returns a referance to all the headers of the request,
+    /// keyed by their lowercased names.
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.headers
+    }
+
+    /// Returns the body of the request.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Returns the value of the named path parameter,
+    /// captured by the [`Router`] route which matched the request.
+    ///
+    /// [`Router`]: crate::router::Router
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .get(name)
+            .map(String::as_str)
+    }
+
+    /// Returns the address of the client which sent the request,
+    /// when it was read from a connection.
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.peer_addr
+    }
+
+    pub(crate) fn set_peer_addr(&mut self, addr: Option<SocketAddr>) {
+        self.peer_addr = addr;
+    }
+
+    pub(crate) fn set_params(&mut self, params: Vec<(String, String)>) {
+        self.params = params.into_iter().collect();
+    }
+}
+
+/// Reads a single CRLF (or bare LF) terminated line of a request head,
+/// taking its length from the `remaining` bytes the head may take up.
+fn read_head_line<R: BufRead>(reader: &mut R, remaining: &mut usize) -> Result<String, RequestError> {
+    let mut buffer = Vec::new();
+    // Reads one byte past the limit, to tell a line which fits exactly from one which doesn't.
+    let limit = remaining.saturating_add(1) as u64;
+
+    match io::Read::take(&mut *reader, limit).read_until(b'\n', &mut buffer)? {
+        0 => Err(RequestError::new(RequestErrorKind::UnexpectedEof)),
+        x if x > *remaining => Err(RequestError::new(RequestErrorKind::HeadersTooLarge)),
+        _ if !buffer.ends_with(b"\n") => Err(RequestError::new(RequestErrorKind::UnexpectedEof)),
+        x => {
+            *remaining -= x;
+            buffer.pop();
+            if buffer.ends_with(b"\r") {
+                buffer.pop();
+            }
+
+            String::from_utf8(buffer)
+                .map_err(|_|RequestError::new(RequestErrorKind::MalformedHeader))
+        },
+    }
+}
+
+/// The error type for reading [`Request`]s.
+#[derive(Debug, Clone)]
+pub struct RequestError {
+    kind: RequestErrorKind
+}
+
+/// A list specifying the categories of [`RequestError`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestErrorKind {
+    /// The stream ended before the request was complete.
+    UnexpectedEof,
+    /// The request line did not have a method, target and version.
+    MalformedRequestLine,
+    /// The request method is not one recognised by [`Method`].
+    UnknownMethod,
+    /// The request was not made over HTTP/1.x.
+    UnsupportedVersion,
+    /// A header line was not a `name: value` pair.
+    MalformedHeader,
+    /// The `Content-Length` header was not a whole number.
+    InvalidContentLength,
+    /// The request had both `Content-Length` and `Transfer-Encoding` headers.
+    ConflictingFraming,
+    /// The request had a `Transfer-Encoding` header, such as `chunked`,
+    /// which isn't supported.
+    UnsupportedTransferEncoding,
+    /// The request line and headers were longer than allowed.
+    HeadersTooLarge,
+    /// The `Content-Length` header gave a body longer than allowed.
+    BodyTooLarge,
+    /// Reading from the stream failed.
+    Io(io::ErrorKind),
+    /// The body could not be deserialised from JSON.
+    #[cfg(feature = "serde_json")]
+    InvalidJson,
+}
+
+impl RequestError {
+    fn new(kind: RequestErrorKind) -> Self {
+        Self {
+            kind
+        }
+    }
+
+    pub fn kind(&self) -> &RequestErrorKind {
+        &self.kind
+    }
+}
+
+impl From<io::Error> for RequestError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::UnexpectedEof => Self::new(RequestErrorKind::UnexpectedEof),
+            kind => Self::new(RequestErrorKind::Io(kind)),
+        }
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            RequestErrorKind::UnexpectedEof => "stream ended before the request was complete",
+            RequestErrorKind::MalformedRequestLine => "malformed request line",
+            RequestErrorKind::UnknownMethod => "unknown request method",
+            RequestErrorKind::UnsupportedVersion => "unsupported protocol version",
+            RequestErrorKind::MalformedHeader => "malformed header",
+            RequestErrorKind::InvalidContentLength => "invalid content length",
+            RequestErrorKind::ConflictingFraming => "both content length and transfer encoding given",
+            RequestErrorKind::UnsupportedTransferEncoding => "unsupported transfer encoding",
+            RequestErrorKind::HeadersTooLarge => "request headers too large",
+            RequestErrorKind::BodyTooLarge => "request body too large",
+            #[cfg(feature = "serde_json")]
+            RequestErrorKind::InvalidJson => "invalid json body",
+            RequestErrorKind::Io(kind) => return write!(f, "request could not be read: {}", kind),
+        }.fmt(f)
+    }
+}
+
+impl std::error::Error for RequestError {}
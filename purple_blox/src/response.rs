@@ -0,0 +1,465 @@
+//! Responses written back to clients.
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    path::Path,
+};
+
+use crate::{
+    request::RequestError,
+    server::ServerError,
+    template::{Context, Template},
+};
+
+/// The most bytes read from a streamed body, to send as a single chunk.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// The status code of an HTTP response.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status {
+    Ok,
+    Created,
+    NoContent,
+    PartialContent,
+    MovedPermanently,
+    Found,
+    NotModified,
+    BadRequest,
+    Forbidden,
+    NotFound,
+    MethodNotAllowed,
+    RequestTimeout,
+    PayloadTooLarge,
+    RangeNotSatisfiable,
+    TooManyRequests,
+    RequestHeaderFieldsTooLarge,
+    InternalServerError,
+    NotImplemented,
+    ServiceUnavailable,
+}
+
+const STATUSES: [Status; 19] = [
+    Status::Ok,
+    Status::Created,
+    Status::NoContent,
+    Status::PartialContent,
+    Status::MovedPermanently,
+    Status::Found,
+    Status::NotModified,
+    Status::BadRequest,
+    Status::Forbidden,
+    Status::NotFound,
+    Status::MethodNotAllowed,
+    Status::RequestTimeout,
+    Status::PayloadTooLarge,
+    Status::RangeNotSatisfiable,
+    Status::TooManyRequests,
+    Status::RequestHeaderFieldsTooLarge,
+    Status::InternalServerError,
+    Status::NotImplemented,
+    Status::ServiceUnavailable,
+];
+
+impl Status {
+    /// Returns the numeric code of the status.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::response::Status;
+    ///
+    /// assert_eq!(404, Status::NotFound.code());
+    /// ```
+    pub fn code(self) -> u16 {
+        match self {
+            Status::Ok => 200,
+            Status::Created => 201,
+            Status::NoContent => 204,
+            Status::PartialContent => 206,
+            Status::MovedPermanently => 301,
+            Status::Found => 302,
+            Status::NotModified => 304,
+            Status::BadRequest => 400,
+            Status::Forbidden => 403,
+            Status::NotFound => 404,
+            Status::MethodNotAllowed => 405,
+            Status::RequestTimeout => 408,
+            Status::PayloadTooLarge => 413,
+            Status::RangeNotSatisfiable => 416,
+            Status::TooManyRequests => 429,
+            Status::RequestHeaderFieldsTooLarge => 431,
+            Status::InternalServerError => 500,
+            Status::NotImplemented => 501,
+            Status::ServiceUnavailable => 503,
+        }
+    }
+
+    /// Returns the status with the given numeric code,
+    /// if it's one this enum has a variant for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::response::Status;
+    ///
+    /// assert_eq!(Some(Status::NotFound), Status::from_code(404));
+    /// assert_eq!(None, Status::from_code(418));
+    /// ```
+    pub fn from_code(code: u16) -> Option<Self> {
+        STATUSES.iter()
+            .copied()
+            .find(|x|x.code() == code)
+    }
+
+    /// Returns the canonical reason phrase of the status.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::response::Status;
+    ///
+    /// assert_eq!("Not Found", Status::NotFound.reason());
+    /// ```
+    pub fn reason(self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::Created => "Created",
+            Status::NoContent => "No Content",
+            Status::PartialContent => "Partial Content",
+            Status::MovedPermanently => "Moved Permanently",
+            Status::Found => "Found",
+            Status::NotModified => "Not Modified",
+            Status::BadRequest => "Bad Request",
+            Status::Forbidden => "Forbidden",
+            Status::NotFound => "Not Found",
+            Status::MethodNotAllowed => "Method Not Allowed",
+            Status::RequestTimeout => "Request Timeout",
+            Status::PayloadTooLarge => "Payload Too Large",
+            Status::RangeNotSatisfiable => "Range Not Satisfiable",
+            Status::TooManyRequests => "Too Many Requests",
+            Status::RequestHeaderFieldsTooLarge => "Request Header Fields Too Large",
+            Status::InternalServerError => "Internal Server Error",
+            Status::NotImplemented => "Not Implemented",
+            Status::ServiceUnavailable => "Service Unavailable",
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.reason())
+    }
+}
+
+/// A response to a [`Request`], produced by a handler.
+///
+/// Responses are built up from their status,
+/// with headers and a body added by chaining methods.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::response::{Response, Status};
+///
+/// let response = Response::ok()
+///     .header("Content-Type", "text/html")
+///     .body("<h1>Hello</h1>");
+///
+/// assert_eq!(Status::Ok, response.status());
+/// assert_eq!(Some("text/html"), response.get_header("content-type"));
+/// assert_eq!(b"<h1>Hello</h1>", response.contents());
+/// ```
+///
+/// [`Request`]: crate::request::Request
+#[derive(Debug)]
+pub struct Response {
+    status: Status,
+    headers: Vec<(String, String)>,
+    body: Body,
+}
+
+/// The body of a response,
+/// either held in memory, or read as it's written.
+enum Body {
+    Bytes(Vec<u8>),
+    Stream(Box<dyn Read + Send>),
+}
+
+impl fmt::Debug for Body {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Body::Bytes(x) => f.debug_tuple("Bytes").field(x).finish(),
+            Body::Stream(_) => f.write_str("Stream(..)"),
+        }
+    }
+}
+
+impl From<RequestError> for Response {
+    /// Responds to a request which couldn't be read or interpreted,
+    /// with the status of the [`ServerError`] it converts to,
+    /// such that both conversions agree.
+    fn from(err: RequestError) -> Self {
+        ServerError::from(err).into()
+    }
+}
+
+impl Response {
+    /// Creates a `Response` with the given status,
+    /// no headers, and an empty body.
+    pub fn new(status: Status) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Body::Bytes(Vec::new()),
+        }
+    }
+
+    /// Creates a `200 OK` response.
+    pub fn ok() -> Self {
+        Self::new(Status::Ok)
+    }
+
+    /// Creates a `400 Bad Request` response.
+    pub fn bad_request() -> Self {
+        Self::new(Status::BadRequest)
+    }
+
+    /// Creates a `403 Forbidden` response.
+    pub fn forbidden() -> Self {
+        Self::new(Status::Forbidden)
+    }
+
+    /// Creates a `404 Not Found` response.
+    pub fn not_found() -> Self {
+        Self::new(Status::NotFound)
+    }
+
+    /// Creates a `500 Internal Server Error` response.
+    pub fn internal_server_error() -> Self {
+        Self::new(Status::InternalServerError)
+    }
+
+    /// Creates a `200 OK` response with `value` serialised as its JSON body.
+    ///
+    /// Responds with an empty `500 Internal Server Error` instead,
+    /// if `value` can't be serialised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use purple_blox::response::Response;
+    ///
+    /// let response = Response::json(&[1, 2, 3]);
+    ///
+    /// assert_eq!(Some("application/json"), response.get_header("content-type"));
+    /// assert_eq!(b"[1,2,3]", response.contents());
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn json<T: serde::Serialize + ?Sized>(value: &T) -> Self {
+        match serde_json::to_vec(value) {
+            Ok(body) => Self::ok()
+                .header("Content-Type", "application/json")
+                .body(body),
+            Err(_) => Self::internal_server_error(),
+        }
+    }
+
+    /// Creates a `200 OK` response with the template at `path`,
+    /// rendered with `ctx`, as its HTML body.
+    ///
+    /// Responds with an empty `500 Internal Server Error` instead,
+    /// if the template can't be rendered.
+    ///
+    /// See [`template`](crate::template) for the template syntax.
+    pub fn template(path: impl AsRef<Path>, ctx: &Context) -> Self {
+        match Template::render(path, ctx) {
+            Ok(body) => Self::ok()
+                .header("Content-Type", "text/html; charset=utf-8")
+                .body(body),
+            Err(_) => Self::internal_server_error(),
+        }
+    }
+
+    /// Sets the named header to `value`, returning the response.
+    ///
+    /// Header names are case-insensitive,
+    /// so setting a header again replaces its previous value.
+    #[must_use]
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.set_header(name, value);
+        self
+    }
+
+    /// Sets the body of the response, returning the response.
+    #[must_use]
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = Body::Bytes(body.into());
+        self
+    }
+
+    /// Sets the body of the response to the contents of `reader`,
+    /// read as the response is written, returning the response.
+    ///
+    /// The body is sent with `Transfer-Encoding: chunked`,
+    /// unless a `Content-Length` header is set,
+    /// in which case that many bytes are sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Read;
+    /// use purple_blox::response::Response;
+    ///
+    /// let numbers = (0..1000).flat_map(|x|format!("{}\n", x).into_bytes()).collect::<Vec<_>>();
+    /// let response = Response::ok().stream(std::io::Cursor::new(numbers));
+    ///
+    /// assert!(response.is_streaming());
+    /// assert!(response.contents().is_empty());
+    /// ```
+    #[must_use]
+    pub fn stream(mut self, reader: impl Read + Send + 'static) -> Self {
+        self.body = Body::Stream(Box::new(reader));
+        self
+    }
+
+    /// Returns the status of the response.
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Changes the status of the response.
+    pub fn set_status(&mut self, status: Status) {
+        self.status = status;
+    }
+
+    /// Sets the named header to `value`,
+    /// replacing any value it was previously set to.
+    pub fn set_header(&mut self, name: &str, value: impl Into<String>) {
+        let value = value.into();
+
+        match self.headers.iter_mut().find(|(x, _)|x.eq_ignore_ascii_case(name)) {
+            Some((_, x)) => *x = value,
+            None => self.headers.push((name.to_owned(), value)),
+        }
+    }
+
+    /// Returns the value of the named header, if it has been set.
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(x, _)|x.eq_ignore_ascii_case(name))
+            .map(|(_, x)|x.as_str())
+    }
+
+    /// Returns the headers set on the response,
+    /// in the order they were first set.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Returns the body of the response,
+    /// which is empty for streamed responses.
+    pub fn contents(&self) -> &[u8] {
+        match &self.body {
+            Body::Bytes(x) => x,
+            Body::Stream(_) => &[],
+        }
+    }
+
+    /// Returns `true` if the body is streamed from a reader,
+    /// set with [`stream`].
+    ///
+    /// [`stream`]: Response::stream
+    pub fn is_streaming(&self) -> bool {
+        matches!(self.body, Body::Stream(_))
+    }
+
+    /// Returns `true` if the end of the body can only be signalled
+    /// by chunking it, or closing the connection.
+    pub(crate) fn is_unbounded(&self) -> bool {
+        self.is_streaming() && self.get_header("Content-Length").is_none()
+    }
+
+    /// Writes the response to `writer`, flushing it afterwards.
+    ///
+    /// Any `Content-Length` or `Transfer-Encoding` headers set are replaced by the body's framing,
+    /// except the `Content-Length` of a stream.
+    /// When `include_body` is `false`, as for responses to `HEAD` requests,
+    /// only the head is written, with the `Content-Length` the body would have had.
+    /// Unbounded streams are chunked when `chunked` is `true`,
+    /// and otherwise written until they end,
+    /// leaving the connection to be closed after.
+    pub(crate) fn write_to<W: Write>(&mut self, writer: W, include_body: bool, chunked: bool) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(writer);
+
+        write!(writer, "HTTP/1.1 {}\r\n", self.status)?;
+
+        // The framing of the body is written below, so any set by a handler can't contradict it,
+        // other than the `Content-Length` of a stream, which is how its length is given.
+        let streaming = self.is_streaming();
+        let framing = |name: &str|name.eq_ignore_ascii_case("Transfer-Encoding")
+            || (!streaming && name.eq_ignore_ascii_case("Content-Length"));
+
+        for (name, value) in self.headers.iter().filter(|(x, _)|!framing(x)) {
+            write!(writer, "{}: {}\r\n", name, value)?;
+        }
+
+        let unbounded = self.is_unbounded();
+        // These statuses never have a body, so don't describe one.
+        let bodiless = matches!(self.status, Status::NoContent | Status::NotModified);
+
+        match &mut self.body {
+            Body::Bytes(_) if bodiless => write!(writer, "\r\n")?,
+            Body::Bytes(x) => write!(writer, "Content-Length: {}\r\n\r\n", x.len())?,
+            Body::Stream(_) if unbounded && chunked => write!(writer, "Transfer-Encoding: chunked\r\n\r\n")?,
+            Body::Stream(_) => write!(writer, "\r\n")?,
+        }
+
+        if include_body && !bodiless {
+            match &mut self.body {
+                Body::Bytes(x) => writer.write_all(x)?,
+                Body::Stream(x) if unbounded && chunked => write_chunked(x, &mut writer)?,
+                Body::Stream(x) if unbounded => {
+                    io::copy(x, &mut writer)?;
+                },
+                Body::Stream(x) => {
+                    let length = self.headers
+                        .iter()
+                        .find(|(x, _)|x.eq_ignore_ascii_case("Content-Length"))
+                        .and_then(|(_, x)|x.trim().parse().ok())
+                        .unwrap_or(0);
+
+                    // A stream ending early would leave the client waiting for the rest.
+                    match io::copy(&mut x.take(length), &mut writer)? == length {
+                        true => (),
+                        false => return Err(io::ErrorKind::UnexpectedEof.into()),
+                    }
+                },
+            }
+        }
+
+        writer.flush()
+    }
+}
+
+/// Writes the contents of `reader` to `writer` as chunks,
+/// followed by the terminating empty chunk.
+fn write_chunked<R: Read + ?Sized, W: Write>(reader: &mut R, writer: &mut W) -> io::Result<()> {
+    let mut buffer = [0; CHUNK_SIZE];
+
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        write!(writer, "{:X}\r\n", read)?;
+        writer.write_all(&buffer[..read])?;
+        write!(writer, "\r\n")?;
+    }
+
+    write!(writer, "0\r\n\r\n")
+}
@@ -0,0 +1,267 @@
+//! Dispatching of requests to handlers,
+//! by their method and path.
+use crate::{
+    middleware::{Middleware, Next},
+    request::{Method, Request},
+    response::{Response, Status},
+};
+
+/// A boxed request handler, as stored by a [`Router`].
+pub type Handler = Box<dyn Fn(&Request) -> Response + Send + Sync + 'static>;
+
+/// A table of handlers, registered by method and path pattern.
+///
+/// Path patterns are made of `/` separated segments,
+/// where segments beginning with `:` match any value,
+/// making it available through [`Request::param`].
+///
+/// Requests which don't match any route are passed to the fallback handler,
+/// which responds with an empty `404` unless one is set with [`fallback`].
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::{
+///     request::Request,
+///     response::Response,
+///     router::Router,
+/// };
+///
+/// let mut router = Router::new();
+/// router.get("/", |_: &Request|Response::ok().body("Home"))
+///     .get("/posts/:id", |req: &Request|Response::ok().body(req.param("id").unwrap()));
+///
+/// let request = Request::read_from(&mut &b"GET /posts/42 HTTP/1.1\r\n\r\n"[..]).unwrap();
+/// assert_eq!(b"42", router.handle(request).contents());
+/// ```
+///
+/// [`fallback`]: Router::fallback
+pub struct Router {
+    routes: Vec<Route>,
+    fallback: Handler,
+    middleware: Vec<Middleware>,
+}
+
+struct Route {
+    method: Method,
+    pattern: Vec<Segment>,
+    handler: Handler,
+}
+
+enum Segment {
+    Literal(String),
+    Param(String),
+}
+
+impl Router {
+    /// Creates an empty `Router`,
+    /// which responds to every request with a `404`.
+    pub fn new() -> Self {
+        Self {
+            routes: Vec::new(),
+            fallback: Box::new(|_|Response::not_found()),
+            middleware: Vec::new(),
+        }
+    }
+
+    /// Registers `handler` for requests with the given method,
+    /// whose path matches `pattern`.
+    ///
+    /// Routes are matched in the order they are registered.
+    pub fn route<F>(&mut self, method: Method, pattern: &str, handler: F) -> &mut Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static, {
+            let pattern = segments(pattern)
+                .map(|x|match x.strip_prefix(':') {
+                    Some(name) => Segment::Param(name.to_owned()),
+                    None => Segment::Literal(x.to_owned()),
+                })
+                .collect();
+
+            self.routes.push(Route {
+                method,
+                pattern,
+                handler: Box::new(handler),
+            });
+            self
+        }
+
+    /// Registers `handler` for `GET` requests matching `pattern`.
+    pub fn get<F>(&mut self, pattern: &str, handler: F) -> &mut Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static, {
+            self.route(Method::Get, pattern, handler)
+        }
+
+    /// Registers `handler` for `POST` requests matching `pattern`.
+    pub fn post<F>(&mut self, pattern: &str, handler: F) -> &mut Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static, {
+            self.route(Method::Post, pattern, handler)
+        }
+
+    /// Registers `handler` for `PUT` requests matching `pattern`.
+    pub fn put<F>(&mut self, pattern: &str, handler: F) -> &mut Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static, {
+            self.route(Method::Put, pattern, handler)
+        }
+
+    /// Registers `handler` for `DELETE` requests matching `pattern`.
+    pub fn delete<F>(&mut self, pattern: &str, handler: F) -> &mut Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static, {
+            self.route(Method::Delete, pattern, handler)
+        }
+
+    /// Sets the handler for requests which don't match any route.
+    pub fn fallback<F>(&mut self, handler: F) -> &mut Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static, {
+            self.fallback = Box::new(handler);
+            self
+        }
+
+    /// Adds a middleware layer, wrapping every request the router handles.
+    ///
+    /// Layers run in the order they are added,
+    /// each deciding whether, and with what request,
+    /// to call the rest of the chain through [`Next`].
+    pub fn middleware<F>(&mut self, layer: F) -> &mut Self
+    where
+        F: Fn(Request, Next<'_>) -> Response + Send + Sync + 'static, {
+            self.middleware.push(Box::new(layer));
+            self
+        }
+
+    /// Passes `request` through the router's middleware,
+    /// then to the first matching route's handler,
+    /// or the fallback handler if none match,
+    /// returning the resulting response.
+    pub fn handle(&self, request: Request) -> Response {
+        Next::new(&self.middleware, self).run(request)
+    }
+
+    /// Passes `request` to the first matching route's handler,
+    /// or the fallback handler if none match.
+    ///
+    /// `HEAD` requests without a route of their own are handled by the `GET` route,
+    /// and `OPTIONS` requests by listing the path's methods.
+    /// Paths with routes, but none for the request method, are answered with a `405`.
+    pub(crate) fn dispatch(&self, mut request: Request) -> Response {
+        let method = request.method();
+        let matching: Vec<_> = self.routes
+            .iter()
+            .filter_map(|x|x.matches(request.path()).map(|params|(x, params)))
+            .collect();
+
+        let position = matching.iter()
+            .position(|(x, _)|x.method == method)
+            .or_else(|| match method {
+                Method::Head => matching.iter().position(|(x, _)|x.method == Method::Get),
+                _ => None,
+            });
+
+        if let Some(position) = position {
+            let (route, params) = matching.into_iter()
+                .nth(position)
+                .unwrap();
+
+            request.set_params(params);
+            return (route.handler)(&request);
+        }
+
+        let allowed: Vec<_> = matching.iter()
+            .map(|(x, _)|x.method)
+            .collect();
+
+        match (method, allowed.is_empty()) {
+            (Method::Options, _) if request.target() == "*" => {
+                let allowed = self.routes.iter().map(|x|x.method).collect();
+                Response::new(Status::NoContent).header("Allow", allow_header(allowed))
+            },
+            (_, true) => (self.fallback)(&request),
+            (Method::Options, false) => Response::new(Status::NoContent).header("Allow", allow_header(allowed)),
+            (_, false) => Response::new(Status::MethodNotAllowed).header("Allow", allow_header(allowed)),
+        }
+    }
+
+    /// Returns the methods with routes matching `path`,
+    /// including the methods the router answers implicitly.
+    ///
+    /// An empty list means no route matches the path.
+    pub fn allowed_methods(&self, path: &str) -> Vec<Method> {
+        let methods = self.routes
+            .iter()
+            .filter(|x|x.matches(path).is_some())
+            .map(|x|x.method)
+            .collect();
+
+        implicit_methods(methods)
+    }
+}
+
+impl Route {
+    /// Returns the parameters captured from `path`,
+    /// if it matches the route's pattern.
+    fn matches(&self, path: &str) -> Option<Vec<(String, String)>> {
+        let mut params = Vec::new();
+        let mut path = segments(path);
+
+        for segment in &self.pattern {
+            match (segment, path.next()) {
+                (Segment::Literal(x), Some(y)) if x == y => (),
+                (Segment::Param(name), Some(y)) => params.push((name.clone(), y.to_owned())),
+                _ => return None,
+            }
+        }
+
+        match path.next() {
+            None => Some(params),
+            Some(_) => None,
+        }
+    }
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Adds the methods answered implicitly for a set of routes,
+/// removing duplicates while preserving their order.
+fn implicit_methods(methods: Vec<Method>) -> Vec<Method> {
+    if methods.is_empty() {
+        return methods;
+    }
+
+    let implicit = match methods.contains(&Method::Get) {
+        true => vec![Method::Head, Method::Options],
+        false => vec![Method::Options],
+    };
+
+    methods.into_iter()
+        .chain(implicit)
+        .fold(Vec::new(), |mut acc, x|{
+            if !acc.contains(&x) {
+                acc.push(x);
+            }
+            acc
+        })
+}
+
+/// Formats a list of methods as the value of an `Allow` header.
+fn allow_header(methods: Vec<Method>) -> String {
+    implicit_methods(methods)
+        .iter()
+        .map(Method::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Splits a path into its non-empty segments.
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter(|x|!x.is_empty())
+}
@@ -0,0 +1,391 @@
+//! The server accept loop, and its shutdown.
+use std::{
+    collections::HashMap,
+    fmt,
+    io,
+    net::{self, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crate::{
+    config::ServerConfig,
+    pool::{PoolInitialisationErrorKind, QueuePolicy, ThreadPool},
+    request::{RequestError, RequestErrorKind},
+    response::{Response, Status},
+    router::Router,
+};
+
+/// A server, accepting connections from a listener,
+/// and handling them with a router.
+///
+/// Further sites can be served from the same listener with [`add_site`],
+/// chosen by the `Host` each request is sent to,
+/// with the server's own router serving any other host.
+///
+/// [`run`] blocks until the server is shut down,
+/// through [`shutdown`] or a [`ShutdownHandle`],
+/// after which no more connections are accepted,
+/// and in-flight connections are given until the configured timeout to finish.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::net::TcpListener;
+/// use purple_blox::{config::ServerConfig, server::Server};
+///
+/// let config = ServerConfig::default();
+/// let listener = TcpListener::bind(config.addr()).unwrap();
+/// let server = Server::new(listener, purple_blox::site_router(&config), config).unwrap();
+///
+/// server.shutdown_on_ctrl_c().unwrap();
+/// server.run().unwrap();
+/// ```
+///
+/// [`run`]: Server::run
+/// [`shutdown`]: Server::shutdown
+/// [`add_site`]: Server::add_site
+pub struct Server {
+    listener: net::TcpListener,
+    sites: Arc<Sites>,
+    config: Arc<ServerConfig>,
+    handle: ShutdownHandle,
+}
+
+impl Server {
+    /// Creates a server, which will serve connections from `listener`.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the listener's local address can't be read.
+    pub fn new(listener: net::TcpListener, router: Router, config: ServerConfig) -> io::Result<Self> {
+        let handle = ShutdownHandle {
+            flag: Arc::new(AtomicBool::new(false)),
+            addr: loopback(listener.local_addr()?),
+        };
+
+        Ok(Self {
+            listener,
+            sites: Arc::new(Sites::new(router)),
+            config: Arc::new(config),
+            handle,
+        })
+    }
+
+    /// Serves the files in `dir` to requests for `host`,
+    /// as [`site_router`] would, using the server's configured pages.
+    ///
+    /// Hosts are matched ignoring case and port,
+    /// replacing any site previously added for the same host.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::net::TcpListener;
+    /// use purple_blox::{config::ServerConfig, server::Server};
+    ///
+    /// let config = ServerConfig::default();
+    /// let listener = TcpListener::bind(config.addr()).unwrap();
+    /// let mut server = Server::new(listener, purple_blox::site_router(&config), config).unwrap();
+    ///
+    /// server.add_site("blog.local", "sites/blog")
+    ///     .add_site("wiki.local", "sites/wiki");
+    ///
+    /// server.run().unwrap();
+    /// ```
+    ///
+    /// [`site_router`]: crate::site_router
+    pub fn add_site(&mut self, host: &str, dir: impl Into<PathBuf>) -> &mut Self {
+        let config = ServerConfig {
+            site_dir: dir.into(),
+            ..(*self.config).clone()
+        };
+
+        self.add_router(host, crate::site_router(&config))
+    }
+
+    /// Handles requests for `host` with `router`.
+    ///
+    /// Hosts are matched as they are by [`add_site`].
+    ///
+    /// [`add_site`]: Server::add_site
+    pub fn add_router(&mut self, host: &str, router: Router) -> &mut Self {
+        Arc::make_mut(&mut self.sites)
+            .hosts
+            .insert(hostname(host), Arc::new(router));
+        self
+    }
+
+    /// Returns the address the server is listening on.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves connections until the server is shut down.
+    ///
+    /// Connections arriving while the configured queue is full
+    /// are answered with a `503 Service Unavailable`.
+    ///
+    /// Once shut down, waits for in-flight connections to finish,
+    /// up to the configured `shutdown_timeout`.
+    ///
+    /// Errors on individual connections, such as a client closing the socket mid-read,
+    /// only end that connection.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the server's worker threads couldn't be started.
+    pub fn run(&self) -> Result<(), ServerError> {
+        let connections = self.listener
+            .incoming()
+            .take_while(|_|!self.handle.is_shutdown())
+            .filter_map(Result::ok);
+
+        let pool = match self.config.queue_capacity {
+            0 => ThreadPool::new(self.config.threads),
+            capacity => ThreadPool::bounded(self.config.threads, capacity, QueuePolicy::Reject),
+        };
+
+        match pool {
+            Ok(pool) => {
+                connections.for_each(|x|{
+                    let overflow = x.try_clone();
+                    let sites = Arc::clone(&self.sites);
+                    let config = Arc::clone(&self.config);
+                    let handle = self.handle.clone();
+
+                    if pool.execute(move||report(crate::handle_connection(x, &sites, &config, &handle))).is_err() {
+                        if let Ok(stream) = overflow {
+                            let _ = Response::new(Status::ServiceUnavailable)
+                                .header("Retry-After", "1")
+                                .header("Connection", "close")
+                                .write_to(&stream, true, true);
+                        }
+                    }
+                });
+
+                if !pool.shutdown_timeout(self.config.shutdown_timeout) {
+                    eprintln!("shutdown timed out, abandoning in-flight connections");
+                }
+            },
+            Err(err) => match err.kind() {
+                PoolInitialisationErrorKind::Spawn(kind) => return Err(ServerError::new(ServerErrorKind::Io(*kind))),
+                _ => connections.for_each(|x|report(crate::handle_connection(x, &self.sites, &self.config, &self.handle))),
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Stops the server accepting connections,
+    /// causing [`run`] to return once in-flight connections finish.
+    ///
+    /// [`run`]: Server::run
+    pub fn shutdown(&self) {
+        self.handle.shutdown();
+    }
+
+    /// Returns a handle which can shut the server down from another thread.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.handle.clone()
+    }
+
+    /// Shuts the server down when the process receives Ctrl-C.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if a Ctrl-C handler has already been set for the process.
+    pub fn shutdown_on_ctrl_c(&self) -> Result<(), ctrlc::Error> {
+        let handle = self.shutdown_handle();
+
+        ctrlc::set_handler(move||{
+            println!("Received Ctrl-C, shutting down.");
+            handle.shutdown();
+        })
+    }
+}
+
+/// The routers a server chooses between by each request's host.
+#[derive(Clone)]
+pub(crate) struct Sites {
+    default: Arc<Router>,
+    hosts: HashMap<String, Arc<Router>>,
+}
+
+impl Sites {
+    pub(crate) fn new(default: Router) -> Self {
+        Self {
+            default: Arc::new(default),
+            hosts: HashMap::new(),
+        }
+    }
+
+    /// Returns the router for `host`,
+    /// or the default router if there isn't one, or no host was given.
+    pub(crate) fn route(&self, host: Option<&str>) -> &Router {
+        host.and_then(|x|self.hosts.get(&hostname(x)))
+            .unwrap_or(&self.default)
+    }
+}
+
+/// Normalises a `Host`, lowercasing it and removing any port.
+fn hostname(host: &str) -> String {
+    let name = match host.trim().strip_prefix('[') {
+        // IPv6 literals are bracketed, as they contain colons.
+        Some(x) => x.split_once(']').map_or(x, |(x, _)|x),
+        None => host.trim().split(':').next().unwrap_or_default(),
+    };
+
+    name.to_ascii_lowercase()
+}
+
+/// A cloneable handle for shutting down a [`Server`].
+#[derive(Debug, Clone)]
+pub struct ShutdownHandle {
+    flag: Arc<AtomicBool>,
+    addr: SocketAddr,
+}
+
+impl ShutdownHandle {
+    /// Stops the server accepting connections.
+    pub fn shutdown(&self) {
+        if !self.flag.swap(true, Ordering::SeqCst) {
+            // Wakes the accept loop, which is blocked waiting for a connection.
+            let _ = net::TcpStream::connect(self.addr);
+        }
+    }
+
+    /// Returns `true` if the server has been shut down.
+    pub fn is_shutdown(&self) -> bool {
+        self.flag.load(Ordering::SeqCst)
+    }
+}
+
+/// Logs a connection's error,
+/// unless it was the client disconnecting.
+pub(crate) fn report(result: Result<(), ServerError>) {
+    if let Err(err) = result {
+        if !err.is_disconnect() {
+            eprintln!("Connection failed: {}", err);
+        }
+    }
+}
+
+/// Maps unspecified addresses, such as `0.0.0.0`,
+/// to the loopback address, so they can be connected to.
+fn loopback(addr: SocketAddr) -> SocketAddr {
+    match addr.ip() {
+        IpAddr::V4(ip) if ip.is_unspecified() => SocketAddr::new(Ipv4Addr::LOCALHOST.into(), addr.port()),
+        IpAddr::V6(ip) if ip.is_unspecified() => SocketAddr::new(Ipv6Addr::LOCALHOST.into(), addr.port()),
+        _ => addr,
+    }
+}
+
+/// The error type for serving connections,
+/// each kind mapping to the status a client is answered with.
+#[derive(Debug, Clone)]
+pub struct ServerError {
+    kind: ServerErrorKind
+}
+
+/// A list specifying the categories of [`ServerError`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerErrorKind {
+    /// Reading from, or writing to, the connection failed.
+    Io(io::ErrorKind),
+    /// The request couldn't be read or interpreted.
+    BadRequest,
+    /// The request's line and headers were longer than allowed.
+    HeadersTooLarge,
+    /// The request's body was longer than allowed.
+    BodyTooLarge,
+    /// Nothing was found to serve the request.
+    NotFound,
+    /// The request used a feature of HTTP the server doesn't support.
+    NotImplemented,
+    /// The server failed while handling the request.
+    Internal,
+}
+
+impl ServerError {
+    pub fn new(kind: ServerErrorKind) -> Self {
+        Self {
+            kind
+        }
+    }
+
+    pub fn kind(&self) -> &ServerErrorKind {
+        &self.kind
+    }
+
+    /// Returns the status a client is answered with for this error,
+    /// `408 Request Timeout` for a read which timed out.
+    pub fn status(&self) -> Status {
+        match self.kind {
+            ServerErrorKind::Io(io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => Status::RequestTimeout,
+            ServerErrorKind::Io(_) | ServerErrorKind::Internal => Status::InternalServerError,
+            ServerErrorKind::BadRequest => Status::BadRequest,
+            ServerErrorKind::HeadersTooLarge => Status::RequestHeaderFieldsTooLarge,
+            ServerErrorKind::BodyTooLarge => Status::PayloadTooLarge,
+            ServerErrorKind::NotFound => Status::NotFound,
+            ServerErrorKind::NotImplemented => Status::NotImplemented,
+        }
+    }
+
+    /// Returns `true` if the error was caused by the client closing the connection.
+    pub fn is_disconnect(&self) -> bool {
+        matches!(self.kind, ServerErrorKind::Io(
+            io::ErrorKind::BrokenPipe
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::UnexpectedEof
+        ))
+    }
+}
+
+impl From<io::Error> for ServerError {
+    fn from(err: io::Error) -> Self {
+        Self::new(ServerErrorKind::Io(err.kind()))
+    }
+}
+
+impl From<RequestError> for ServerError {
+    fn from(err: RequestError) -> Self {
+        match err.kind() {
+            RequestErrorKind::Io(kind) => Self::new(ServerErrorKind::Io(*kind)),
+            RequestErrorKind::HeadersTooLarge => Self::new(ServerErrorKind::HeadersTooLarge),
+            RequestErrorKind::BodyTooLarge => Self::new(ServerErrorKind::BodyTooLarge),
+            RequestErrorKind::UnsupportedTransferEncoding => Self::new(ServerErrorKind::NotImplemented),
+            _ => Self::new(ServerErrorKind::BadRequest),
+        }
+    }
+}
+
+impl From<ServerError> for Response {
+    /// Responds with the error's [`status`], and an empty body.
+    ///
+    /// [`status`]: ServerError::status
+    fn from(err: ServerError) -> Self {
+        Self::new(err.status())
+    }
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ServerErrorKind::Io(kind) => return write!(f, "connection failed: {}", kind),
+            ServerErrorKind::BadRequest => "bad request",
+            ServerErrorKind::HeadersTooLarge => "request headers too large",
+            ServerErrorKind::BodyTooLarge => "request body too large",
+            ServerErrorKind::NotFound => "not found",
+            ServerErrorKind::NotImplemented => "not implemented",
+            ServerErrorKind::Internal => "internal server error",
+        }.fmt(f)
+    }
+}
+
+impl std::error::Error for ServerError {}
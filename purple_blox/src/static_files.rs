@@ -0,0 +1,356 @@
+//! A handler for serving files from a directory.
+use std::{
+    fs,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    date,
+    request::Request,
+    response::{Response, Status},
+    template::escape_html,
+    url,
+};
+
+const INDEX_FILE: &str = "index.html";
+
+/// The size in bytes above which files are streamed,
+/// rather than read into memory whole.
+pub const STREAM_THRESHOLD: u64 = 1024 * 1024;
+
+/// Serves files from a root directory,
+/// mapping request paths to the files beneath it.
+///
+/// Paths which try to escape the root, through `..` segments,
+/// are rejected with a `403`, and paths without a file with a `404`.
+/// Requests for a directory are served its `index.html`, when it has one,
+/// or a listing of its contents, if listings are enabled with [`with_listing`].
+/// Files larger than [`STREAM_THRESHOLD`] are streamed from disk,
+/// rather than read into memory,
+/// and single byte ranges asked for with a `Range` header
+/// are served as `206 Partial Content`.
+///
+/// Files are served with a weak `ETag`, made from their size and modification time,
+/// and a `Last-Modified` date, with a `304 Not Modified`
+/// answering requests whose `If-None-Match` or `If-Modified-Since` headers
+/// show the client's copy is still current.
+///
+/// # Examples
+///
+/// ```no_run
+/// use purple_blox::{router::Router, static_files::StaticFiles};
+///
+/// let mut router = Router::new();
+/// router.fallback(StaticFiles::serve("purple_blox/site"));
+/// ```
+///
+/// [`with_listing`]: StaticFiles::with_listing
+#[derive(Debug, Clone)]
+pub struct StaticFiles {
+    root: PathBuf,
+    listing: bool,
+}
+
+impl StaticFiles {
+    /// Creates a `StaticFiles` serving files from `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            listing: false,
+        }
+    }
+
+    /// Sets whether directories without an `index.html`
+    /// are served a listing of their contents, rather than a `404`.
+    ///
+    /// Hidden files, whose names start with a `.`, aren't listed.
+    #[must_use]
+    pub fn with_listing(mut self, listing: bool) -> Self {
+        self.listing = listing;
+        self
+    }
+
+    /// Creates a handler serving files from `root`,
+    /// for registering with a [`Router`].
+    ///
+    /// [`Router`]: crate::router::Router
+    pub fn serve(root: impl Into<PathBuf>) -> impl Fn(&Request) -> Response + Send + Sync + 'static {
+        Self::new(root).into_handler()
+    }
+
+    /// Converts the `StaticFiles` into a handler,
+    /// for registering with a [`Router`].
+    ///
+    /// [`Router`]: crate::router::Router
+    pub fn into_handler(self) -> impl Fn(&Request) -> Response + Send + Sync + 'static {
+        move |req|self.respond(req)
+    }
+
+    /// Returns the root directory files are served from.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Responds to `request` with the file its path maps to.
+    pub fn respond(&self, request: &Request) -> Response {
+        let filepath = match self.resolve(request.path()) {
+            Some(filepath) => filepath,
+            None => return Response::forbidden(),
+        };
+
+        let filepath = match filepath.is_dir() {
+            true if self.listing && !filepath.join(INDEX_FILE).is_file() => return listing(&filepath, request.path())
+                .unwrap_or_else(|_|Response::forbidden()),
+            true => filepath.join(INDEX_FILE),
+            false => filepath,
+        };
+
+        match open(&filepath, request) {
+            Ok(response) => response.header("Content-Type", mime_type(&filepath)),
+            Err(err) if err.kind() == io::ErrorKind::PermissionDenied => Response::forbidden(),
+            Err(_) => Response::not_found(),
+        }
+    }
+
+    /// Maps a request path onto the root directory,
+    /// percent-decoding each of its segments,
+    /// returning `None` if it would escape the root.
+    fn resolve(&self, path: &str) -> Option<PathBuf> {
+        // Segments are decoded after splitting, so an escaped `/` can't add a segment.
+        path.split('/')
+            .map(url::percent_decode_path)
+            .filter(|x|!x.is_empty() && x != ".")
+            .try_fold(self.root.clone(), |acc, x|match x.as_str() {
+                ".." => None,
+                x if x.contains(['/', '\\', '\0']) => None,
+                x => Some(acc.join(x)),
+            })
+    }
+}
+
+/// Lists the contents of the directory at `dirpath`, requested as `path`,
+/// as an HTML table of names, sizes and modification times,
+/// with directories first, then sorted by name.
+fn listing(dirpath: &Path, path: &str) -> io::Result<Response> {
+    let base = match path.ends_with('/') {
+        true => path.to_owned(),
+        false => format!("{}/", path),
+    };
+
+    let mut entries = fs::read_dir(dirpath)?
+        .filter_map(Result::ok)
+        .filter_map(|x|Some((x.file_name().into_string().ok()?, x.metadata().ok()?)))
+        .filter(|(name, _)|!name.starts_with('.'))
+        .collect::<Vec<_>>();
+    entries.sort_by(|(a, a_meta), (b, b_meta)|b_meta.is_dir().cmp(&a_meta.is_dir()).then_with(||a.cmp(b)));
+
+    // Links are built from the decoded path, re-encoded, so the shown and linked paths agree.
+    let href = base.split('/')
+        .map(|x|url::percent_encode_path_segment(&url::percent_decode_path(x)))
+        .collect::<Vec<_>>()
+        .join("/");
+    let title = escape_html(&format!("Index of {}", url::percent_decode_path(&base)));
+    let mut html = format!("<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{0}</title></head>\n<body>\n<h1>{0}</h1>\n<table>\n<tr><th>Name</th><th>Size</th><th>Last modified</th></tr>\n", title);
+
+    if base != "/" {
+        html.push_str("<tr><td><a href=\"../\">../</a></td><td>-</td><td>-</td></tr>\n");
+    }
+
+    for (name, meta) in entries {
+        let (suffix, size) = match meta.is_dir() {
+            true => ("/", String::from("-")),
+            false => ("", meta.len().to_string()),
+        };
+        let modified = meta.modified()
+            .map_or_else(|_|String::from("-"), date::format);
+
+        html.push_str(&format!(
+            "<tr><td><a href=\"{0}{1}{2}\">{3}{2}</a></td><td>{4}</td><td>{5}</td></tr>\n",
+            escape_html(&href), url::percent_encode_path_segment(&name), suffix, escape_html(&name), size, modified
+        ));
+    }
+
+    html.push_str("</table>\n</body>\n</html>\n");
+
+    Ok(Response::ok()
+        .header("Content-Type", "text/html; charset=utf-8")
+        .body(html))
+}
+
+/// Opens the file at `filepath` as a response to `request`,
+/// limited to the part of it the `Range` header asks for, if any,
+/// and streaming its contents if they're larger than [`STREAM_THRESHOLD`].
+///
+/// Responds with a `304` if the request's conditional headers
+/// show the client's cached copy is still fresh.
+fn open(filepath: &Path, request: &Request) -> io::Result<Response> {
+    let mut file = fs::File::open(filepath)?;
+    let metadata = file.metadata()?;
+
+    if !metadata.is_file() {
+        return Err(io::ErrorKind::NotFound.into());
+    }
+
+    let size = metadata.len();
+    let modified = metadata.modified().ok();
+    let etag = modified.map(|x|etag(size, x));
+    let last_modified = modified.map(date::format);
+
+    let validators = |response: Response|match (&etag, &last_modified) {
+        (Some(etag), Some(last_modified)) => response.header("ETag", etag.as_str())
+            .header("Last-Modified", last_modified.as_str()),
+        _ => response,
+    };
+
+    if is_fresh(request, etag.as_deref(), modified) {
+        return Ok(validators(Response::new(Status::NotModified)));
+    }
+
+    // Ranges only apply if the client's partial copy is of the current file.
+    let range = match request.header("if-range") {
+        Some(x) if Some(x) != last_modified.as_deref() => None,
+        _ => request.header("range"),
+    };
+
+    let (response, start, length) = match byte_range(range, size) {
+        ByteRange::Full => (Response::ok(), 0, size),
+        ByteRange::Partial(start, end) => {
+            let response = Response::new(Status::PartialContent)
+                .header("Content-Range", format!("bytes {}-{}/{}", start, end, size));
+
+            (response, start, end - start + 1)
+        },
+        ByteRange::Unsatisfiable => {
+            return Ok(Response::new(Status::RangeNotSatisfiable)
+                .header("Content-Range", format!("bytes */{}", size)));
+        },
+    };
+
+    let response = validators(response.header("Accept-Ranges", "bytes"));
+    file.seek(SeekFrom::Start(start))?;
+    let mut file = file.take(length);
+
+    if length > STREAM_THRESHOLD {
+        return Ok(response
+            .header("Content-Length", length.to_string())
+            .stream(file));
+    }
+
+    let mut contents = Vec::with_capacity(length as usize);
+    file.read_to_end(&mut contents)?;
+    Ok(response.body(contents))
+}
+
+/// Creates a weak entity tag for a file, from its size and modification time.
+fn etag(size: u64, modified: SystemTime) -> String {
+    let modified = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+    format!("W/\"{:x}-{:x}.{:x}\"", size, modified.as_secs(), modified.subsec_nanos())
+}
+
+/// Returns `true` if the client's cached copy of a file is still current,
+/// according to the request's `If-None-Match` header,
+/// or when it has none, its `If-Modified-Since` header.
+fn is_fresh(request: &Request, etag: Option<&str>, modified: Option<SystemTime>) -> bool {
+    if let Some(tags) = request.header("if-none-match") {
+        // Entity tags are compared weakly, ignoring their `W/` prefixes.
+        let etag = etag.map(|x|x.trim_start_matches("W/"));
+
+        return tags.split(',')
+            .map(str::trim)
+            .any(|x|x == "*" || Some(x.trim_start_matches("W/")) == etag);
+    }
+
+    match (request.header("if-modified-since").and_then(date::parse), modified) {
+        // HTTP dates only have whole seconds, so the modification time is truncated to match.
+        (Some(since), Some(modified)) => modified.duration_since(UNIX_EPOCH)
+            .is_ok_and(|x|UNIX_EPOCH + Duration::from_secs(x.as_secs()) <= since),
+        _ => false,
+    }
+}
+
+/// The part of a file a `Range` header asks for.
+#[derive(Debug, PartialEq, Eq)]
+enum ByteRange {
+    /// The whole file, when no range, or one that can't be served alone, is asked for.
+    Full,
+    /// The bytes from the first offset to the second, inclusive.
+    Partial(u64, u64),
+    /// A range beginning past the end of the file.
+    Unsatisfiable,
+}
+
+/// Interprets a `Range` header for a file of `size` bytes.
+///
+/// Only single ranges are served,
+/// with malformed headers and multiple ranges answered with the whole file.
+fn byte_range(header: Option<&str>, size: u64) -> ByteRange {
+    let spec = match header.and_then(|x|x.trim().strip_prefix("bytes=")) {
+        Some(spec) if !spec.contains(',') => spec.trim(),
+        _ => return ByteRange::Full,
+    };
+
+    let (start, end) = match spec.split_once('-') {
+        Some(x) => x,
+        None => return ByteRange::Full,
+    };
+
+    match (start.parse::<u64>(), end.parse::<u64>()) {
+        // A suffix range, asking for the last `n` bytes.
+        (Err(_), Ok(n)) if start.is_empty() => match n.min(size) {
+            0 => ByteRange::Unsatisfiable,
+            n => ByteRange::Partial(size - n, size - 1),
+        },
+        (Ok(start), _) if start >= size => ByteRange::Unsatisfiable,
+        (Ok(start), Err(_)) if end.is_empty() => ByteRange::Partial(start, size - 1),
+        (Ok(start), Ok(end)) if start <= end => ByteRange::Partial(start, end.min(size - 1)),
+        _ => ByteRange::Full,
+    }
+}
+
+/// Returns the MIME type for a file, based on its extension,
+/// defaulting to `application/octet-stream`.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::static_files::mime_type;
+///
+/// assert_eq!("text/html; charset=utf-8", mime_type("index.html".as_ref()));
+/// assert_eq!("image/png", mime_type("logo.PNG".as_ref()));
+/// assert_eq!("application/octet-stream", mime_type("data".as_ref()));
+/// ```
+pub fn mime_type(path: &Path) -> &'static str {
+    let extension = path.extension()
+        .and_then(|x|x.to_str())
+        .map(str::to_ascii_lowercase);
+
+    match extension.as_deref() {
+        Some("html" | "htm") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js" | "mjs") => "text/javascript; charset=utf-8",
+        Some("txt") => "text/plain; charset=utf-8",
+        Some("csv") => "text/csv; charset=utf-8",
+        Some("json") => "application/json",
+        Some("xml") => "application/xml",
+        Some("wasm") => "application/wasm",
+        Some("pdf") => "application/pdf",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("ico") => "image/x-icon",
+        Some("mp3") => "audio/mpeg",
+        Some("ogg") => "audio/ogg",
+        Some("wav") => "audio/wav",
+        Some("mp4") => "video/mp4",
+        Some("webm") => "video/webm",
+        Some("woff") => "font/woff",
+        Some("woff2") => "font/woff2",
+        Some("ttf") => "font/ttf",
+        Some("otf") => "font/otf",
+        _ => "application/octet-stream",
+    }
+}
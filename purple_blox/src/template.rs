@@ -0,0 +1,274 @@
+//! Rendering HTML templates, with variable substitution and includes.
+//!
+//! Templates are plain text, with tags marked by double braces:
+//!
+//! * `{{name}}` is replaced by the variable's value, escaped for HTML.
+//! * `{{&name}}` is replaced by the variable's value, as it is.
+//! * `{{>path}}` is replaced by the template at `path`,
+//!   relative to the including template's directory.
+//!
+//! Whitespace around a tag's contents is ignored.
+use std::{
+    collections::HashMap,
+    fmt,
+    fs,
+    io,
+    path::Path,
+};
+
+/// The deepest templates can be nested with includes,
+/// stopping templates which include themselves.
+pub const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// A parsed template, with its includes resolved.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::template::{Context, Template};
+///
+/// let template = Template::parse("<p>{{name}} has visited {{ count }} times</p>").unwrap();
+/// let ctx = Context::new()
+///     .with("name", "<Ferris>")
+///     .with("count", 3);
+///
+/// assert_eq!("<p>&lt;Ferris&gt; has visited 3 times</p>", template.apply(&ctx).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Variable {
+        name: String,
+        escape: bool,
+    },
+}
+
+/// The variables a template is rendered with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Context {
+    values: HashMap<String, String>,
+}
+
+impl Template {
+    /// Loads the template at `path` and renders it with `ctx`.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the template, or one it includes,
+    /// can't be read or parsed,
+    /// or uses a variable `ctx` doesn't have.
+    pub fn render(path: impl AsRef<Path>, ctx: &Context) -> Result<String, TemplateError> {
+        Self::load(path)?.apply(ctx)
+    }
+
+    /// Loads and parses the template at `path`, resolving its includes.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the template, or one it includes,
+    /// can't be read or parsed,
+    /// or includes are nested deeper than [`MAX_INCLUDE_DEPTH`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TemplateError> {
+        Self::load_nested(path.as_ref(), 0)
+    }
+
+    /// Parses `source` as a template.
+    ///
+    /// Includes are resolved relative to the current directory.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if a tag isn't closed,
+    /// or an included template can't be loaded.
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        Self::parse_in(source, Path::new(""), 0)
+    }
+
+    /// Renders the template with `ctx`.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the template uses a variable `ctx` doesn't have.
+    pub fn apply(&self, ctx: &Context) -> Result<String, TemplateError> {
+        self.segments
+            .iter()
+            .try_fold(String::new(), |mut acc, x|{
+                match x {
+                    Segment::Text(text) => acc.push_str(text),
+                    Segment::Variable { name, escape } => {
+                        let value = ctx.get(name)
+                            .ok_or_else(||TemplateError::new(TemplateErrorKind::MissingVariable, name))?;
+
+                        match escape {
+                            true => acc.push_str(&escape_html(value)),
+                            false => acc.push_str(value),
+                        }
+                    },
+                }
+
+                Ok(acc)
+            })
+    }
+
+    fn load_nested(path: &Path, depth: usize) -> Result<Self, TemplateError> {
+        if depth > MAX_INCLUDE_DEPTH {
+            return Err(TemplateError::new(TemplateErrorKind::IncludeDepth, path.display()));
+        }
+
+        let source = fs::read_to_string(path)
+            .map_err(|err|TemplateError::new(TemplateErrorKind::Io(err.kind()), path.display()))?;
+
+        Self::parse_in(&source, path.parent().unwrap_or(Path::new("")), depth)
+    }
+
+    fn parse_in(source: &str, dir: &Path, depth: usize) -> Result<Self, TemplateError> {
+        let mut segments = Vec::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            let (text, tag) = rest.split_at(start);
+            let end = tag.find("}}")
+                .ok_or_else(||TemplateError::new(TemplateErrorKind::Unclosed, tag.lines().next().unwrap_or_default()))?;
+
+            push_text(&mut segments, text);
+
+            match tag[2..end].trim() {
+                x if x.starts_with('>') => {
+                    let included = Self::load_nested(&dir.join(x[1..].trim()), depth + 1)?;
+                    included.segments
+                        .into_iter()
+                        .for_each(|x|match x {
+                            Segment::Text(text) => push_text(&mut segments, &text),
+                            variable => segments.push(variable),
+                        });
+                },
+                x if x.starts_with('&') => segments.push(Segment::Variable {
+                    name: x[1..].trim().to_string(),
+                    escape: false,
+                }),
+                x => segments.push(Segment::Variable {
+                    name: x.to_string(),
+                    escape: true,
+                }),
+            }
+
+            rest = &tag[end + 2..];
+        }
+
+        push_text(&mut segments, rest);
+        Ok(Self {
+            segments
+        })
+    }
+}
+
+/// Adds text to the template, joining it with any text before it.
+fn push_text(segments: &mut Vec<Segment>, text: &str) {
+    match segments.last_mut() {
+        _ if text.is_empty() => (),
+        Some(Segment::Text(last)) => last.push_str(text),
+        _ => segments.push(Segment::Text(text.to_string())),
+    }
+}
+
+/// Escapes the characters with special meaning in HTML.
+pub fn escape_html(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut acc, x|{
+            match x {
+                '&' => acc.push_str("&amp;"),
+                '<' => acc.push_str("&lt;"),
+                '>' => acc.push_str("&gt;"),
+                '"' => acc.push_str("&quot;"),
+                '\'' => acc.push_str("&#39;"),
+                x => acc.push(x),
+            }
+            acc
+        })
+}
+
+impl Context {
+    /// Creates an empty `Context`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the variable `name` to `value`, returning the context.
+    #[must_use]
+    pub fn with(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    /// Sets the variable `name` to `value`.
+    pub fn insert(&mut self, name: impl Into<String>, value: impl ToString) {
+        self.values.insert(name.into(), value.to_string());
+    }
+
+    /// Returns the value of the variable `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .get(name)
+            .map(String::as_str)
+    }
+}
+
+/// The error type for loading and rendering templates.
+#[derive(Debug, Clone)]
+pub struct TemplateError {
+    kind: TemplateErrorKind,
+    detail: String,
+}
+
+/// A list specifying the categories of [`TemplateError`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateErrorKind {
+    /// A template couldn't be read.
+    Io(io::ErrorKind),
+    /// A tag was opened with `{{`, but not closed.
+    Unclosed,
+    /// The template used a variable the context didn't have.
+    MissingVariable,
+    /// Includes were nested deeper than [`MAX_INCLUDE_DEPTH`].
+    IncludeDepth,
+}
+
+impl TemplateError {
+    fn new(kind: TemplateErrorKind, detail: impl fmt::Display) -> Self {
+        Self {
+            kind,
+            detail: detail.to_string(),
+        }
+    }
+
+    pub fn kind(&self) -> &TemplateErrorKind {
+        &self.kind
+    }
+
+    /// Returns what the error was about,
+    /// such as the template's path, or the variable's name.
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self.kind {
+            TemplateErrorKind::Io(kind) => return write!(f, "template could not be read: {}: {}", kind, self.detail),
+            TemplateErrorKind::Unclosed => "unclosed tag",
+            TemplateErrorKind::MissingVariable => "missing variable",
+            TemplateErrorKind::IncludeDepth => "includes nested too deeply",
+        };
+
+        write!(f, "{}: {}", description, self.detail)
+    }
+}
+
+impl std::error::Error for TemplateError {}
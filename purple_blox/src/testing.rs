@@ -0,0 +1,187 @@
+//! An in-process server, and a tiny client for it,
+//! for testing routers end to end.
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread,
+};
+
+use crate::{
+    config::ServerConfig,
+    response::{Response, Status},
+    router::Router,
+    server::{Server, ShutdownHandle},
+};
+
+/// A server running a router on an ephemeral port,
+/// in the background, until it's dropped.
+///
+/// Each request is sent over a new connection, which is closed after the response.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::{
+///     request::Request,
+///     response::{Response, Status},
+///     router::Router,
+///     testing::TestServer,
+/// };
+///
+/// let mut router = Router::new();
+/// router.get("/", |_: &Request|Response::ok().body("Hello!"));
+///
+/// let server = TestServer::start(router).unwrap();
+///
+/// let response = server.get("/").unwrap();
+/// assert_eq!(Status::Ok, response.status());
+/// assert_eq!(b"Hello!", response.contents());
+///
+/// assert_eq!(Status::NotFound, server.get("/missing").unwrap().status());
+/// ```
+pub struct TestServer {
+    addr: SocketAddr,
+    handle: ShutdownHandle,
+    running: Option<thread::JoinHandle<()>>,
+}
+
+impl TestServer {
+    /// Starts a server for `router`, with the default configuration.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if an ephemeral port couldn't be bound.
+    pub fn start(router: Router) -> io::Result<Self> {
+        Self::with_config(router, ServerConfig::default())
+    }
+
+    /// Starts a server for `router`, with `config`,
+    /// ignoring its address and port.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if an ephemeral port couldn't be bound.
+    pub fn with_config(router: Router, config: ServerConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let server = Server::new(listener, router, config)?;
+
+        Ok(Self {
+            addr: server.local_addr()?,
+            handle: server.shutdown_handle(),
+            running: Some(thread::spawn(move||{
+                let _ = server.run();
+            })),
+        })
+    }
+
+    /// Returns the address the server is listening on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Sends a `GET` request for `path`, returning the response.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the request couldn't be sent,
+    /// or the response couldn't be read.
+    pub fn get(&self, path: &str) -> io::Result<Response> {
+        self.request(format!("GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path))
+    }
+
+    /// Sends a `POST` request for `path`, with `body`, returning the response.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the request couldn't be sent,
+    /// or the response couldn't be read.
+    pub fn post(&self, path: &str, body: impl AsRef<[u8]>) -> io::Result<Response> {
+        let body = body.as_ref();
+        let head = format!("POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", path, body.len());
+
+        let mut raw = head.into_bytes();
+        raw.extend_from_slice(body);
+        self.request(raw)
+    }
+
+    /// Sends a raw request, returning the response.
+    ///
+    /// The request should ask for the connection to be closed,
+    /// as the response's body is read until it is,
+    /// when it isn't chunked.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`Err`] if the request couldn't be sent,
+    /// or the response couldn't be read.
+    pub fn request(&self, raw: impl AsRef<[u8]>) -> io::Result<Response> {
+        let mut stream = TcpStream::connect(self.addr)?;
+        stream.write_all(raw.as_ref())?;
+
+        read_response(&mut BufReader::new(stream))
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.handle.shutdown();
+
+        if let Some(running) = self.running.take() {
+            let _ = running.join();
+        }
+    }
+}
+
+/// Reads a response, decoding a chunked body.
+fn read_response<R: BufRead>(reader: &mut R) -> io::Result<Response> {
+    let invalid = |detail: &str|io::Error::new(io::ErrorKind::InvalidData, detail.to_string());
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+
+    let mut response = line.split(' ')
+        .nth(1)
+        .and_then(|x|x.parse().ok())
+        .and_then(Status::from_code)
+        .map(Response::new)
+        .ok_or_else(||invalid("malformed status line"))?;
+
+    loop {
+        line.clear();
+        reader.read_line(&mut line)?;
+
+        match line.trim_end().split_once(':') {
+            Some((name, value)) => response.set_header(name, value.trim()),
+            None if line.trim_end().is_empty() => break,
+            None => return Err(invalid("malformed header")),
+        }
+    }
+
+    let mut body = Vec::new();
+
+    match response.get_header("Transfer-Encoding") {
+        Some(x) if x.eq_ignore_ascii_case("chunked") => loop {
+            line.clear();
+            reader.read_line(&mut line)?;
+
+            let size = usize::from_str_radix(line.trim(), 16)
+                .map_err(|_|invalid("malformed chunk size"))?;
+            let start = body.len();
+            body.resize(start + size, 0);
+            reader.read_exact(&mut body[start..])?;
+
+            // Each chunk, including the last, is followed by a blank line.
+            line.clear();
+            reader.read_line(&mut line)?;
+
+            if size == 0 {
+                break;
+            }
+        },
+        _ => {
+            reader.read_to_end(&mut body)?;
+        },
+    }
+
+    Ok(response.body(body))
+}
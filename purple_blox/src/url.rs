@@ -0,0 +1,109 @@
+//! Encoding and decoding for URL paths, query strings and form bodies.
+use std::collections::HashMap;
+
+/// Decodes `%XX` escapes in `s`, along with `+` as a space,
+/// as used in query strings and form bodies.
+///
+/// Malformed escapes are kept as they are,
+/// and invalid UTF-8 is replaced with `U+FFFD`.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::url::percent_decode;
+///
+/// assert_eq!("a b&c", percent_decode("a+b%26c"));
+/// assert_eq!("100%", percent_decode("100%"));
+/// ```
+pub fn percent_decode(s: &str) -> String {
+    decode(s, true)
+}
+
+/// Decodes `%XX` escapes in `s`, as used in the paths of URLs,
+/// where, unlike in query strings, `+` is kept as it is.
+///
+/// Malformed escapes are kept as they are,
+/// and invalid UTF-8 is replaced with `U+FFFD`.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::url::percent_decode_path;
+///
+/// assert_eq!("/my file+1.txt", percent_decode_path("/my%20file+1.txt"));
+/// ```
+pub fn percent_decode_path(s: &str) -> String {
+    decode(s, false)
+}
+
+/// Encodes `segment` for use as one segment of a URL path,
+/// escaping every byte but ASCII letters, digits, and `-._~` as `%XX`.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::url::percent_encode_path_segment;
+///
+/// assert_eq!("a%20b%23%3F%25%2F~.txt", percent_encode_path_segment("a b#?%/~.txt"));
+/// ```
+pub fn percent_encode_path_segment(segment: &str) -> String {
+    segment.bytes()
+        .map(|x|match x {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => char::from(x).to_string(),
+            x => format!("%{:02X}", x),
+        })
+        .collect()
+}
+
+/// Decodes `%XX` escapes in `s`, and `+` as a space, if `plus_as_space`.
+fn decode(s: &str, plus_as_space: bool) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3)
+            .and_then(|x|std::str::from_utf8(x).ok())
+            .and_then(|x|u8::from_str_radix(x, 16).ok());
+
+        match (bytes[i], escaped) {
+            (b'%', Some(x)) => {
+                decoded.push(x);
+                i += 3;
+                continue;
+            },
+            (b'+', _) if plus_as_space => decoded.push(b' '),
+            (x, _) => decoded.push(x),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Parses `key=value` pairs separated by `&`,
+/// percent-decoding both halves.
+///
+/// Keys without a value map to an empty string,
+/// and the last value wins when a key is repeated.
+///
+/// # Examples
+///
+/// ```
+/// use purple_blox::url::parse_query;
+///
+/// let query = parse_query("name=purple+blox&empty&lang=en%2DGB");
+///
+/// assert_eq!(Some("purple blox"), query.get("name").map(String::as_str));
+/// assert_eq!(Some(""), query.get("empty").map(String::as_str));
+/// assert_eq!(Some("en-GB"), query.get("lang").map(String::as_str));
+/// ```
+pub fn parse_query(query: &str) -> HashMap<String, String> {
+    query.split('&')
+        .filter(|x|!x.is_empty())
+        .map(|x|match x.split_once('=') {
+            Some((key, value)) => (percent_decode(key), percent_decode(value)),
+            None => (percent_decode(x), String::new()),
+        })
+        .collect()
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "tokio")]
+use std::net::{SocketAddr, TcpListener};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use purple_blox::{
+    async_server,
+    request::Request,
+    response::Response,
+    router::Router,
+};
+
+/// Runs the server on an ephemeral port, as a background task.
+fn spawn(router: Router) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async_server::run_async(listener, router, Default::default()));
+    addr
+}
+
+/// Sends a raw request, returning the raw response.
+async fn send(addr: SocketAddr, raw: &str) -> String {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(raw.as_bytes()).await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router.get("/", |_: &Request|Response::ok().body("async"))
+        .post("/echo", |req: &Request|Response::ok().body(req.body().to_vec()));
+
+    router
+}
+
+#[tokio::test]
+async fn serves_requests() {
+    let addr = spawn(router());
+
+    let response = send(addr, "GET / HTTP/1.1\r\nConnection: close\r\n\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.ends_with("async"));
+
+    let response = send(addr, "GET /missing HTTP/1.1\r\nConnection: close\r\n\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+}
+
+#[tokio::test]
+async fn reads_request_bodies() {
+    let addr = spawn(router());
+
+    let response = send(addr, "POST /echo HTTP/1.1\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello").await;
+    assert!(response.ends_with("hello"));
+}
+
+#[tokio::test]
+async fn keeps_connections_alive() {
+    let addr = spawn(router());
+
+    let response = send(addr, "GET / HTTP/1.1\r\n\r\nGET / HTTP/1.1\r\nConnection: close\r\n\r\n").await;
+    assert_eq!(2, response.matches("HTTP/1.1 200 OK").count());
+}
+
+#[tokio::test]
+async fn rejects_malformed_requests() {
+    let addr = spawn(router());
+
+    let response = send(addr, "nonsense\r\n\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request"));
+}
+
+#[tokio::test]
+async fn refuses_oversized_requests() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let config = purple_blox::config::ServerConfig { max_header_size: 128, max_body_size: 16, ..Default::default() };
+    tokio::spawn(async_server::run_async(listener, router(), config));
+
+    let headers = format!("GET / HTTP/1.1\r\nX-Padding: {}\r\n\r\n", "a".repeat(200));
+    assert!(send(addr, &headers).await.starts_with("HTTP/1.1 431 Request Header Fields Too Large"));
+
+    let body = "POST /echo HTTP/1.1\r\nContent-Length: 1000000\r\n\r\n";
+    assert!(send(addr, body).await.starts_with("HTTP/1.1 413 Payload Too Large"));
+}
@@ -0,0 +1,78 @@
+//! Helpers shared between the integration tests.
+#![allow(dead_code)] // Each test crate uses only some of the helpers.
+
+use std::{
+    env,
+    fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+    process,
+};
+use purple_blox::request::Request;
+
+/// Reads a request from `raw`.
+///
+/// # Panics
+///
+/// Panics if `raw` isn't a complete, valid request.
+pub fn request(raw: &str) -> Request {
+    Request::read_from(&mut raw.as_bytes()).unwrap()
+}
+
+/// A directory in the system's temporary directory, unique to a test,
+/// removed along with its contents when dropped.
+///
+/// Dereferences to its path, so files can be joined onto it.
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates an empty directory for the test `name`,
+    /// replacing any left behind by an earlier run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory can't be created.
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("purple_blox_{}_{}", name, process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        Self {
+            path
+        }
+    }
+
+    /// Writes `contents` to the file at `path` in the directory,
+    /// creating any directories it's nested in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file can't be written.
+    pub fn write(&self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> &Self {
+        let path = self.path.join(path);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+
+        fs::write(path, contents).unwrap();
+        self
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
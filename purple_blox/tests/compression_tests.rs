@@ -0,0 +1,73 @@
+#![cfg(feature = "compression")]
+mod common;
+
+use std::io::Read;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use purple_blox::{
+    middleware,
+    request::Request,
+    response::Response,
+    router::Router,
+};
+use common::request;
+
+const MIN_SIZE: usize = 64;
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router.get("/text", |_: &Request|Response::ok()
+            .header("Content-Type", "text/plain; charset=utf-8")
+            .body("compressible ".repeat(20)))
+        .get("/short", |_: &Request|Response::ok()
+            .header("Content-Type", "text/plain; charset=utf-8")
+            .body("short"))
+        .get("/image", |_: &Request|Response::ok()
+            .header("Content-Type", "image/png")
+            .body(vec![0; 1024]))
+        .middleware(middleware::compress(MIN_SIZE));
+
+    router
+}
+
+fn get(router: &Router, path: &str, accept_encoding: &str) -> Response {
+    let raw = format!("GET {} HTTP/1.1\r\nAccept-Encoding: {}\r\n\r\n", path, accept_encoding);
+    router.handle(request(&raw))
+}
+
+#[test]
+fn compresses_negotiated_encodings() {
+    let router = router();
+    let expected = "compressible ".repeat(20);
+
+    let gzip = get(&router, "/text", "gzip, deflate");
+    assert_eq!(Some("gzip"), gzip.get_header("Content-Encoding"));
+    assert_eq!(Some("Accept-Encoding"), gzip.get_header("Vary"));
+
+    let mut body = String::new();
+    GzDecoder::new(gzip.contents()).read_to_string(&mut body).unwrap();
+    assert_eq!(expected, body);
+
+    let deflate = get(&router, "/text", "deflate");
+    assert_eq!(Some("deflate"), deflate.get_header("Content-Encoding"));
+
+    let mut body = String::new();
+    ZlibDecoder::new(deflate.contents()).read_to_string(&mut body).unwrap();
+    assert_eq!(expected, body);
+}
+
+#[test]
+fn skips_unsuitable_responses() {
+    let router = router();
+
+    let identity = get(&router, "/text", "br, gzip;q=0");
+    assert_eq!(None, identity.get_header("Content-Encoding"));
+    assert_eq!(Some("Accept-Encoding"), identity.get_header("Vary"));
+
+    let short = get(&router, "/short", "gzip");
+    assert_eq!(None, short.get_header("Content-Encoding"));
+    assert_eq!(b"short", short.contents());
+
+    let image = get(&router, "/image", "gzip");
+    assert_eq!(None, image.get_header("Content-Encoding"));
+    assert_eq!(None, image.get_header("Vary"));
+}
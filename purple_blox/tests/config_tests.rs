@@ -0,0 +1,140 @@
+mod common;
+
+use std::{
+    env,
+    net::Ipv4Addr,
+    path::PathBuf,
+    time::Duration,
+};
+use purple_blox::{
+    config::{ConfigErrorKind, ServerConfig},
+    response::Status,
+};
+use common::{request, TempDir};
+
+/// Creates a fresh site directory for a test,
+/// containing an index page and a not found page.
+fn site(name: &str) -> TempDir {
+    let root = TempDir::new(&format!("config_{}", name));
+    root.write("home.html", "<h1>Home</h1>")
+        .write("errors/404.html", "<h1>Lost</h1>");
+
+    root
+}
+
+fn config(site: PathBuf) -> ServerConfig {
+    ServerConfig {
+        site_dir: site,
+        index_page: "home.html".into(),
+        error_pages: [(404, "errors/404.html".into())].into(),
+        ..Default::default()
+    }
+}
+
+fn kind<T>(result: Result<T, purple_blox::config::ConfigError>) -> Option<ConfigErrorKind> {
+    result.err().map(|err|*err.kind())
+}
+
+#[test]
+fn toml_overrides_defaults() {
+    let config = ServerConfig::from_toml(r#"
+        bind_address = "0.0.0.0"
+        threads = 0
+        keep_alive_timeout = 0
+        max_body_size = 4096
+        directory_listing = true
+
+        [error_pages]
+        500 = "oops.html"
+    "#).unwrap();
+
+    assert_eq!(Ipv4Addr::UNSPECIFIED, config.bind_address);
+    assert_eq!(0, config.threads);
+    assert_eq!(Duration::ZERO, config.keep_alive_timeout);
+    assert_eq!(4096, config.max_body_size);
+    assert!(config.directory_listing);
+    assert_eq!(ServerConfig::default().port, config.port);
+    assert_eq!(Some(&PathBuf::from("oops.html")), config.error_pages.get(&500));
+    assert!(config.error_pages.contains_key(&404));
+}
+
+#[test]
+fn invalid_toml() {
+    assert_eq!(Some(ConfigErrorKind::Syntax), kind(ServerConfig::from_toml("port = ")));
+    assert_eq!(Some(ConfigErrorKind::Syntax), kind(ServerConfig::from_toml("port = \"80\"")));
+    assert_eq!(Some(ConfigErrorKind::Syntax), kind(ServerConfig::from_toml("workers = 2")));
+    assert_eq!(Some(ConfigErrorKind::InvalidTimeout), kind(ServerConfig::from_toml("read_timeout = -1")));
+    assert_eq!(Some(ConfigErrorKind::InvalidErrorStatus), kind(ServerConfig::from_toml("[error_pages]\nmissing = \"404.html\"")));
+    assert_eq!(Some(ConfigErrorKind::Io(std::io::ErrorKind::NotFound)), kind(ServerConfig::load("missing.toml")));
+}
+
+#[test]
+fn environment_overrides() {
+    let config = ServerConfig::from_toml("port = 80").unwrap()
+        .override_from([
+            ("PURPLE_BLOX_PORT", "8080"),
+            ("PURPLE_BLOX_READ_TIMEOUT", "0.5"),
+            ("PURPLE_BLOX_SITE_DIR", "public"),
+            ("PURPLE_BLOX_MAX_HEADER_SIZE", "1024"),
+            ("PATH", "/bin"),
+        ])
+        .unwrap();
+
+    assert_eq!(8080, config.port);
+    assert_eq!(Duration::from_millis(500), config.read_timeout);
+    assert_eq!(PathBuf::from("public"), config.site_dir);
+    assert_eq!(1024, config.max_header_size);
+
+    let invalid = ServerConfig::default().override_from([("PURPLE_BLOX_PORT", "eighty")]);
+    assert_eq!(Some(ConfigErrorKind::InvalidVariable), kind(invalid));
+
+    let unknown = ServerConfig::default().override_from([("PURPLE_BLOX_WORKERS", "2")]);
+    assert_eq!(Some(ConfigErrorKind::InvalidVariable), kind(unknown));
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_environment_is_skipped() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    // Only another program's variable, which could hold anything.
+    env::set_var("CONFIG_TESTS_NOT_UTF8", OsStr::from_bytes(b"caf\xe9"));
+
+    assert!(ServerConfig::from_env().is_ok());
+}
+
+#[test]
+fn validation() {
+    let site = site("validation");
+    let valid = config(site.to_path_buf());
+    assert!(valid.validate().is_ok());
+
+    let no_site = ServerConfig { site_dir: "missing".into(), ..valid.clone() };
+    assert_eq!(Some(ConfigErrorKind::MissingSiteDir), kind(no_site.validate()));
+
+    let no_page = ServerConfig { index_page: "missing.html".into(), ..valid.clone() };
+    assert_eq!(Some(ConfigErrorKind::MissingPage), kind(no_page.validate()));
+
+    let not_an_error = ServerConfig { error_pages: [(200, "home.html".into())].into(), ..valid.clone() };
+    assert_eq!(Some(ConfigErrorKind::InvalidErrorStatus), kind(not_an_error.validate()));
+
+    let no_timeout = ServerConfig { read_timeout: Duration::ZERO, ..valid };
+    assert_eq!(Some(ConfigErrorKind::InvalidTimeout), kind(no_timeout.validate()));
+}
+
+#[test]
+fn site_router_uses_configured_pages() {
+    let site = site("router");
+    let router = purple_blox::site_router(&config(site.to_path_buf()));
+    let get = |path: &str|{
+        let raw = format!("GET {} HTTP/1.1\r\n\r\n", path);
+        router.handle(request(&raw))
+    };
+
+    assert_eq!(b"<h1>Home</h1>", get("/").contents());
+
+    let missing = get("/missing");
+    assert_eq!(Status::NotFound, missing.status());
+    assert_eq!(b"<h1>Lost</h1>", missing.contents());
+    assert_eq!(Some("text/html; charset=utf-8"), missing.get_header("Content-Type"));
+}
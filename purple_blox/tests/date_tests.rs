@@ -0,0 +1,38 @@
+use std::time::{Duration, UNIX_EPOCH};
+use purple_blox::date;
+
+#[test]
+fn formats_dates() {
+    let at = |secs|date::format(UNIX_EPOCH + Duration::from_secs(secs));
+
+    assert_eq!("Thu, 01 Jan 1970 00:00:00 GMT", at(0));
+    assert_eq!("Tue, 29 Feb 2000 12:00:00 GMT", at(951825600));
+    assert_eq!("Fri, 31 Dec 2100 23:59:59 GMT", at(4133980799));
+}
+
+#[test]
+fn round_trips() {
+    for secs in (0..5_000_000_000).step_by(86_399_937) {
+        let time = UNIX_EPOCH + Duration::from_secs(secs);
+        assert_eq!(Some(time), date::parse(&date::format(time)));
+    }
+}
+
+#[test]
+fn rejects_malformed_dates() {
+    assert_eq!(None, date::parse("06 Nov 1994 08:49:37 GMT"));
+    assert_eq!(None, date::parse("Sun, 06 Nov 1994 08:49:37 UTC"));
+    assert_eq!(None, date::parse("Sun, 06 Nox 1994 08:49:37 GMT"));
+    assert_eq!(None, date::parse("Sun, 06 Nov 1994 24:49:37 GMT"));
+    assert_eq!(None, date::parse("Sun, 06 Nov 1994 08:49 GMT"));
+    assert_eq!(None, date::parse("Wed, 31 Dec 1969 23:59:59 GMT"));
+}
+
+#[test]
+fn rejects_oversized_years() {
+    assert_eq!(None, date::parse("Fri, 31 Dec 10000 23:59:59 GMT"));
+    assert_eq!(None, date::parse("Sun, 01 Jan 300000000000 00:00:00 GMT"));
+    assert_eq!(None, date::parse("Sun, 01 Jan 999999999999 00:00:00 GMT"));
+    assert_eq!(None, date::parse("Sun, 01 Jan 18446744073709551615 00:00:00 GMT"));
+    assert!(date::parse("Fri, 31 Dec 9999 23:59:59 GMT").is_some());
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "serde_json")]
+mod common;
+
+use serde::{Deserialize, Serialize};
+use purple_blox::{
+    request::{Request, RequestErrorKind},
+    response::{Response, Status},
+    router::Router,
+};
+use common::request;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Score {
+    name: String,
+    points: u32,
+}
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router.post("/scores", |req: &Request|match req.json::<Score>() {
+        Ok(score) => Response::json(&Score { points: score.points * 2, ..score }),
+        Err(err) => err.into(),
+    });
+
+    router
+}
+
+#[test]
+fn round_trips_json() {
+    let body = r#"{"name":"ada","points":21}"#;
+    let response = router().handle(request(&format!("POST /scores HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}", body.len(), body)));
+
+    assert_eq!(Status::Ok, response.status());
+    assert_eq!(Some("application/json"), response.get_header("Content-Type"));
+    assert_eq!(r#"{"name":"ada","points":42}"#.as_bytes(), response.contents());
+}
+
+#[test]
+fn invalid_json_is_a_bad_request() {
+    let request = request("POST /scores HTTP/1.1\r\nContent-Length: 9\r\n\r\n{\"name\":1");
+
+    assert_eq!(RequestErrorKind::InvalidJson, *request.json::<Score>().unwrap_err().kind());
+    assert_eq!(Status::BadRequest, router().handle(request).status());
+}
+
+#[test]
+fn unserialisable_values_are_server_errors() {
+    let map: std::collections::HashMap<(u8, u8), u8> = [((1, 2), 3)].into();
+
+    assert_eq!(Status::InternalServerError, Response::json(&map).status());
+}
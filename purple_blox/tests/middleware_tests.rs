@@ -0,0 +1,88 @@
+mod common;
+
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+use purple_blox::{
+    middleware::{self, Next},
+    request::Request,
+    response::{Response, Status},
+    router::Router,
+};
+use common::request;
+
+/// A cloneable log target, for inspecting what's been written to it.
+#[derive(Clone, Default)]
+struct SharedLog(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn layers_run_in_order() {
+    let mut router = Router::new();
+    router.get("/", |_: &Request|Response::ok().body("handler"))
+        .middleware(|req: Request, next: Next<'_>|{
+            let response = next.run(req);
+            let body = format!("outer({})", String::from_utf8_lossy(response.contents()));
+            response.body(body)
+        })
+        .middleware(|req: Request, next: Next<'_>|{
+            let response = next.run(req);
+            let body = format!("inner({})", String::from_utf8_lossy(response.contents()));
+            response.body(body)
+        });
+
+    assert_eq!(b"outer(inner(handler))", router.handle(request("GET / HTTP/1.1\r\n\r\n")).contents());
+}
+
+#[test]
+fn layers_can_short_circuit() {
+    let mut router = Router::new();
+    router.get("/", |_: &Request|Response::ok())
+        .middleware(|req: Request, next: Next<'_>|match req.header("authorization") {
+            Some(_) => next.run(req),
+            None => Response::forbidden(),
+        });
+
+    assert_eq!(Status::Forbidden, router.handle(request("GET / HTTP/1.1\r\n\r\n")).status());
+    assert_eq!(Status::Ok, router.handle(request("GET / HTTP/1.1\r\nAuthorization: yes\r\n\r\n")).status());
+}
+
+#[test]
+fn access_log_records_requests() {
+    let log = SharedLog::default();
+
+    let mut router = Router::new();
+    router.get("/", |_: &Request|Response::ok())
+        .middleware(middleware::access_log(log.clone()));
+
+    router.handle(request("GET / HTTP/1.1\r\n\r\n"));
+    router.handle(request("POST /missing?x=1 HTTP/1.1\r\n\r\n"));
+
+    let written = String::from_utf8(log.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<_> = written.lines().collect();
+
+    assert_eq!(2, lines.len());
+    assert!(lines[0].starts_with("- \"GET /\" 200 "));
+    assert!(lines[1].starts_with("- \"POST /missing\" 404 "));
+}
+
+#[test]
+fn rate_limit_skips_requests_without_peers() {
+    let mut router = Router::new();
+    router.get("/", |_: &Request|Response::ok())
+        .middleware(middleware::rate_limit(1, std::time::Duration::from_secs(60)));
+
+    for _ in 0..3 {
+        assert_eq!(Status::Ok, router.handle(request("GET / HTTP/1.1\r\n\r\n")).status());
+    }
+}
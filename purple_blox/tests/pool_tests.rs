@@ -0,0 +1,332 @@
+use std::{
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use purple_blox::pool::{DEFAULT_THREAD_NAME, ExecuteErrorKind, JobErrorKind, PoolEvents, PoolInitialisationErrorKind, QueuePolicy, ThreadPool};
+
+/// Occupies the pool's single worker until the returned sender is used,
+/// waiting until the worker has picked the job up.
+fn occupy(pool: &ThreadPool) -> Sender<()> {
+    let (release, wait) = mpsc::channel();
+    let (started, running) = mpsc::channel();
+
+    pool.execute(move||{
+        started.send(()).unwrap();
+        wait.recv().unwrap();
+    }).unwrap();
+
+    running.recv().unwrap();
+    release
+}
+
+/// Queues a job recording `n` once it runs.
+fn record(pool: &ThreadPool, log: &Arc<Mutex<Vec<usize>>>, n: usize) -> Result<(), ExecuteErrorKind> {
+    let log = Arc::clone(log);
+    pool.execute(move||log.lock().unwrap().push(n))
+        .map_err(|err|*err.kind())
+}
+
+fn finish(pool: ThreadPool) {
+    assert!(pool.shutdown_timeout(Duration::from_secs(5)));
+}
+
+#[test]
+fn invalid_pools() {
+    assert!(matches!(ThreadPool::new(0).err().unwrap().kind(), PoolInitialisationErrorKind::ZeroThreads));
+    assert!(matches!(ThreadPool::bounded(1, 0, QueuePolicy::Block).err().unwrap().kind(), PoolInitialisationErrorKind::ZeroCapacity));
+}
+
+#[test]
+fn reject_policy() {
+    let pool = ThreadPool::bounded(1, 2, QueuePolicy::Reject).unwrap();
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let release = occupy(&pool);
+
+    assert_eq!(Ok(()), record(&pool, &log, 1));
+    assert_eq!(Ok(()), record(&pool, &log, 2));
+    assert_eq!(Err(ExecuteErrorKind::QueueFull), record(&pool, &log, 3));
+    assert_eq!(2, pool.queue_depth());
+    assert_eq!(1, pool.dropped_jobs());
+
+    release.send(()).unwrap();
+    finish(pool);
+    assert_eq!(vec![1, 2], *log.lock().unwrap());
+}
+
+#[test]
+fn drop_oldest_policy() {
+    let pool = ThreadPool::bounded(1, 2, QueuePolicy::DropOldest).unwrap();
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let release = occupy(&pool);
+
+    for n in 1..=4 {
+        assert_eq!(Ok(()), record(&pool, &log, n));
+    }
+    assert_eq!(2, pool.queue_depth());
+    assert_eq!(2, pool.peak_queue_depth());
+    assert_eq!(2, pool.dropped_jobs());
+
+    release.send(()).unwrap();
+    finish(pool);
+    assert_eq!(vec![3, 4], *log.lock().unwrap());
+}
+
+#[test]
+fn block_policy() {
+    let pool = Arc::new(ThreadPool::bounded(1, 1, QueuePolicy::Block).unwrap());
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let release = occupy(&pool);
+
+    assert_eq!(Ok(()), record(&pool, &log, 1));
+
+    let (done, blocked): (Sender<()>, Receiver<()>) = mpsc::channel();
+    let sender = {
+        let (pool, log) = (Arc::clone(&pool), Arc::clone(&log));
+        thread::spawn(move||{
+            record(&pool, &log, 2).unwrap();
+            done.send(()).unwrap();
+        })
+    };
+
+    // The second job can't be queued until the worker is released.
+    assert!(blocked.recv_timeout(Duration::from_millis(100)).is_err());
+
+    release.send(()).unwrap();
+    blocked.recv_timeout(Duration::from_secs(5)).unwrap();
+    sender.join().unwrap();
+
+    finish(Arc::try_unwrap(pool).ok().unwrap());
+    assert_eq!(vec![1, 2], *log.lock().unwrap());
+}
+
+#[test]
+fn workers_survive_panicking_jobs() {
+    let mut pool = ThreadPool::new(1).unwrap();
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    pool.execute(||panic!("job failed")).unwrap();
+    assert_eq!(Ok(()), record(&pool, &log, 1));
+
+    let (done, finished) = mpsc::channel();
+    pool.execute(move||done.send(()).unwrap()).unwrap();
+    finished.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    assert_eq!(1, pool.panicked_jobs());
+    assert_eq!(1, pool.healthy_workers());
+    assert_eq!(0, pool.rebuild());
+
+    finish(pool);
+    assert_eq!(vec![1], *log.lock().unwrap());
+}
+
+#[test]
+fn submitted_jobs_return_results() {
+    let pool = ThreadPool::new(2).unwrap();
+
+    let square = pool.submit(||7 * 7).unwrap();
+    let failed = pool.submit(||-> u32 {panic!("job failed")}).unwrap();
+
+    assert_eq!(Ok(49), square.join().map_err(|err|*err.kind()));
+    assert_eq!(Err(JobErrorKind::Panicked), failed.join().map_err(|err|*err.kind()));
+}
+
+#[test]
+fn polling_results() {
+    let pool = ThreadPool::new(1).unwrap();
+    let release = occupy(&pool);
+
+    let handle = pool.submit(||"done").unwrap();
+    assert!(handle.try_result().is_none());
+
+    release.send(()).unwrap();
+    finish(pool);
+
+    assert_eq!(Some("done"), handle.try_result().and_then(Result::ok));
+    assert_eq!(Some(JobErrorKind::Lost), handle.try_result().and_then(|x|x.err()).map(|err|*err.kind()));
+}
+
+#[test]
+fn discarded_jobs_are_lost() {
+    let pool = ThreadPool::bounded(1, 1, QueuePolicy::DropOldest).unwrap();
+    let release = occupy(&pool);
+
+    let discarded = pool.submit(||1).unwrap();
+    let kept = pool.submit(||2).unwrap();
+
+    release.send(()).unwrap();
+
+    assert_eq!(Err(JobErrorKind::Lost), discarded.join().map_err(|err|*err.kind()));
+    assert_eq!(Ok(2), kept.join().map_err(|err|*err.kind()));
+}
+
+/// Pool events recorded as strings, for checking which were called.
+#[derive(Clone, Default)]
+struct EventLog(Arc<Mutex<Vec<String>>>);
+
+impl PoolEvents for EventLog {
+    fn on_job_start(&self, worker: usize) {
+        self.0.lock().unwrap().push(format!("start {}", worker));
+    }
+
+    fn on_job_end(&self, worker: usize, _elapsed: Duration, panic: Option<&str>) {
+        self.0.lock().unwrap().push(format!("end {} {:?}", worker, panic));
+    }
+
+    fn on_shutdown(&self, worker: usize) {
+        self.0.lock().unwrap().push(format!("shutdown {}", worker));
+    }
+}
+
+#[test]
+fn events_are_reported() {
+    let events = EventLog::default();
+    let pool = ThreadPool::builder(1)
+        .events(events.clone())
+        .build()
+        .unwrap();
+
+    pool.execute(||()).unwrap();
+    pool.execute(||panic!("job failed")).unwrap();
+    finish(pool);
+
+    let expected = ["start 0", "end 0 None", "start 0", "end 0 Some(\"job failed\")", "shutdown 0"];
+    assert_eq!(expected.to_vec(), *events.0.lock().unwrap());
+}
+
+/// Pool events which panic as each job starts, killing the worker.
+struct PanickingEvents;
+
+impl PoolEvents for PanickingEvents {
+    fn on_job_start(&self, _worker: usize) {
+        panic!("hook failed");
+    }
+}
+
+#[test]
+fn dead_workers_do_not_stop_shutdown() {
+    let build = ||ThreadPool::builder(2)
+        .events(PanickingEvents)
+        .build()
+        .unwrap();
+    let kill = |pool: &ThreadPool|{
+        pool.execute(||()).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while pool.healthy_workers() == 2 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+    };
+
+    let pool = build();
+    kill(&pool);
+    assert!(pool.shutdown_timeout(Duration::from_secs(5)));
+
+    let pool = build();
+    kill(&pool);
+    drop(pool);
+}
+
+#[test]
+fn delayed_jobs_wait_for_their_time() {
+    let pool = ThreadPool::new(1).unwrap();
+    let (tx, rx) = mpsc::channel();
+    let start = Instant::now();
+
+    let late = tx.clone();
+    pool.execute_after(Duration::from_millis(100), move||late.send("late").unwrap());
+    pool.execute_after(Duration::from_millis(20), move||tx.send("early").unwrap());
+
+    assert_eq!("early", rx.recv().unwrap());
+    assert_eq!("late", rx.recv().unwrap());
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn cancelled_jobs_are_not_run() {
+    let pool = ThreadPool::new(1).unwrap();
+    let (tx, rx) = mpsc::channel::<()>();
+
+    let token = pool.execute_after(Duration::from_millis(50), move||tx.send(()).unwrap());
+    token.cancel();
+
+    assert!(token.is_cancelled());
+    assert!(rx.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[test]
+fn recurring_jobs_run_until_cancelled() {
+    let pool = ThreadPool::new(1).unwrap();
+    let (tx, rx) = mpsc::channel();
+
+    let token = pool.every(Duration::from_millis(10), move||{
+        let _ = tx.send(());
+    });
+
+    for _ in 0..3 {
+        rx.recv_timeout(Duration::from_secs(1)).unwrap();
+    }
+
+    token.cancel();
+    // Any run queued before cancelling may still arrive.
+    thread::sleep(Duration::from_millis(50));
+    while rx.try_recv().is_ok() {}
+
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+}
+
+#[test]
+#[should_panic(expected = "non-zero period")]
+fn recurring_jobs_need_a_period() {
+    let pool = ThreadPool::new(1).unwrap();
+    let _ = pool.every(Duration::ZERO, ||());
+}
+
+#[test]
+fn shutdown_discards_scheduled_jobs() {
+    let pool = ThreadPool::new(1).unwrap();
+    let (tx, rx) = mpsc::channel::<()>();
+
+    pool.execute_after(Duration::from_secs(60), move||tx.send(()).unwrap());
+
+    assert!(pool.shutdown_timeout(Duration::from_secs(1)));
+    assert!(rx.recv().is_err());
+}
+
+#[test]
+fn workers_are_named() {
+    let pool = ThreadPool::builder(2)
+        .name("test-worker")
+        .build()
+        .unwrap();
+
+    let name = pool.submit(||thread::current().name().map(String::from)).unwrap().join().unwrap();
+
+    assert_eq!("test-worker", pool.name());
+    assert!(matches!(name.as_deref(), Some("test-worker-0" | "test-worker-1")));
+    assert_eq!(DEFAULT_THREAD_NAME, ThreadPool::new(1).unwrap().name());
+}
+
+#[test]
+fn stats_count_each_workers_jobs() {
+    let pool = ThreadPool::new(1).unwrap();
+
+    pool.submit(||thread::sleep(Duration::from_millis(20))).unwrap().join().unwrap();
+    let _ = pool.submit(||panic!("job failed")).unwrap().join();
+
+    // Stats are updated just after a job's result is sent.
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while pool.stats()[0].jobs() < 2 && Instant::now() < deadline {
+        thread::yield_now();
+    }
+
+    let stats = pool.stats();
+
+    assert_eq!(1, stats.len());
+    assert_eq!(0, stats[0].id());
+    assert_eq!(2, stats[0].jobs());
+    assert!(stats[0].busy() >= Duration::from_millis(20));
+}
@@ -0,0 +1,99 @@
+use purple_blox::request::{Method, Request, RequestErrorKind};
+
+fn parse(raw: &[u8]) -> Result<Request, RequestErrorKind> {
+    Request::read_from(&mut &raw[..]).map_err(|err|*err.kind())
+}
+
+#[test]
+fn parses_head_and_body() {
+    let request = parse(b"POST /submit HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\n\r\nhello").unwrap();
+
+    assert_eq!(Method::Post, request.method());
+    assert_eq!("/submit", request.target());
+    assert_eq!("HTTP/1.1", request.version());
+    assert_eq!(Some("localhost"), request.header("Host"));
+    assert_eq!(b"hello", request.body());
+}
+
+#[test]
+fn body_ignores_trailing_bytes() {
+    let request = parse(b"POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\nhello").unwrap();
+
+    assert_eq!(b"he", request.body());
+}
+
+#[test]
+fn repeated_headers_combine() {
+    let request = parse(b"GET / HTTP/1.1\r\nAccept: text/html\r\naccept: text/plain\r\n\r\n").unwrap();
+
+    assert_eq!(Some("text/html, text/plain"), request.header("accept"));
+}
+
+#[test]
+fn malformed_requests() {
+    assert_eq!(Err(RequestErrorKind::MalformedRequestLine), parse(b"GET /\r\n\r\n").map(|_|()));
+    assert_eq!(Err(RequestErrorKind::UnknownMethod), parse(b"FETCH / HTTP/1.1\r\n\r\n").map(|_|()));
+    assert_eq!(Err(RequestErrorKind::UnsupportedVersion), parse(b"GET / HTTP/2\r\n\r\n").map(|_|()));
+    assert_eq!(Err(RequestErrorKind::MalformedHeader), parse(b"GET / HTTP/1.1\r\nHost\r\n\r\n").map(|_|()));
+    assert_eq!(Err(RequestErrorKind::InvalidContentLength), parse(b"GET / HTTP/1.1\r\nContent-Length: -1\r\n\r\n").map(|_|()));
+}
+
+#[test]
+fn transfer_encodings_are_refused() {
+    let chunked = b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n0\r\n\r\n";
+    assert_eq!(Err(RequestErrorKind::UnsupportedTransferEncoding), parse(chunked).map(|_|()));
+
+    let both = b"POST / HTTP/1.1\r\nContent-Length: 5\r\nTransfer-Encoding: chunked\r\n\r\nhello";
+    assert_eq!(Err(RequestErrorKind::ConflictingFraming), parse(both).map(|_|()));
+}
+
+#[test]
+fn incomplete_requests() {
+    assert_eq!(Err(RequestErrorKind::UnexpectedEof), parse(b"").map(|_|()));
+    assert_eq!(Err(RequestErrorKind::UnexpectedEof), parse(b"GET / HTTP/1.1\r\nHost: a").map(|_|()));
+    assert_eq!(Err(RequestErrorKind::UnexpectedEof), parse(b"POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nhi").map(|_|()));
+
+    // An oversized length is read as far as the stream goes, not allocated up front.
+    let oversized = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\nhi", usize::MAX);
+    assert_eq!(Err(RequestErrorKind::UnexpectedEof), parse(oversized.as_bytes()).map(|_|()));
+}
+
+#[test]
+fn query_parameters() {
+    let request = parse(b"GET /search?q=caf%C3%A9+au+lait&tag=a&tag=b&flag HTTP/1.1\r\n\r\n").unwrap();
+    let query = request.query();
+
+    assert_eq!("/search", request.path());
+    assert_eq!("café au lait", query["q"]);
+    assert_eq!("b", query["tag"]);
+    assert_eq!("", query["flag"]);
+    assert!(parse(b"GET / HTTP/1.1\r\n\r\n").unwrap().query().is_empty());
+}
+
+#[test]
+fn form_bodies() {
+    let request = parse(b"POST / HTTP/1.1\r\nContent-Type: application/x-www-form-urlencoded; charset=utf-8\r\nContent-Length: 23\r\n\r\nname=Ada&note=1%2B1%3D2").unwrap();
+    let form = request.form();
+
+    assert_eq!("Ada", form["name"]);
+    assert_eq!("1+1=2", form["note"]);
+
+    let request = parse(b"POST / HTTP/1.1\r\nContent-Type: text/plain\r\nContent-Length: 8\r\n\r\nname=Ada").unwrap();
+    assert!(request.form().is_empty());
+}
+
+#[test]
+fn size_limits() {
+    let limited = |raw: &[u8], max_header_size, max_body_size|{
+        Request::read_limited(&mut &raw[..], max_header_size, max_body_size)
+            .map(|_|())
+            .map_err(|err|*err.kind())
+    };
+    let raw = b"POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello";
+
+    // The head is 38 bytes, including the blank line ending it.
+    assert_eq!(Ok(()), limited(raw, 38, 5));
+    assert_eq!(Err(RequestErrorKind::HeadersTooLarge), limited(raw, 37, 5));
+    assert_eq!(Err(RequestErrorKind::HeadersTooLarge), limited(b"GET /a-very-long-path HTTP/1.1\r\n\r\n", 16, 0));
+    assert_eq!(Err(RequestErrorKind::BodyTooLarge), limited(raw, 38, 4));
+}
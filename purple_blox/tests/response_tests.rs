@@ -0,0 +1,50 @@
+use purple_blox::{
+    request::Request,
+    response::{Response, Status},
+    server::ServerError,
+};
+
+#[test]
+fn status_lines() {
+    assert_eq!("200 OK", Status::Ok.to_string());
+    assert_eq!("404 Not Found", Status::NotFound.to_string());
+    assert_eq!("431 Request Header Fields Too Large", Status::RequestHeaderFieldsTooLarge.to_string());
+}
+
+#[test]
+fn builder_sets_parts() {
+    let response = Response::new(Status::Created)
+        .header("Content-Type", "text/plain")
+        .header("X-Example", "1")
+        .body("created");
+
+    assert_eq!(Status::Created, response.status());
+    assert_eq!(Some("text/plain"), response.get_header("content-type"));
+    assert_eq!(Some("1"), response.get_header("x-example"));
+    assert_eq!(b"created", response.contents());
+}
+
+#[test]
+fn headers_replace_case_insensitively() {
+    let mut response = Response::ok()
+        .header("Content-Type", "text/plain")
+        .header("content-type", "text/html");
+
+    assert_eq!(1, response.headers().len());
+    assert_eq!(Some("text/html"), response.get_header("Content-Type"));
+
+    response.set_status(Status::NotFound);
+    assert_eq!(Status::NotFound, response.status());
+}
+
+#[test]
+fn request_errors_agree_with_server_errors() {
+    let statuses = |raw: &[u8]|{
+        let err = Request::read_limited(&mut &raw[..], 1024, 4).unwrap_err();
+        (Response::from(err.clone()).status(), ServerError::from(err).status())
+    };
+
+    assert_eq!((Status::BadRequest, Status::BadRequest), statuses(b"GET /\r\n\r\n"));
+    assert_eq!((Status::PayloadTooLarge, Status::PayloadTooLarge), statuses(b"POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello"));
+    assert_eq!((Status::NotImplemented, Status::NotImplemented), statuses(b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n"));
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use purple_blox::{
+    request::{Method, Request},
+    response::{Response, Status},
+    router::Router,
+};
+use common::request;
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router.get("/", |_: &Request|Response::ok().body("index"))
+        .get("/posts/:id", |req: &Request|Response::ok().body(format!("post {}", req.param("id").unwrap())))
+        .get("/posts/:id/comments/:comment", |req: &Request|{
+            Response::ok().body(format!("{}/{}", req.param("id").unwrap(), req.param("comment").unwrap()))
+        })
+        .post("/posts", |req: &Request|Response::ok().body(req.body().to_vec()));
+
+    router
+}
+
+#[test]
+fn literal_routes() {
+    let response = router().handle(request("GET / HTTP/1.1\r\n\r\n"));
+
+    assert_eq!(Status::Ok, response.status());
+    assert_eq!(b"index", response.contents());
+}
+
+#[test]
+fn path_parameters() {
+    let router = router();
+
+    assert_eq!(b"post 7", router.handle(request("GET /posts/7 HTTP/1.1\r\n\r\n")).contents());
+    assert_eq!(b"post 7", router.handle(request("GET /posts/7?sort=new HTTP/1.1\r\n\r\n")).contents());
+    assert_eq!(b"7/3", router.handle(request("GET /posts/7/comments/3 HTTP/1.1\r\n\r\n")).contents());
+}
+
+#[test]
+fn routes_by_method() {
+    let response = router().handle(request("POST /posts HTTP/1.1\r\nContent-Length: 3\r\n\r\nnew"));
+
+    assert_eq!(b"new", response.contents());
+}
+
+#[test]
+fn unmatched_requests_fall_back() {
+    let mut router = router();
+
+    assert_eq!(Status::NotFound, router.handle(request("GET /comments HTTP/1.1\r\n\r\n")).status());
+    assert_eq!(Status::NotFound, router.handle(request("GET /posts/7/extra HTTP/1.1\r\n\r\n")).status());
+
+    router.fallback(|req: &Request|Response::not_found().body(req.path().to_owned()));
+    assert_eq!(b"/missing", router.handle(request("DELETE /missing HTTP/1.1\r\n\r\n")).contents());
+}
+
+#[test]
+fn unsupported_methods_are_not_allowed() {
+    let response = router().handle(request("DELETE /posts/7 HTTP/1.1\r\n\r\n"));
+
+    assert_eq!(Status::MethodNotAllowed, response.status());
+    assert_eq!(Some("GET, HEAD, OPTIONS"), response.get_header("Allow"));
+}
+
+#[test]
+fn options_lists_allowed_methods() {
+    let router = router();
+    let response = router.handle(request("OPTIONS /posts HTTP/1.1\r\n\r\n"));
+
+    assert_eq!(Status::NoContent, response.status());
+    assert_eq!(Some("POST, OPTIONS"), response.get_header("Allow"));
+
+    let response = router.handle(request("OPTIONS * HTTP/1.1\r\n\r\n"));
+    assert_eq!(Some("GET, POST, HEAD, OPTIONS"), response.get_header("Allow"));
+    assert!(router.allowed_methods("/missing").is_empty());
+}
+
+#[test]
+fn head_requests_use_get_routes() {
+    let mut router = router();
+
+    assert_eq!(b"post 7", router.handle(request("HEAD /posts/7 HTTP/1.1\r\n\r\n")).contents());
+
+    router.route(Method::Head, "/", |_: &Request|Response::ok().body("head"));
+    assert_eq!(b"head", router.handle(request("HEAD / HTTP/1.1\r\n\r\n")).contents());
+}
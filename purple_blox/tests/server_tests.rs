@@ -0,0 +1,511 @@
+mod common;
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use purple_blox::{
+    config::ServerConfig,
+    request::Request,
+    response::{Response, Status},
+    router::Router,
+    server::{Server, ServerError, ServerErrorKind},
+};
+use common::TempDir;
+
+/// Runs the server on an ephemeral port, in the background.
+fn spawn(router: Router, config: ServerConfig) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    thread::spawn(move||purple_blox::run(listener, router, config));
+    addr
+}
+
+/// Sends a `GET` request for `path`, returning the raw response.
+fn get(addr: SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn single_threaded_mode() {
+    let mut router = Router::new();
+    router.get("/", |_: &Request|Response::ok().body("serial"));
+
+    let addr = spawn(router, ServerConfig { threads: 0, ..Default::default() });
+
+    assert!(get(addr, "/").ends_with("serial"));
+    assert!(get(addr, "/missing").starts_with("HTTP/1.1 404 Not Found"));
+}
+
+#[test]
+fn concurrent_requests() {
+    const CLIENTS: usize = 4;
+
+    // Each request waits for every other to arrive,
+    // which only succeeds if they're handled concurrently.
+    let arrived = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&arrived);
+
+    let mut router = Router::new();
+    router.get("/wait", move |_: &Request|{
+        counter.fetch_add(1, Ordering::SeqCst);
+
+        let start = Instant::now();
+        while counter.load(Ordering::SeqCst) < CLIENTS && start.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        match counter.load(Ordering::SeqCst) >= CLIENTS {
+            true => Response::ok().body("together"),
+            false => Response::internal_server_error(),
+        }
+    });
+
+    let addr = spawn(router, ServerConfig { threads: CLIENTS, ..Default::default() });
+
+    let clients: Vec<_> = (0..CLIENTS)
+        .map(|_|thread::spawn(move||get(addr, "/wait")))
+        .collect();
+
+    for client in clients {
+        assert!(client.join().unwrap().ends_with("together"));
+    }
+    assert_eq!(CLIENTS, arrived.load(Ordering::SeqCst));
+}
+
+#[test]
+fn shutdown_drains_in_flight_connections() {
+    let mut router = Router::new();
+    router.get("/slow", |_: &Request|{
+        thread::sleep(Duration::from_millis(200));
+        Response::ok().body("finished")
+    });
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let server = Arc::new(Server::new(listener, router, Default::default()).unwrap());
+    let addr = server.local_addr().unwrap();
+
+    let running = {
+        let server = Arc::clone(&server);
+        thread::spawn(move||server.run())
+    };
+
+    let client = thread::spawn(move||get(addr, "/slow"));
+    thread::sleep(Duration::from_millis(50));
+
+    server.shutdown();
+    running.join().unwrap().unwrap();
+
+    assert!(client.join().unwrap().ends_with("finished"));
+    assert!(server.shutdown_handle().is_shutdown());
+}
+
+#[test]
+fn shutdown_times_out() {
+    let mut router = Router::new();
+    router.get("/stuck", |_: &Request|{
+        thread::sleep(Duration::from_secs(5));
+        Response::ok()
+    });
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let config = ServerConfig { threads: 1, shutdown_timeout: Duration::from_millis(100), ..Default::default() };
+    let server = Arc::new(Server::new(listener, router, config).unwrap());
+    let addr = server.local_addr().unwrap();
+    let handle = server.shutdown_handle();
+
+    let running = thread::spawn(move||server.run());
+
+    thread::spawn(move||get(addr, "/stuck"));
+    thread::sleep(Duration::from_millis(50));
+
+    let start = Instant::now();
+    handle.shutdown();
+    running.join().unwrap().unwrap();
+
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+/// Reads a single response from `reader`, using its `Content-Length`.
+fn read_response<R: BufRead>(reader: &mut R) -> String {
+    let mut head = String::new();
+    let mut length = 0;
+
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+
+        if let Some(x) = line.strip_prefix("Content-Length: ") {
+            length = x.trim().parse().unwrap();
+        }
+        head.push_str(&line);
+
+        if line == "\r\n" {
+            break;
+        }
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+    head + &String::from_utf8(body).unwrap()
+}
+
+#[test]
+fn keep_alive_reuses_connections() {
+    let mut router = Router::new();
+    router.get("/:n", |req: &Request|Response::ok().body(req.param("n").unwrap().to_owned()));
+
+    let addr = spawn(router, Default::default());
+    let stream = TcpStream::connect(addr).unwrap();
+    let mut reader = BufReader::new(&stream);
+
+    write!(&stream, "GET /1 HTTP/1.1\r\n\r\nGET /2 HTTP/1.1\r\n\r\n").unwrap();
+
+    let first = read_response(&mut reader);
+    assert!(first.contains("Connection: keep-alive"));
+    assert!(first.ends_with("1"));
+    assert!(read_response(&mut reader).ends_with("2"));
+
+    write!(&stream, "GET /3 HTTP/1.1\r\nConnection: close\r\n\r\n").unwrap();
+
+    let last = read_response(&mut reader);
+    assert!(last.contains("Connection: close"));
+    assert!(last.ends_with("3"));
+
+    // The server closes the connection after the final response.
+    assert_eq!(0, reader.read(&mut [0; 1]).unwrap());
+}
+
+#[test]
+fn http_1_0_closes_by_default() {
+    let addr = spawn(Router::new(), Default::default());
+    let mut stream = TcpStream::connect(addr).unwrap();
+
+    write!(stream, "GET / HTTP/1.0\r\n\r\n").unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.contains("Connection: close"));
+}
+
+#[test]
+fn idle_connections_time_out() {
+    let config = ServerConfig {
+        keep_alive_timeout: Duration::from_millis(100),
+        ..Default::default()
+    };
+    let addr = spawn(Router::new(), config);
+    let stream = TcpStream::connect(addr).unwrap();
+    let mut reader = BufReader::new(&stream);
+
+    write!(&stream, "GET / HTTP/1.1\r\n\r\n").unwrap();
+    read_response(&mut reader);
+
+    let start = Instant::now();
+    assert_eq!(0, reader.read(&mut [0; 1]).unwrap());
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+#[test]
+fn head_responses_have_no_body() {
+    let mut router = Router::new();
+    router.get("/", |_: &Request|Response::ok().body("hidden"));
+
+    let addr = spawn(router, Default::default());
+    let mut stream = TcpStream::connect(addr).unwrap();
+
+    write!(stream, "HEAD / HTTP/1.1\r\nConnection: close\r\n\r\n").unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.contains("Content-Length: 6"));
+    assert!(response.ends_with("\r\n\r\n"));
+}
+
+#[test]
+fn handlers_cannot_override_framing() {
+    let mut router = Router::new();
+    router.get("/bytes", |_: &Request|Response::ok()
+        .header("Content-Length", "100")
+        .header("Transfer-Encoding", "chunked")
+        .body("short"));
+    router.get("/stream", |_: &Request|Response::ok()
+        .header("Transfer-Encoding", "gzip")
+        .header("Content-Length", "4")
+        .stream(std::io::Cursor::new("long stream")));
+
+    let addr = spawn(router, Default::default());
+
+    let response = get(addr, "/bytes");
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert_eq!(1, head.matches("Content-Length").count());
+    assert!(head.contains("Content-Length: 5"));
+    assert!(!head.contains("Transfer-Encoding"));
+    assert_eq!("short", body);
+
+    // A stream's `Content-Length` is how it's framed, so is kept.
+    let response = get(addr, "/stream");
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.contains("Content-Length: 4"));
+    assert!(!head.contains("Transfer-Encoding"));
+    assert_eq!("long", body);
+}
+
+/// Decodes a chunked body, returning it and the bytes after its final chunk.
+fn dechunk(mut raw: &str) -> (String, &str) {
+    let mut body = String::new();
+
+    loop {
+        let (size, rest) = raw.split_once("\r\n").unwrap();
+        let size = usize::from_str_radix(size, 16).unwrap();
+
+        if size == 0 {
+            return (body, rest.strip_prefix("\r\n").unwrap());
+        }
+
+        body.push_str(&rest[..size]);
+        raw = rest[size..].strip_prefix("\r\n").unwrap();
+    }
+}
+
+fn streaming_router() -> Router {
+    let mut router = Router::new();
+    router.get("/stream", |_: &Request|{
+        let lines = (0..5000).flat_map(|x|format!("{}\n", x).into_bytes()).collect::<Vec<_>>();
+        Response::ok().stream(std::io::Cursor::new(lines))
+    });
+
+    router
+}
+
+#[test]
+fn streams_are_chunked() {
+    let expected: String = (0..5000).map(|x|format!("{}\n", x)).collect();
+    let addr = spawn(streaming_router(), Default::default());
+    let mut stream = TcpStream::connect(addr).unwrap();
+
+    write!(stream, "GET /stream HTTP/1.1\r\nConnection: close\r\n\r\n").unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.contains("Transfer-Encoding: chunked"));
+    assert!(!head.contains("Content-Length"));
+
+    let (body, rest) = dechunk(body);
+    assert_eq!(expected, body);
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn http_1_0_streams_until_close() {
+    let addr = spawn(streaming_router(), Default::default());
+    let mut stream = TcpStream::connect(addr).unwrap();
+
+    write!(stream, "GET /stream HTTP/1.0\r\nConnection: keep-alive\r\n\r\n").unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.contains("Connection: close"));
+    assert!(!head.contains("Transfer-Encoding"));
+    assert!(body.ends_with("4999\n"));
+}
+
+#[test]
+fn full_queues_are_unavailable() {
+    let (release, wait) = std::sync::mpsc::channel::<()>();
+    let wait = std::sync::Mutex::new(wait);
+
+    let mut router = Router::new();
+    router.get("/busy", move |_: &Request|{
+        let _ = wait.lock().unwrap().recv_timeout(Duration::from_secs(5));
+        Response::ok().body("done")
+    });
+
+    let addr = spawn(router, ServerConfig { threads: 1, queue_capacity: 1, ..Default::default() });
+
+    // The first connection occupies the worker, and the second fills the queue.
+    let busy: Vec<_> = (0..2)
+        .map(|_|{
+            let client = thread::spawn(move||get(addr, "/busy"));
+            thread::sleep(Duration::from_millis(100));
+            client
+        })
+        .collect();
+
+    // Refused connections are answered straight away, without reading the request.
+    let mut refused = String::new();
+    TcpStream::connect(addr).unwrap().read_to_string(&mut refused).unwrap();
+    assert!(refused.starts_with("HTTP/1.1 503 Service Unavailable"));
+
+    for client in busy {
+        release.send(()).unwrap();
+        assert!(client.join().unwrap().ends_with("done"));
+    }
+}
+
+#[test]
+fn panicking_handlers_are_server_errors() {
+    let mut router = Router::new();
+    router.get("/panic", |_: &Request|panic!("handler failed"))
+        .get("/", |_: &Request|Response::ok().body("still serving"));
+
+    let addr = spawn(router, ServerConfig { threads: 0, ..Default::default() });
+
+    assert!(get(addr, "/panic").starts_with("HTTP/1.1 500 Internal Server Error"));
+    assert!(get(addr, "/").ends_with("still serving"));
+}
+
+#[test]
+fn client_closing_mid_request() {
+    let mut router = Router::new();
+    router.get("/", |_: &Request|Response::ok().body("still serving"));
+
+    let addr = spawn(router, ServerConfig { threads: 0, ..Default::default() });
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(b"GET / HTTP/1.1\r\nHost: loc").unwrap();
+    drop(stream);
+
+    assert!(get(addr, "/").ends_with("still serving"));
+}
+
+#[test]
+fn server_errors_map_to_statuses() {
+    let status = |kind|ServerError::new(kind).status();
+
+    assert_eq!(Status::BadRequest, status(ServerErrorKind::BadRequest));
+    assert_eq!(Status::NotFound, status(ServerErrorKind::NotFound));
+    assert_eq!(Status::InternalServerError, status(ServerErrorKind::Internal));
+    assert_eq!(Status::NotImplemented, status(ServerErrorKind::NotImplemented));
+    assert_eq!(Status::RequestTimeout, status(ServerErrorKind::Io(std::io::ErrorKind::TimedOut)));
+    assert!(ServerError::new(ServerErrorKind::Io(std::io::ErrorKind::BrokenPipe)).is_disconnect());
+}
+
+/// Sends a `GET` request for `path` to `host`, returning the raw response.
+fn get_host(addr: SocketAddr, host: &str, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", path, host).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn virtual_hosts() {
+    let blog = TempDir::new("server_blog");
+    blog.write("post.txt", "a blog post");
+
+    let mut default = Router::new();
+    default.get("/", |_: &Request|Response::ok().body("default site"));
+
+    let mut wiki = Router::new();
+    wiki.get("/", |_: &Request|Response::ok().body("wiki"));
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut server = Server::new(listener, default, Default::default()).unwrap();
+    server.add_site("blog.local", &*blog)
+        .add_router("Wiki.Local", wiki);
+
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move||server.run());
+
+    assert!(get_host(addr, "blog.local", "/post.txt").ends_with("a blog post"));
+    assert!(get_host(addr, "BLOG.local:7878", "/post.txt").ends_with("a blog post"));
+    assert!(get_host(addr, "wiki.local", "/").ends_with("wiki"));
+    assert!(get_host(addr, "other.local", "/").ends_with("default site"));
+    assert!(get_host(addr, "other.local", "/post.txt").starts_with("HTTP/1.1 404 Not Found"));
+}
+
+#[test]
+fn rate_limited_clients() {
+    let mut router = Router::new();
+    router.get("/", |_: &Request|Response::ok().body("allowed"))
+        .middleware(purple_blox::middleware::rate_limit(2, Duration::from_secs(60)));
+
+    let addr = spawn(router, Default::default());
+
+    assert!(get(addr, "/").ends_with("allowed"));
+    assert!(get(addr, "/").ends_with("allowed"));
+
+    let limited = get(addr, "/");
+    assert!(limited.starts_with("HTTP/1.1 429 Too Many Requests"));
+    assert!(limited.contains("Retry-After: 60\r\n"));
+}
+
+#[test]
+fn oversized_requests() {
+    let mut router = Router::new();
+    router.post("/", |req: &Request|Response::ok().body(req.body().to_vec()));
+
+    let addr = spawn(router, ServerConfig { max_header_size: 128, max_body_size: 16, ..Default::default() });
+    let send = |raw: String|{
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(raw.as_bytes()).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    let headers = format!("POST / HTTP/1.1\r\nX-Padding: {}\r\n\r\n", "a".repeat(200));
+    assert!(send(headers).starts_with("HTTP/1.1 431 Request Header Fields Too Large"));
+
+    // The body is never sent, as the server refuses it from its length alone.
+    let body = String::from("POST / HTTP/1.1\r\nContent-Length: 1000000\r\n\r\n");
+    assert!(send(body).starts_with("HTTP/1.1 413 Payload Too Large"));
+
+    let fits = String::from("POST / HTTP/1.1\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello");
+    assert!(send(fits).ends_with("hello"));
+}
+
+#[test]
+fn pipelined_chunked_requests_are_refused() {
+    let mut router = Router::new();
+    router.post("/", |req: &Request|Response::ok().body(req.body().to_vec()));
+    router.get("/smuggled", |_: &Request|Response::ok().body("smuggled"));
+
+    let addr = spawn(router, Default::default());
+    let send = |raw: &str|{
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(raw.as_bytes()).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    // Were the chunked body left unread, the request hidden in it would be answered as the next one.
+    let smuggled = "GET /smuggled HTTP/1.1\r\n\r\n";
+    let chunked = format!(
+        "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n0\r\n\r\nGET / HTTP/1.1\r\n\r\n",
+        smuggled.len(),
+        smuggled,
+    );
+    let response = send(&chunked);
+    assert!(response.starts_with("HTTP/1.1 501 Not Implemented"));
+    assert!(response.contains("Connection: close"));
+    assert!(!response.contains("smuggled"));
+    assert_eq!(1, response.matches("HTTP/1.1").count());
+
+    let both = "POST / HTTP/1.1\r\nContent-Length: 5\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\nGET /smuggled HTTP/1.1\r\n\r\n";
+    let response = send(both);
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request"));
+    assert_eq!(1, response.matches("HTTP/1.1").count());
+}
@@ -0,0 +1,222 @@
+mod common;
+
+use std::fs;
+use purple_blox::{
+    response::{Response, Status},
+    static_files::StaticFiles,
+};
+use common::{request, TempDir};
+
+/// Creates a fresh site directory for a test,
+/// containing an index page, a binary file, and a nested directory.
+fn site(name: &str) -> TempDir {
+    let root = TempDir::new(name);
+    root.write("index.html", "<h1>Home</h1>")
+        .write("css/site.css", "h1 {}")
+        .write("logo.png", [0x89, b'P', b'N', b'G', 0xff, 0x00]);
+
+    root
+}
+
+fn get(files: &StaticFiles, path: &str) -> Response {
+    let raw = format!("GET {} HTTP/1.1\r\n\r\n", path);
+    files.respond(&request(&raw))
+}
+
+fn content_type(response: &Response) -> Option<&str> {
+    response.get_header("Content-Type")
+}
+
+#[test]
+fn serves_files_with_mime_types() {
+    let root = site("mime");
+    let files = StaticFiles::new(&*root);
+
+    let css = get(&files, "/css/site.css");
+    assert_eq!(Status::Ok, css.status());
+    assert_eq!(Some("text/css; charset=utf-8"), content_type(&css));
+
+    let png = get(&files, "/logo.png");
+    assert_eq!(&[0x89, b'P', b'N', b'G', 0xff, 0x00], png.contents());
+    assert_eq!(Some("image/png"), content_type(&png));
+}
+
+#[test]
+fn directories_serve_their_index() {
+    let root = site("index");
+    let files = StaticFiles::new(&*root);
+
+    assert_eq!(b"<h1>Home</h1>", get(&files, "/").contents());
+    assert_eq!(Status::NotFound, get(&files, "/css/").status());
+}
+
+#[test]
+fn missing_files_are_not_found() {
+    let root = site("missing");
+    let files = StaticFiles::new(&*root);
+
+    assert_eq!(Status::NotFound, get(&files, "/nope.html").status());
+}
+
+#[test]
+fn traversal_is_forbidden() {
+    let root = site("traversal");
+    let files = StaticFiles::new(&*root);
+
+    assert_eq!(Status::Forbidden, get(&files, "/../secret").status());
+    assert_eq!(Status::Forbidden, get(&files, "/css/../../secret").status());
+    assert_eq!(Status::Forbidden, get(&files, "/%2e%2e/secret").status());
+    assert_eq!(Status::Forbidden, get(&files, "/css/%2E%2E%2f%2e%2e/secret").status());
+}
+
+#[test]
+fn paths_are_percent_decoded() {
+    let root = site("decoding");
+    fs::write(root.join("my file.txt"), "spaced").unwrap();
+    fs::write(root.join("a+b.txt"), "plus").unwrap();
+    let files = StaticFiles::new(&*root);
+
+    assert_eq!(b"spaced", get(&files, "/my%20file.txt").contents());
+    assert_eq!(b"plus", get(&files, "/a+b.txt").contents());
+    assert_eq!(b"plus", get(&files, "/a%2Bb.txt").contents());
+    assert_eq!(b"h1 {}", get(&files, "/%63ss/site.css").contents());
+}
+
+#[test]
+fn streams_large_files() {
+    let root = site("large");
+    let size = purple_blox::static_files::STREAM_THRESHOLD + 1;
+    fs::write(root.join("large.bin"), vec![7; size as usize]).unwrap();
+
+    let files = StaticFiles::new(&*root);
+    let response = get(&files, "/large.bin");
+
+    assert_eq!(Status::Ok, response.status());
+    assert!(response.is_streaming());
+    assert_eq!(Some(size.to_string().as_str()), response.get_header("Content-Length"));
+    assert!(!get(&files, "/index.html").is_streaming());
+}
+
+fn get_range(files: &StaticFiles, path: &str, range: &str) -> Response {
+    let raw = format!("GET {} HTTP/1.1\r\nRange: {}\r\n\r\n", path, range);
+    files.respond(&request(&raw))
+}
+
+#[test]
+fn serves_byte_ranges() {
+    let root = site("ranges");
+    let files = StaticFiles::new(&*root);
+
+    assert_eq!(Some("bytes"), get(&files, "/index.html").get_header("Accept-Ranges"));
+
+    let middle = get_range(&files, "/index.html", "bytes=4-7");
+    assert_eq!(Status::PartialContent, middle.status());
+    assert_eq!(Some("bytes 4-7/13"), middle.get_header("Content-Range"));
+    assert_eq!(b"Home", middle.contents());
+
+    assert_eq!(b"h1>", get_range(&files, "/index.html", "bytes=10-").contents());
+    assert_eq!(b"1>", get_range(&files, "/index.html", "bytes=-2").contents());
+    assert_eq!(b"</h1>", get_range(&files, "/index.html", "bytes=8-100").contents());
+}
+
+#[test]
+fn unsatisfiable_and_ignored_ranges() {
+    let root = site("bad_ranges");
+    let files = StaticFiles::new(&*root);
+
+    let past_end = get_range(&files, "/index.html", "bytes=13-");
+    assert_eq!(Status::RangeNotSatisfiable, past_end.status());
+    assert_eq!(Some("bytes */13"), past_end.get_header("Content-Range"));
+
+    for range in ["bytes=0-1,4-5", "bytes=5-2", "lines=1-2", "bytes=x-"] {
+        let response = get_range(&files, "/index.html", range);
+        assert_eq!(Status::Ok, response.status());
+        assert_eq!(b"<h1>Home</h1>", response.contents());
+    }
+}
+
+fn get_with(files: &StaticFiles, path: &str, header: &str, value: &str) -> Response {
+    let raw = format!("GET {} HTTP/1.1\r\n{}: {}\r\n\r\n", path, header, value);
+    files.respond(&request(&raw))
+}
+
+#[test]
+fn conditional_requests() {
+    let root = site("conditional");
+    let files = StaticFiles::new(&*root);
+    let response = get(&files, "/index.html");
+    let etag = response.get_header("ETag").unwrap();
+    let last_modified = response.get_header("Last-Modified").unwrap();
+
+    assert!(etag.starts_with("W/\""));
+
+    let cached = get_with(&files, "/index.html", "If-None-Match", &format!("\"other\", {}", etag));
+    assert_eq!(Status::NotModified, cached.status());
+    assert_eq!(Some(etag), cached.get_header("ETag"));
+    assert!(cached.contents().is_empty());
+
+    assert_eq!(Status::Ok, get_with(&files, "/index.html", "If-None-Match", "W/\"other\"").status());
+    assert_eq!(Status::NotModified, get_with(&files, "/index.html", "If-Modified-Since", last_modified).status());
+    assert_eq!(Status::Ok, get_with(&files, "/index.html", "If-Modified-Since", "Thu, 01 Jan 1970 00:00:00 GMT").status());
+}
+
+#[test]
+fn stale_ranges_serve_the_whole_file() {
+    let root = site("if_range");
+    let files = StaticFiles::new(&*root);
+    let last_modified = get(&files, "/index.html").get_header("Last-Modified").unwrap().to_owned();
+
+    let raw = |if_range: &str|format!("GET /index.html HTTP/1.1\r\nRange: bytes=0-3\r\nIf-Range: {}\r\n\r\n", if_range);
+    let respond = |raw: String|files.respond(&request(&raw));
+
+    assert_eq!(Status::PartialContent, respond(raw(&last_modified)).status());
+    assert_eq!(Status::Ok, respond(raw("Thu, 01 Jan 1970 00:00:00 GMT")).status());
+}
+
+#[test]
+fn directory_listings() {
+    let root = site("listing");
+    fs::create_dir_all(root.join("css/fonts")).unwrap();
+    fs::write(root.join("css/<b>.css"), "b {}").unwrap();
+    fs::write(root.join("css/.hidden"), "").unwrap();
+
+    assert_eq!(Status::NotFound, get(&StaticFiles::new(&*root), "/css/").status());
+
+    let files = StaticFiles::new(&*root).with_listing(true);
+    let listing = get(&files, "/css");
+    let html = String::from_utf8(listing.contents().to_vec()).unwrap();
+
+    assert_eq!(Status::Ok, listing.status());
+    assert_eq!(Some("text/html; charset=utf-8"), content_type(&listing));
+    assert!(html.contains("<title>Index of /css/</title>"));
+    assert!(html.contains("<a href=\"../\">../</a>"));
+    assert!(html.contains("<a href=\"/css/%3Cb%3E.css\">&lt;b&gt;.css</a></td><td>4</td>"));
+    assert!(!html.contains(".hidden"));
+
+    // Directories come first, then files, sorted by name.
+    let fonts = html.find("fonts/").unwrap();
+    let bold = html.find("&lt;b&gt;.css").unwrap();
+    let site = html.find("site.css").unwrap();
+    assert!(fonts < bold && bold < site);
+
+    // Directories with an index page are still served it.
+    assert_eq!(b"<h1>Home</h1>", get(&files, "/").contents());
+}
+
+#[test]
+fn listing_links_are_percent_encoded() {
+    let root = site("listing_links");
+    fs::create_dir_all(root.join("my docs/a#b")).unwrap();
+    fs::write(root.join("my docs/50% off?.txt"), "sale").unwrap();
+    let files = StaticFiles::new(&*root).with_listing(true);
+
+    let listing = get(&files, "/my%20docs/");
+    let html = String::from_utf8(listing.contents().to_vec()).unwrap();
+
+    assert!(html.contains("<title>Index of /my docs/</title>"));
+    assert!(html.contains("<a href=\"/my%20docs/50%25%20off%3F.txt\">50% off?.txt</a>"));
+    assert!(html.contains("<a href=\"/my%20docs/a%23b/\">a#b/</a>"));
+
+    // The links lead back to the files they name.
+    assert_eq!(b"sale", get(&files, "/my%20docs/50%25%20off%3F.txt").contents());
+}
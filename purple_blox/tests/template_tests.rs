@@ -0,0 +1,74 @@
+mod common;
+
+use purple_blox::{
+    response::{Response, Status},
+    template::{Context, Template, TemplateError, TemplateErrorKind},
+};
+use common::TempDir;
+
+/// Creates a directory of templates, unique to the test.
+fn templates(name: &str) -> TempDir {
+    let root = TempDir::new(&format!("template_{}", name));
+    root.write("page.html", "{{> partials/header.html}}<main>{{&body}}</main>")
+        .write("partials/header.html", "<h1>{{ title }}</h1>")
+        .write("loop.html", "{{>loop.html}}");
+
+    root
+}
+
+#[test]
+fn variables_are_substituted() {
+    let template = Template::parse("{{greeting}}, {{ name }}!").unwrap();
+    let ctx = Context::new()
+        .with("greeting", "Hello")
+        .with("name", "world");
+
+    assert_eq!("Hello, world!", template.apply(&ctx).unwrap());
+}
+
+#[test]
+fn variables_are_escaped() {
+    let template = Template::parse("{{entry}} {{&entry}}").unwrap();
+    let ctx = Context::new().with("entry", "<b>\"hi\" & 'bye'</b>");
+
+    assert_eq!(
+        "&lt;b&gt;&quot;hi&quot; &amp; &#39;bye&#39;&lt;/b&gt; <b>\"hi\" & 'bye'</b>",
+        template.apply(&ctx).unwrap()
+    );
+}
+
+#[test]
+fn includes_are_resolved() {
+    let root = templates("includes");
+    let ctx = Context::new()
+        .with("title", "Guestbook")
+        .with("body", "<p>First!</p>");
+
+    assert_eq!("<h1>Guestbook</h1><main><p>First!</p></main>", Template::render(root.join("page.html"), &ctx).unwrap());
+}
+
+#[test]
+fn template_errors() {
+    let root = templates("errors");
+    let kind = |result: Result<String, TemplateError>|*result.unwrap_err().kind();
+
+    assert_eq!(TemplateErrorKind::Unclosed, *Template::parse("{{name").unwrap_err().kind());
+    assert_eq!(TemplateErrorKind::MissingVariable, kind(Template::parse("{{name}}").unwrap().apply(&Context::new())));
+    assert_eq!(TemplateErrorKind::IncludeDepth, kind(Template::render(root.join("loop.html"), &Context::new())));
+    assert!(matches!(kind(Template::render(root.join("missing.html"), &Context::new())), TemplateErrorKind::Io(_)));
+}
+
+#[test]
+fn template_responses() {
+    let root = templates("responses");
+    let ctx = Context::new()
+        .with("title", "Visits")
+        .with("body", 42);
+
+    let response = Response::template(root.join("page.html"), &ctx);
+    assert_eq!(Status::Ok, response.status());
+    assert_eq!(Some("text/html; charset=utf-8"), response.get_header("content-type"));
+    assert_eq!(b"<h1>Visits</h1><main>42</main>", response.contents());
+
+    assert_eq!(Status::InternalServerError, Response::template(root.join("page.html"), &Context::new()).status());
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use purple_blox::{
+    config::ServerConfig,
+    request::Request,
+    response::{Response, Status},
+    router::Router,
+    testing::TestServer,
+};
+use common::TempDir;
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router.get("/", |_: &Request|Response::ok().body("home"))
+        .post("/echo", |req: &Request|Response::ok().body(req.body().to_vec()))
+        .get("/stream", |_: &Request|Response::ok().stream(std::io::Cursor::new(vec![b'x'; 20_000])));
+
+    router
+}
+
+#[test]
+fn routes_requests() {
+    let server = TestServer::start(router()).unwrap();
+
+    let home = server.get("/").unwrap();
+    assert_eq!(Status::Ok, home.status());
+    assert_eq!(b"home", home.contents());
+
+    assert_eq!(b"posted", server.post("/echo", "posted").unwrap().contents());
+    assert_eq!(Status::MethodNotAllowed, server.post("/", "").unwrap().status());
+    assert_eq!(Status::NotFound, server.get("/missing").unwrap().status());
+}
+
+#[test]
+fn decodes_chunked_bodies() {
+    let server = TestServer::start(router()).unwrap();
+    let response = server.get("/stream").unwrap();
+
+    assert_eq!(Some("chunked"), response.get_header("transfer-encoding"));
+    assert_eq!(vec![b'x'; 20_000], response.contents());
+}
+
+#[test]
+fn serves_static_files() {
+    let site = TempDir::new("testing");
+    site.write("index/index.html", "<h1>Index</h1>")
+        .write("notes.txt", "some notes");
+
+    let config = ServerConfig { site_dir: site.to_path_buf(), error_pages: Default::default(), ..Default::default() };
+    let server = TestServer::with_config(purple_blox::site_router(&config), config).unwrap();
+
+    assert_eq!(b"<h1>Index</h1>", server.get("/").unwrap().contents());
+
+    let notes = server.get("/notes.txt").unwrap();
+    assert_eq!(b"some notes", notes.contents());
+    assert_eq!(Some("text/plain; charset=utf-8"), notes.get_header("content-type"));
+
+    assert_eq!(Status::NotFound, server.get("/missing.txt").unwrap().status());
+}